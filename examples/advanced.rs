@@ -21,12 +21,12 @@ fn main() -> Result<(), StatError> {
     let mut base = StatResolver::new();
     let hp_id = StatId::from_str("HP");
 
-    base.register_source(hp_id.clone(), Box::new(ConstantSource(100.0)));
+    base.register_source(hp_id.clone(), Box::new(ConstantSource::new(100.0)));
     println!("Base resolver: HP = 100 (base)");
 
     // Fork the resolver
     let mut fork = base.fork();
-    fork.register_source(hp_id.clone(), Box::new(ConstantSource(50.0)));
+    fork.register_source(hp_id.clone(), Box::new(ConstantSource::new(50.0)));
     println!("Fork resolver: HP = 100 (base) + 50 (fork) = 150");
 
     let context = StatContext::new();
@@ -49,12 +49,12 @@ fn main() -> Result<(), StatError> {
     let mp_id = StatId::from_str("MP");
 
     // Register all stats
-    resolver.register_source(str_id.clone(), Box::new(ConstantSource(10.0)));
-    resolver.register_source(dex_id.clone(), Box::new(ConstantSource(15.0)));
-    resolver.register_source(atk_id.clone(), Box::new(ConstantSource(50.0)));
-    resolver.register_source(crit_id.clone(), Box::new(ConstantSource(5.0)));
-    resolver.register_source(hp_id.clone(), Box::new(ConstantSource(100.0)));
-    resolver.register_source(mp_id.clone(), Box::new(ConstantSource(50.0)));
+    resolver.register_source(str_id.clone(), Box::new(ConstantSource::new(10.0)));
+    resolver.register_source(dex_id.clone(), Box::new(ConstantSource::new(15.0)));
+    resolver.register_source(atk_id.clone(), Box::new(ConstantSource::new(50.0)));
+    resolver.register_source(crit_id.clone(), Box::new(ConstantSource::new(5.0)));
+    resolver.register_source(hp_id.clone(), Box::new(ConstantSource::new(100.0)));
+    resolver.register_source(mp_id.clone(), Box::new(ConstantSource::new(50.0)));
 
     // ATK depends on STR
     resolver.register_transform(
@@ -89,7 +89,7 @@ fn main() -> Result<(), StatError> {
     let mut resolver = StatResolver::new();
     let atk_id = StatId::from_str("ATK");
 
-    resolver.register_source(atk_id.clone(), Box::new(ConstantSource(100.0)));
+    resolver.register_source(atk_id.clone(), Box::new(ConstantSource::new(100.0)));
 
     // Register transforms in "wrong" order - phases will determine actual order
     println!("Registering transforms (order: clamp, multiply, add)");
@@ -114,7 +114,7 @@ fn main() -> Result<(), StatError> {
     let mut resolver = StatResolver::new();
     let crit_id = StatId::from_str("CRIT_CHANCE");
 
-    resolver.register_source(crit_id.clone(), Box::new(ConstantSource(0.95))); // 95% crit
+    resolver.register_source(crit_id.clone(), Box::new(ConstantSource::new(0.95))); // 95% crit
 
     // Register multiple clamp transforms with MinMax stack rule
     // When multiple clamps apply: effective_min = max(all mins), effective_max = min(all maxes)
@@ -155,7 +155,7 @@ fn main() -> Result<(), StatError> {
     // Demonstrate min-only and max-only clamps
     let mut resolver2 = StatResolver::new();
     let move_speed_id = StatId::from_str("MOVE_SPEED");
-    resolver2.register_source(move_speed_id.clone(), Box::new(ConstantSource(50.0)));
+    resolver2.register_source(move_speed_id.clone(), Box::new(ConstantSource::new(50.0)));
 
     println!("Registering min-only and max-only clamps:");
     resolver2.register_transform_with_rule(
@@ -182,7 +182,7 @@ fn main() -> Result<(), StatError> {
     let mut base = StatResolver::new();
     let atk_id = StatId::from_str("ATK");
 
-    base.register_source(atk_id.clone(), Box::new(ConstantSource(100.0)));
+    base.register_source(atk_id.clone(), Box::new(ConstantSource::new(100.0)));
     base.register_transform(atk_id.clone(), Box::new(MultiplicativeTransform::new(1.5)));
 
     println!("Base: ATK = 100 * 1.5 = 150");
@@ -191,8 +191,8 @@ fn main() -> Result<(), StatError> {
     let mut fork1 = base.fork();
     let mut fork2 = base.fork();
 
-    fork1.register_source(atk_id.clone(), Box::new(ConstantSource(10.0)));
-    fork2.register_source(atk_id.clone(), Box::new(ConstantSource(20.0)));
+    fork1.register_source(atk_id.clone(), Box::new(ConstantSource::new(10.0)));
+    fork2.register_source(atk_id.clone(), Box::new(ConstantSource::new(20.0)));
 
     println!("Fork 1: ATK = (100 + 10) * 1.5 = 165");
     println!("Fork 2: ATK = (100 + 20) * 1.5 = 180");
@@ -18,10 +18,10 @@ fn main() -> Result<(), StatError> {
 
     // Register multiple sources (they will be summed)
     println!("Registering sources for HP:");
-    resolver.register_source(hp_id.clone(), Box::new(ConstantSource(100.0)));
+    resolver.register_source(hp_id.clone(), Box::new(ConstantSource::new(100.0)));
     println!("  - Base HP: 100");
 
-    resolver.register_source(hp_id.clone(), Box::new(ConstantSource(50.0)));
+    resolver.register_source(hp_id.clone(), Box::new(ConstantSource::new(50.0)));
     println!("  - Item bonus: +50");
 
     // Register a transform (percentage modifier)
@@ -29,9 +29,9 @@ fn main() -> Result<(), StatError> {
     let mut base_resolver = StatResolver::new();
 
     // Register base HP source
-    base_resolver.register_source(hp_id.clone(), Box::new(ConstantSource(1000.0)));
-    base_resolver.register_source(attack_id.clone(), Box::new(ConstantSource(100.0)));
-    base_resolver.register_source(crit_chance_id.clone(), Box::new(ConstantSource(0.5)));
+    base_resolver.register_source(hp_id.clone(), Box::new(ConstantSource::new(1000.0)));
+    base_resolver.register_source(attack_id.clone(), Box::new(ConstantSource::new(100.0)));
+    base_resolver.register_source(crit_chance_id.clone(), Box::new(ConstantSource::new(0.5)));
 
     // ========================================================================
     // Example 1: Basic Bonus Usage
@@ -40,7 +40,7 @@ fn main() -> Result<(), StatError> {
     println!("--- Example 1: Basic Bonus Usage ---");
 
     // Define bonuses declaratively
-    let bonuses = vec![
+    let bonuses = [
         Bonus::add(hp_id.clone())
             .flat(50.0)
             .in_phase(TransformPhase::Custom(3)),
@@ -50,7 +50,7 @@ fn main() -> Result<(), StatError> {
     ];
 
     // Compile once (all branching happens here)
-    let compiled: Vec<_> = bonuses.iter().map(|b| compile_bonus::<f64>(b)).collect();
+    let compiled: Vec<_> = bonuses.iter().map(compile_bonus::<StatValue>).collect();
 
     // Apply to resolver fork
     let mut fork = base_resolver.fork();
@@ -77,9 +77,9 @@ fn main() -> Result<(), StatError> {
 
     // Setup: Base HP = 1000, Item phase adds +200 HP and +10% HP
     let mut override_resolver = StatResolver::new();
-    override_resolver.register_source(hp_id.clone(), Box::new(ConstantSource(1000.0)));
+    override_resolver.register_source(hp_id.clone(), Box::new(ConstantSource::new(1000.0)));
 
-    let item_bonuses = vec![
+    let item_bonuses = [
         Bonus::add(hp_id.clone())
             .flat(200.0)
             .in_phase(TransformPhase::Custom(3)),
@@ -89,7 +89,7 @@ fn main() -> Result<(), StatError> {
     ];
     let item_compiled: Vec<_> = item_bonuses
         .iter()
-        .map(|b| compile_bonus::<f64>(b))
+        .map(compile_bonus::<StatValue>)
         .collect();
 
     let mut item_fork = override_resolver.fork();
@@ -102,7 +102,7 @@ fn main() -> Result<(), StatError> {
     );
 
     // Buff phase: Override HP = 500, then +50% HP
-    let buff_bonuses = vec![
+    let buff_bonuses = [
         Bonus::r#override(hp_id.clone(), 500.0).in_phase(TransformPhase::Custom(4)),
         Bonus::mul(hp_id.clone())
             .percent(0.50)
@@ -110,7 +110,7 @@ fn main() -> Result<(), StatError> {
     ];
     let buff_compiled: Vec<_> = buff_bonuses
         .iter()
-        .map(|b| compile_bonus::<f64>(b))
+        .map(compile_bonus::<StatValue>)
         .collect();
 
     let mut buff_fork = item_fork.fork();
@@ -135,13 +135,16 @@ fn main() -> Result<(), StatError> {
         Bonus::clamp_max(crit_chance_id.clone(), 0.75).in_phase(TransformPhase::Final);
 
     let mut clamp_fork = base_resolver.fork();
-    apply_compiled_bonus(&mut clamp_fork, &compile_bonus::<f64>(&clamp_bonus));
+    apply_compiled_bonus(&mut clamp_fork, &compile_bonus::<StatValue>(&clamp_bonus));
 
     // Test with high crit chance
     let mut high_crit_resolver = StatResolver::new();
-    high_crit_resolver.register_source(crit_chance_id.clone(), Box::new(ConstantSource(1.0))); // 100% crit
+    high_crit_resolver.register_source(crit_chance_id.clone(), Box::new(ConstantSource::new(1.0))); // 100% crit
     let mut high_crit_fork = high_crit_resolver.fork();
-    apply_compiled_bonus(&mut high_crit_fork, &compile_bonus::<f64>(&clamp_bonus));
+    apply_compiled_bonus(
+        &mut high_crit_fork,
+        &compile_bonus::<StatValue>(&clamp_bonus),
+    );
 
     let clamped_stats = high_crit_fork.resolve(&crit_chance_id, &context)?;
     println!(
@@ -184,10 +187,7 @@ fn main() -> Result<(), StatError> {
     let mut all_bonuses = Vec::new();
     all_bonuses.extend(sword.bonuses);
     all_bonuses.extend(armor.bonuses);
-    let all_compiled: Vec<_> = all_bonuses
-        .iter()
-        .map(|b| compile_bonus::<f64>(b))
-        .collect();
+    let all_compiled: Vec<_> = all_bonuses.iter().map(compile_bonus::<StatValue>).collect();
 
     // Apply to character
     let mut equipped_fork = base_resolver.fork();
@@ -27,21 +27,21 @@ fn main() -> Result<(), StatError> {
 
     // Base attributes
     println!("Base Attributes:");
-    resolver.register_source(str_id.clone(), Box::new(ConstantSource(20.0)));
+    resolver.register_source(str_id.clone(), Box::new(ConstantSource::new(20.0)));
     println!("  STR: 20");
 
-    resolver.register_source(dex_id.clone(), Box::new(ConstantSource(15.0)));
+    resolver.register_source(dex_id.clone(), Box::new(ConstantSource::new(15.0)));
     println!("  DEX: 15");
 
-    resolver.register_source(int_id.clone(), Box::new(ConstantSource(25.0)));
+    resolver.register_source(int_id.clone(), Box::new(ConstantSource::new(25.0)));
     println!("  INT: 25");
 
     // HP: Base + STR scaling + item bonuses
     println!("\nHP Calculation:");
-    resolver.register_source(hp_id.clone(), Box::new(ConstantSource(100.0)));
+    resolver.register_source(hp_id.clone(), Box::new(ConstantSource::new(100.0)));
     println!("  Base: 100");
 
-    resolver.register_source(hp_id.clone(), Box::new(ConstantSource(50.0)));
+    resolver.register_source(hp_id.clone(), Box::new(ConstantSource::new(50.0)));
     println!("  Item bonus: +50");
 
     resolver.register_transform(
@@ -55,7 +55,7 @@ fn main() -> Result<(), StatError> {
 
     // MP: Base + INT scaling
     println!("\nMP Calculation:");
-    resolver.register_source(mp_id.clone(), Box::new(ConstantSource(50.0)));
+    resolver.register_source(mp_id.clone(), Box::new(ConstantSource::new(50.0)));
     println!("  Base: 50");
 
     resolver.register_transform(
@@ -66,7 +66,7 @@ fn main() -> Result<(), StatError> {
 
     // ATK: Base + STR scaling + DEX scaling
     println!("\nATK Calculation:");
-    resolver.register_source(atk_id.clone(), Box::new(ConstantSource(30.0)));
+    resolver.register_source(atk_id.clone(), Box::new(ConstantSource::new(30.0)));
     println!("  Base: 30");
 
     resolver.register_transform(
@@ -86,7 +86,7 @@ fn main() -> Result<(), StatError> {
 
     // CRIT: Base + DEX scaling
     println!("\nCRIT Calculation:");
-    resolver.register_source(crit_id.clone(), Box::new(ConstantSource(5.0)));
+    resolver.register_source(crit_id.clone(), Box::new(ConstantSource::new(5.0)));
     println!("  Base: 5");
 
     resolver.register_transform(
@@ -97,7 +97,7 @@ fn main() -> Result<(), StatError> {
 
     // DPS: Depends on ATK and CRIT
     println!("\nDPS Calculation:");
-    resolver.register_source(dps_id.clone(), Box::new(ConstantSource(0.0)));
+    resolver.register_source(dps_id.clone(), Box::new(ConstantSource::new(0.0)));
     println!("  Base: 0");
 
     resolver.register_transform(
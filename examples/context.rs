@@ -4,6 +4,7 @@
 //! - Using StatContext to pass game state
 //! - Conditional transforms based on context
 //! - Different stat values in different contexts
+//! - Declaring context keys so cached stats follow context changes
 
 use zzstat::source::ConstantSource;
 use zzstat::transform::{ConditionalTransform, MultiplicativeTransform};
@@ -18,7 +19,7 @@ fn main() -> Result<(), StatError> {
     println!("=== Setting up stats with context-dependent transforms ===\n");
 
     // Base ATK
-    resolver.register_source(atk_id.clone(), Box::new(ConstantSource(100.0)));
+    resolver.register_source(atk_id.clone(), Box::new(ConstantSource::new(100.0)));
     println!("ATK base: 100");
 
     // ATK gets +50% bonus in combat
@@ -26,12 +27,13 @@ fn main() -> Result<(), StatError> {
         |ctx| ctx.get::<bool>("in_combat").unwrap_or(false),
         Box::new(MultiplicativeTransform::new(1.5)),
         "Combat bonus +50%",
-    );
+    )
+    .with_context_keys(["in_combat"]);
     resolver.register_transform(atk_id.clone(), Box::new(combat_bonus));
     println!("ATK: +50% when in combat");
 
     // Base DEF
-    resolver.register_source(def_id.clone(), Box::new(ConstantSource(80.0)));
+    resolver.register_source(def_id.clone(), Box::new(ConstantSource::new(80.0)));
     println!("DEF base: 80");
 
     // DEF gets +25% bonus in PvP zones
@@ -43,7 +45,8 @@ fn main() -> Result<(), StatError> {
         },
         Box::new(MultiplicativeTransform::new(1.25)),
         "PvP zone bonus +25%",
-    );
+    )
+    .with_context_keys(["zone_type"]);
     resolver.register_transform(def_id.clone(), Box::new(pvp_bonus));
     println!("DEF: +25% in PvP zones");
 
@@ -65,10 +68,8 @@ fn main() -> Result<(), StatError> {
     context2.set("in_combat", true);
     context2.set("zone_type", "normal");
 
-    // Invalidate cache to force recalculation
-    resolver.invalidate(&atk_id);
-    resolver.invalidate(&def_id);
-
+    // No manual invalidation needed: ATK declared "in_combat", so the
+    // resolver notices the change and recalculates it
     let atk2 = resolver.resolve(&atk_id, &context2)?;
     let def2 = resolver.resolve(&def_id, &context2)?;
 
@@ -81,9 +82,6 @@ fn main() -> Result<(), StatError> {
     context3.set("in_combat", false);
     context3.set("zone_type", "pvp");
 
-    resolver.invalidate(&atk_id);
-    resolver.invalidate(&def_id);
-
     let atk3 = resolver.resolve(&atk_id, &context3)?;
     let def3 = resolver.resolve(&def_id, &context3)?;
 
@@ -96,9 +94,6 @@ fn main() -> Result<(), StatError> {
     context4.set("in_combat", true);
     context4.set("zone_type", "pvp");
 
-    resolver.invalidate(&atk_id);
-    resolver.invalidate(&def_id);
-
     let atk4 = resolver.resolve(&atk_id, &context4)?;
     let def4 = resolver.resolve(&def_id, &context4)?;
 
@@ -19,7 +19,7 @@ fn main() {
     println!("=== Setting up circular dependencies ===\n");
 
     // A depends on B
-    resolver.register_source(a_id.clone(), Box::new(ConstantSource(10.0)));
+    resolver.register_source(a_id.clone(), Box::new(ConstantSource::new(10.0)));
     resolver.register_transform(
         a_id.clone(),
         Box::new(ScalingTransform::new(b_id.clone(), 1.0)),
@@ -27,7 +27,7 @@ fn main() {
     println!("A: 10 (base) + B * 1.0");
 
     // B depends on C
-    resolver.register_source(b_id.clone(), Box::new(ConstantSource(20.0)));
+    resolver.register_source(b_id.clone(), Box::new(ConstantSource::new(20.0)));
     resolver.register_transform(
         b_id.clone(),
         Box::new(ScalingTransform::new(c_id.clone(), 1.0)),
@@ -35,7 +35,7 @@ fn main() {
     println!("B: 20 (base) + C * 1.0");
 
     // C depends on A (creates cycle: A -> B -> C -> A)
-    resolver.register_source(c_id.clone(), Box::new(ConstantSource(30.0)));
+    resolver.register_source(c_id.clone(), Box::new(ConstantSource::new(30.0)));
     resolver.register_transform(
         c_id.clone(),
         Box::new(ScalingTransform::new(a_id.clone(), 1.0)),
@@ -75,17 +75,17 @@ fn main() {
     let z_id = StatId::from_str("Z");
 
     // Linear chain: X -> Y -> Z (no cycle)
-    resolver2.register_source(x_id.clone(), Box::new(ConstantSource(10.0)));
+    resolver2.register_source(x_id.clone(), Box::new(ConstantSource::new(10.0)));
     println!("X: 10 (base)");
 
-    resolver2.register_source(y_id.clone(), Box::new(ConstantSource(20.0)));
+    resolver2.register_source(y_id.clone(), Box::new(ConstantSource::new(20.0)));
     resolver2.register_transform(
         y_id.clone(),
         Box::new(ScalingTransform::new(x_id.clone(), 1.0)),
     );
     println!("Y: 20 (base) + X * 1.0");
 
-    resolver2.register_source(z_id.clone(), Box::new(ConstantSource(30.0)));
+    resolver2.register_source(z_id.clone(), Box::new(ConstantSource::new(30.0)));
     resolver2.register_transform(
         z_id.clone(),
         Box::new(ScalingTransform::new(y_id.clone(), 1.0)),
@@ -21,16 +21,16 @@ fn main() -> Result<(), StatError> {
     println!("=== Setting up base stats ===");
 
     // Base stats (no dependencies)
-    resolver.register_source(str_id.clone(), Box::new(ConstantSource(10.0)));
+    resolver.register_source(str_id.clone(), Box::new(ConstantSource::new(10.0)));
     println!("STR: 10 (base)");
 
-    resolver.register_source(dex_id.clone(), Box::new(ConstantSource(15.0)));
+    resolver.register_source(dex_id.clone(), Box::new(ConstantSource::new(15.0)));
     println!("DEX: 15 (base)");
 
     println!("\n=== Setting up derived stats ===");
 
     // ATK depends on STR
-    resolver.register_source(atk_id.clone(), Box::new(ConstantSource(50.0)));
+    resolver.register_source(atk_id.clone(), Box::new(ConstantSource::new(50.0)));
     resolver.register_transform(
         atk_id.clone(),
         Box::new(ScalingTransform::new(str_id.clone(), 2.0)),
@@ -38,7 +38,7 @@ fn main() -> Result<(), StatError> {
     println!("ATK: 50 (base) + STR * 2.0");
 
     // CRIT depends on DEX
-    resolver.register_source(crit_id.clone(), Box::new(ConstantSource(5.0)));
+    resolver.register_source(crit_id.clone(), Box::new(ConstantSource::new(5.0)));
     resolver.register_transform(
         crit_id.clone(),
         Box::new(ScalingTransform::new(dex_id.clone(), 1.5)),
@@ -77,7 +77,7 @@ impl Character {
 
         // Register base stat sources (these are the character's base attributes)
         for (stat_id, value) in &base_stats {
-            resolver.register_source(stat_id.clone(), Box::new(ConstantSource(*value)));
+            resolver.register_source(stat_id.clone(), Box::new(ConstantSource::new(*value)));
         }
 
         // ====================================================================
@@ -88,7 +88,7 @@ impl Character {
 
         // ATK = STR * 2 + DEX
         // We start with base 0, then add scaling from STR and DEX
-        resolver.register_source(atk_id.clone(), Box::new(ConstantSource(0.0)));
+        resolver.register_source(atk_id.clone(), Box::new(ConstantSource::new(0.0)));
         resolver.register_transform(
             atk_id.clone(),
             Box::new(ScalingTransform::new(str_id.clone(), 2.0)),
@@ -99,14 +99,14 @@ impl Character {
         );
 
         // DEF = VIT * 1.5
-        resolver.register_source(def_id.clone(), Box::new(ConstantSource(0.0)));
+        resolver.register_source(def_id.clone(), Box::new(ConstantSource::new(0.0)));
         resolver.register_transform(
             def_id.clone(),
             Box::new(ScalingTransform::new(vit_id.clone(), 1.5)),
         );
 
         // HP = VIT * 10
-        resolver.register_source(hp_id.clone(), Box::new(ConstantSource(0.0)));
+        resolver.register_source(hp_id.clone(), Box::new(ConstantSource::new(0.0)));
         resolver.register_transform(
             hp_id.clone(),
             Box::new(ScalingTransform::new(vit_id.clone(), 10.0)),
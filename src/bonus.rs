@@ -6,16 +6,31 @@
 
 use crate::context::StatContext;
 use crate::error::StatError;
-use crate::numeric::{StatNumeric, StatValue};
+use crate::numeric::{Decimal, FixedPoint, StatNumeric};
+use crate::resolver::TransformHandle;
 use crate::stat_id::StatId;
 use crate::transform::{
-    AdditiveTransform, ClampTransform, MultiplicativeTransform, StackRule, StatTransform,
-    TransformPhase,
+    AdditiveTransform, ClampTransform, Contribution, MultiplicativeTransform, StackRule,
+    StatTransform, TransformPhase,
 };
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+/// Version of the bonus wire format written by `BonusTemplate`.
+///
+/// Bumped whenever the serialized shape of `Bonus` or `CompiledBonus`
+/// changes incompatibly.
+///
+/// - Version 2 writes every value as a decimal string
+///   (`{"Percent":"0.15"}`), the same in every build.
+/// - Version 1 wrote values as `StatValue`s: JSON numbers, or the
+///   `{ "value", "scale" }` form under the `fixed-point` feature.
+///
+/// `BonusTemplate::from_json` reads both.
+pub const BONUS_FORMAT_VERSION: u32 = 2;
+
 /// Bonus operation type.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum BonusOp {
     /// Add a flat or percentage value.
     Add,
@@ -30,19 +45,24 @@ pub enum BonusOp {
 }
 
 /// Bonus value type.
-#[derive(Debug, Clone, Copy, PartialEq)]
+///
+/// Values are stored as exact `Decimal`s, so the same bonus compiles into
+/// an `f64` or a fixed-point resolver without going through `f64` on the
+/// way. They serialize as decimal strings (`{"Percent":"0.1"}`) whatever
+/// the build's `StatValue` is.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum BonusValue {
     /// Flat numeric value.
-    Flat(f64),
+    Flat(Decimal),
     /// Percentage value (e.g., 0.10 for 10%).
-    Percent(f64),
+    Percent(Decimal),
 }
 
 /// A bonus definition.
 ///
 /// This is the declarative form that game code uses to define bonuses.
 /// It must be compiled into a `CompiledBonus` before being applied.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Bonus {
     /// The target stat ID.
     pub target: StatId,
@@ -129,8 +149,11 @@ impl Bonus {
     /// let bonus = Bonus::r#override(hp_id, 500.0)
     ///     .in_phase(TransformPhase::Custom(4));
     /// ```
-    pub fn r#override(target: StatId, value: f64) -> OverrideBonusBuilder {
-        OverrideBonusBuilder { target, value }
+    pub fn r#override(target: StatId, value: impl Into<Decimal>) -> OverrideBonusBuilder {
+        OverrideBonusBuilder {
+            target,
+            value: value.into(),
+        }
     }
 
     /// Create a new clamp minimum bonus.
@@ -146,8 +169,49 @@ impl Bonus {
     /// let bonus = Bonus::clamp_min(hp_id, 100.0)
     ///     .in_phase(TransformPhase::Final);
     /// ```
-    pub fn clamp_min(target: StatId, value: f64) -> ClampMinBonusBuilder {
-        ClampMinBonusBuilder { target, value }
+    pub fn clamp_min(target: StatId, value: impl Into<Decimal>) -> ClampMinBonusBuilder {
+        ClampMinBonusBuilder {
+            target,
+            value: value.into(),
+        }
+    }
+
+    /// Get the combined bonus of `stacks` copies of this bonus.
+    ///
+    /// Additive bonuses and percentage multipliers scale linearly
+    /// (3 stacks of +3% ATK give +9%). Flat multipliers compound
+    /// (3 stacks of ×1.1 give ×1.331). Overrides and clamps don't scale.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use zzstat::bonus::{Bonus, BonusValue};
+    /// use zzstat::StatId;
+    /// use zzstat::transform::TransformPhase;
+    ///
+    /// let bonus = Bonus::mul(StatId::from_str("ATK"))
+    ///     .percent(0.03)
+    ///     .in_phase(TransformPhase::Multiplicative);
+    /// assert_eq!(bonus.scaled(5).value, BonusValue::Percent(0.15.into()));
+    /// ```
+    pub fn scaled(&self, stacks: u32) -> Bonus {
+        let n = FixedPoint::from_int(stacks as i64);
+        let times = |v: Decimal| Decimal::from(v.to_fixed() * n);
+        let value = match (self.operation, self.value) {
+            (BonusOp::Add, BonusValue::Flat(v)) => BonusValue::Flat(times(v)),
+            (BonusOp::Add, BonusValue::Percent(p)) => BonusValue::Percent(times(p)),
+            (BonusOp::Multiply, BonusValue::Percent(p)) => BonusValue::Percent(times(p)),
+            (BonusOp::Multiply, BonusValue::Flat(v)) => BonusValue::Flat(
+                (0..stacks)
+                    .fold(FixedPoint::from_int(1), |acc, _| acc * v.to_fixed())
+                    .into(),
+            ),
+            (BonusOp::Override | BonusOp::ClampMin | BonusOp::ClampMax, value) => value,
+        };
+        Bonus {
+            value,
+            ..self.clone()
+        }
     }
 
     /// Create a new clamp maximum bonus.
@@ -163,17 +227,20 @@ impl Bonus {
     /// let bonus = Bonus::clamp_max(crit_id, 0.75)
     ///     .in_phase(TransformPhase::Final);
     /// ```
-    pub fn clamp_max(target: StatId, value: f64) -> ClampMaxBonusBuilder {
-        ClampMaxBonusBuilder { target, value }
+    pub fn clamp_max(target: StatId, value: impl Into<Decimal>) -> ClampMaxBonusBuilder {
+        ClampMaxBonusBuilder {
+            target,
+            value: value.into(),
+        }
     }
 }
 
 impl AddBonusBuilder {
     /// Set a flat value for the additive bonus.
-    pub fn flat(self, value: f64) -> AddBonusBuilderWithValue {
+    pub fn flat(self, value: impl Into<Decimal>) -> AddBonusBuilderWithValue {
         AddBonusBuilderWithValue {
             target: self.target,
-            value: BonusValue::Flat(value),
+            value: BonusValue::Flat(value.into()),
         }
     }
 
@@ -181,10 +248,10 @@ impl AddBonusBuilder {
     ///
     /// The percentage is applied to the current value and added.
     /// For example, 0.10 means add 10% of the current value.
-    pub fn percent(self, value: f64) -> AddBonusBuilderWithValue {
+    pub fn percent(self, value: impl Into<Decimal>) -> AddBonusBuilderWithValue {
         AddBonusBuilderWithValue {
             target: self.target,
-            value: BonusValue::Percent(value),
+            value: BonusValue::Percent(value.into()),
         }
     }
 }
@@ -194,10 +261,10 @@ impl MulBonusBuilder {
     ///
     /// The percentage is converted to a multiplier.
     /// For example, 0.20 means multiply by 1.20 (20% increase).
-    pub fn percent(self, value: f64) -> MulBonusBuilderWithValue {
+    pub fn percent(self, value: impl Into<Decimal>) -> MulBonusBuilderWithValue {
         MulBonusBuilderWithValue {
             target: self.target,
-            value: BonusValue::Percent(value),
+            value: BonusValue::Percent(value.into()),
         }
     }
 }
@@ -229,7 +296,7 @@ impl MulBonusBuilderWithValue {
 /// Builder for override bonuses.
 pub struct OverrideBonusBuilder {
     target: StatId,
-    value: f64,
+    value: Decimal,
 }
 
 impl OverrideBonusBuilder {
@@ -247,7 +314,7 @@ impl OverrideBonusBuilder {
 /// Builder for clamp minimum bonuses.
 pub struct ClampMinBonusBuilder {
     target: StatId,
-    value: f64,
+    value: Decimal,
 }
 
 impl ClampMinBonusBuilder {
@@ -265,7 +332,7 @@ impl ClampMinBonusBuilder {
 /// Builder for clamp maximum bonuses.
 pub struct ClampMaxBonusBuilder {
     target: StatId,
-    value: f64,
+    value: Decimal,
 }
 
 impl ClampMaxBonusBuilder {
@@ -284,7 +351,8 @@ impl ClampMaxBonusBuilder {
 ///
 /// This is the compiled form of a `Bonus`, containing a fully constructed
 /// transform that requires no branching during stat resolution.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(bound = "")]
 pub struct CompiledBonus<N: StatNumeric> {
     /// The target stat ID.
     pub stat: StatId,
@@ -292,21 +360,112 @@ pub struct CompiledBonus<N: StatNumeric> {
     pub phase: TransformPhase,
     /// The stack rule for this transform.
     pub stack_rule: StackRule,
-    /// The transform data (stored as enum for cloning).
-    transform_data: TransformData,
-    /// Phantom data to track the numeric type (for type safety).
-    _phantom: std::marker::PhantomData<N>,
+    /// The transform data (stored as enum for cloning), already in the
+    /// numeric backend `N`.
+    #[serde(rename = "transform")]
+    transform_data: TransformData<N>,
 }
 
 /// Internal enum to store transform data in a cloneable way.
-#[derive(Debug, Clone)]
-enum TransformData {
-    AdditiveFlat(f64),
-    AdditivePercent(StatId, f64),
-    Multiplicative(f64),
-    Override(f64),
-    ClampMin(f64),
-    ClampMax(f64),
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", bound = "")]
+enum TransformData<N: StatNumeric> {
+    AdditiveFlat(N),
+    AdditivePercent(StatId, N),
+    Multiplicative(N),
+    Override(N),
+    ClampMin(N),
+    ClampMax(N),
+}
+
+/// A versioned collection of bonuses, e.g. the bonuses of one item template.
+///
+/// This is the wire format for storing bonuses in a database or shipping
+/// them to clients. The `version` field lets readers reject data written
+/// by an incompatible release instead of misreading it.
+///
+/// # Examples
+///
+/// ```rust
+/// use zzstat::bonus::{Bonus, BonusTemplate};
+/// use zzstat::StatId;
+/// use zzstat::transform::TransformPhase;
+///
+/// let sword = BonusTemplate::new(vec![
+///     Bonus::add(StatId::from_str("ATK")).flat(25.0).in_phase(TransformPhase::Additive),
+///     Bonus::mul(StatId::from_str("ATK")).percent(0.15).in_phase(TransformPhase::Multiplicative),
+/// ]);
+///
+/// let json = sword.to_json().unwrap();
+/// let loaded = BonusTemplate::from_json(&json).unwrap();
+/// assert_eq!(loaded, sword);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BonusTemplate {
+    /// The wire format version, `BONUS_FORMAT_VERSION` when written by this release.
+    pub version: u32,
+    /// The bonuses in the template.
+    pub bonuses: Vec<Bonus>,
+}
+
+/// Just the version field, read before the rest of a template.
+#[derive(Deserialize)]
+struct FormatVersion {
+    version: u32,
+}
+
+impl BonusTemplate {
+    /// Create a template in the current wire format version.
+    pub fn new(bonuses: Vec<Bonus>) -> Self {
+        Self {
+            version: BONUS_FORMAT_VERSION,
+            bonuses,
+        }
+    }
+
+    /// Serialize the template to JSON.
+    pub fn to_json(&self) -> Result<String, StatError> {
+        serde_json::to_string(self).map_err(|err| StatError::Definition {
+            location: "bonus template".to_string(),
+            message: err.to_string(),
+        })
+    }
+
+    /// Parse a template from JSON.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(BonusTemplate)` - The parsed template, upgraded to
+    ///   `BONUS_FORMAT_VERSION`
+    /// * `Err(StatError::UnsupportedVersion)` - If the data was written in a
+    ///   format version this release doesn't know
+    /// * `Err(StatError::Definition)` - If the JSON is malformed, with the
+    ///   `line:column` of the problem
+    pub fn from_json(json: &str) -> Result<Self, StatError> {
+        let parse_error = |err: serde_json::Error| StatError::Definition {
+            location: format!("{}:{}", err.line(), err.column()),
+            message: err.to_string(),
+        };
+        // Check the version first: other fields may have a different
+        // shape in other versions
+        let FormatVersion { version } = serde_json::from_str(json).map_err(parse_error)?;
+        // Version 1 only differs in how values are written, and values
+        // are read in either form
+        if !(1..=BONUS_FORMAT_VERSION).contains(&version) {
+            return Err(StatError::UnsupportedVersion {
+                found: version,
+                supported: BONUS_FORMAT_VERSION,
+            });
+        }
+        let mut template: Self = serde_json::from_str(json).map_err(parse_error)?;
+        template.version = BONUS_FORMAT_VERSION;
+        Ok(template)
+    }
+
+    /// Compile every bonus in the template.
+    pub fn compile<N: StatNumeric>(&self) -> Vec<CompiledBonus<N>> {
+        self.bonuses.iter().map(compile_bonus).collect()
+    }
 }
 
 /// Compile a bonus into a compiled bonus.
@@ -327,7 +486,7 @@ enum TransformData {
 ///
 /// ```rust
 /// use zzstat::bonus::{Bonus, compile_bonus};
-/// use zzstat::StatId;
+/// use zzstat::{StatId, StatValue};
 /// use zzstat::transform::TransformPhase;
 ///
 /// let hp_id = StatId::from_str("HP");
@@ -335,48 +494,31 @@ enum TransformData {
 ///     .flat(50.0)
 ///     .in_phase(TransformPhase::Custom(3));
 ///
-/// let compiled = compile_bonus::<f64>(&bonus);
+/// let compiled = compile_bonus::<StatValue>(&bonus);
 /// ```
 pub fn compile_bonus<N: StatNumeric>(bonus: &Bonus) -> CompiledBonus<N> {
+    let amount = N::from_decimal(bonus.value.amount());
     let (transform_data, stack_rule) = match bonus.operation {
         BonusOp::Add => match bonus.value {
-            BonusValue::Flat(value) => (TransformData::AdditiveFlat(value), StackRule::Additive),
-            BonusValue::Percent(percent) => (
-                TransformData::AdditivePercent(bonus.target.clone(), percent),
+            BonusValue::Flat(_) => (TransformData::AdditiveFlat(amount), StackRule::Additive),
+            BonusValue::Percent(_) => (
+                TransformData::AdditivePercent(bonus.target.clone(), amount),
                 StackRule::Additive,
             ),
         },
         BonusOp::Multiply => {
             let multiplier = match bonus.value {
-                BonusValue::Percent(percent) => 1.0 + percent,
-                BonusValue::Flat(v) => v,
+                BonusValue::Percent(_) => N::from_int(1) + amount,
+                BonusValue::Flat(_) => amount,
             };
             (
                 TransformData::Multiplicative(multiplier),
                 StackRule::Multiplicative,
             )
         }
-        BonusOp::Override => {
-            let value = match bonus.value {
-                BonusValue::Flat(v) => v,
-                BonusValue::Percent(_) => bonus.value.to_f64(),
-            };
-            (TransformData::Override(value), StackRule::Override)
-        }
-        BonusOp::ClampMin => {
-            let min_value = match bonus.value {
-                BonusValue::Flat(v) => v,
-                BonusValue::Percent(_) => bonus.value.to_f64(),
-            };
-            (TransformData::ClampMin(min_value), StackRule::MinMax)
-        }
-        BonusOp::ClampMax => {
-            let max_value = match bonus.value {
-                BonusValue::Flat(v) => v,
-                BonusValue::Percent(_) => bonus.value.to_f64(),
-            };
-            (TransformData::ClampMax(max_value), StackRule::MinMax)
-        }
+        BonusOp::Override => (TransformData::Override(amount), StackRule::Override),
+        BonusOp::ClampMin => (TransformData::ClampMin(amount), StackRule::MinMax),
+        BonusOp::ClampMax => (TransformData::ClampMax(amount), StackRule::MinMax),
     };
 
     CompiledBonus {
@@ -384,28 +526,23 @@ pub fn compile_bonus<N: StatNumeric>(bonus: &Bonus) -> CompiledBonus<N> {
         phase: bonus.phase,
         stack_rule,
         transform_data,
-        _phantom: std::marker::PhantomData,
     }
 }
 
 impl<N: StatNumeric> CompiledBonus<N> {
     /// Create a Box<dyn StatTransform> from the stored transform data.
-    fn to_transform(&self) -> Box<dyn StatTransform> {
+    fn to_transform(&self) -> Box<dyn StatTransform<N>> {
         match &self.transform_data {
-            TransformData::AdditiveFlat(value) => Box::new(AdditiveTransform::new(*value)),
+            TransformData::AdditiveFlat(value) => Box::new(AdditiveTransform::from_value(*value)),
             TransformData::AdditivePercent(dep, percent) => {
                 Box::new(PercentAdditiveTransform::new(dep.clone(), *percent))
             }
             TransformData::Multiplicative(multiplier) => {
-                Box::new(MultiplicativeTransform::new(*multiplier))
+                Box::new(MultiplicativeTransform::from_value(*multiplier))
             }
             TransformData::Override(value) => Box::new(OverrideTransform::new(*value)),
-            TransformData::ClampMin(min_value) => {
-                Box::new(ClampTransform::with_min(StatValue::from_f64(*min_value)))
-            }
-            TransformData::ClampMax(max_value) => {
-                Box::new(ClampTransform::with_max(StatValue::from_f64(*max_value)))
-            }
+            TransformData::ClampMin(min_value) => Box::new(ClampTransform::with_min(*min_value)),
+            TransformData::ClampMax(max_value) => Box::new(ClampTransform::with_max(*max_value)),
         }
     }
 }
@@ -420,6 +557,11 @@ impl<N: StatNumeric> CompiledBonus<N> {
 /// * `resolver` - The resolver to apply the bonus to
 /// * `compiled` - The compiled bonus to apply
 ///
+/// # Returns
+///
+/// The handle of the registered transform, which can be passed to
+/// `StatResolver::remove_transform` to take the bonus back off.
+///
 /// # Examples
 ///
 /// ```rust
@@ -433,20 +575,20 @@ impl<N: StatNumeric> CompiledBonus<N> {
 ///     .flat(50.0)
 ///     .in_phase(TransformPhase::Custom(3));
 ///
-/// let compiled = compile_bonus::<f64>(&bonus);
+/// let compiled = compile_bonus(&bonus);
 /// apply_compiled_bonus(&mut resolver, &compiled);
 /// ```
 #[inline]
 pub fn apply_compiled_bonus<N: StatNumeric>(
-    resolver: &mut crate::resolver::StatResolver,
+    resolver: &mut crate::resolver::StatResolver<N>,
     compiled: &CompiledBonus<N>,
-) {
+) -> TransformHandle {
     resolver.register_transform_with_rule(
         compiled.stat.clone(),
         compiled.phase,
-        compiled.stack_rule,
+        compiled.stack_rule.clone(),
         compiled.to_transform(),
-    );
+    )
 }
 
 /// Apply multiple compiled bonuses to a resolver.
@@ -456,6 +598,10 @@ pub fn apply_compiled_bonus<N: StatNumeric>(
 /// * `resolver` - The resolver to apply bonuses to
 /// * `compiled` - The compiled bonuses to apply
 ///
+/// # Returns
+///
+/// The handles of the registered transforms, in the same order as `compiled`.
+///
 /// # Examples
 ///
 /// ```rust
@@ -470,16 +616,17 @@ pub fn apply_compiled_bonus<N: StatNumeric>(
 ///     Bonus::mul(hp_id).percent(0.10).in_phase(TransformPhase::Custom(3)),
 /// ];
 ///
-/// let compiled: Vec<_> = bonuses.iter().map(|b| compile_bonus::<f64>(b)).collect();
+/// let compiled: Vec<_> = bonuses.iter().map(compile_bonus).collect();
 /// apply_compiled_bonuses(&mut resolver, &compiled);
 /// ```
 pub fn apply_compiled_bonuses<N: StatNumeric>(
-    resolver: &mut crate::resolver::StatResolver,
+    resolver: &mut crate::resolver::StatResolver<N>,
     compiled: &[CompiledBonus<N>],
-) {
-    for bonus in compiled {
-        apply_compiled_bonus(resolver, bonus);
-    }
+) -> Vec<TransformHandle> {
+    compiled
+        .iter()
+        .map(|bonus| apply_compiled_bonus(resolver, bonus))
+        .collect()
 }
 
 // Custom transforms
@@ -488,13 +635,13 @@ pub fn apply_compiled_bonuses<N: StatNumeric>(
 ///
 /// This is used for additive percent bonuses (e.g., +10% HP).
 /// It depends on the stat itself to read the current value.
-struct PercentAdditiveTransform {
+struct PercentAdditiveTransform<N: StatNumeric> {
     dependency: StatId,
-    percent: f64,
+    percent: N,
 }
 
-impl PercentAdditiveTransform {
-    fn new(dependency: StatId, percent: f64) -> Self {
+impl<N: StatNumeric> PercentAdditiveTransform<N> {
+    fn new(dependency: StatId, percent: N) -> Self {
         Self {
             dependency,
             percent,
@@ -502,7 +649,7 @@ impl PercentAdditiveTransform {
     }
 }
 
-impl StatTransform for PercentAdditiveTransform {
+impl<N: StatNumeric> StatTransform<N> for PercentAdditiveTransform<N> {
     fn depends_on(&self) -> Vec<StatId> {
         vec![self.dependency.clone()]
     }
@@ -513,24 +660,35 @@ impl StatTransform for PercentAdditiveTransform {
 
     fn apply(
         &self,
-        input: StatValue,
-        dependencies: &HashMap<StatId, StatValue>,
+        input: N,
+        dependencies: &HashMap<StatId, N>,
         _context: &StatContext,
-    ) -> Result<StatValue, StatError> {
+    ) -> Result<N, StatError> {
         let dep_value = dependencies
             .get(&self.dependency)
             .ok_or_else(|| StatError::MissingDependency(self.dependency.clone()))?;
         // Add (current_value * percent) to input
-        let bonus = *dep_value * StatValue::from_f64(self.percent);
+        let bonus = *dep_value * self.percent;
         Ok(input + bonus)
     }
 
     fn description(&self) -> String {
-        format!("+{:.1}% (additive)", self.percent * 100.0)
+        format!("+{:.1}% (additive)", self.percent.to_f64() * 100.0)
+    }
+
+    fn contribution(
+        &self,
+        dependencies: &HashMap<StatId, N>,
+        _context: &StatContext,
+    ) -> Result<Option<Contribution<N>>, StatError> {
+        let dep_value = dependencies
+            .get(&self.dependency)
+            .ok_or_else(|| StatError::MissingDependency(self.dependency.clone()))?;
+        Ok(Some(Contribution::Delta(*dep_value * self.percent)))
     }
 }
 
-impl Clone for PercentAdditiveTransform {
+impl<N: StatNumeric> Clone for PercentAdditiveTransform<N> {
     fn clone(&self) -> Self {
         Self {
             dependency: self.dependency.clone(),
@@ -544,17 +702,17 @@ impl Clone for PercentAdditiveTransform {
 /// This transform ignores the input value completely and returns
 /// the absolute value. It is used for Override bonuses.
 #[derive(Clone)]
-struct OverrideTransform {
-    absolute_value: f64,
+struct OverrideTransform<N: StatNumeric> {
+    absolute_value: N,
 }
 
-impl OverrideTransform {
-    fn new(absolute_value: f64) -> Self {
+impl<N: StatNumeric> OverrideTransform<N> {
+    fn new(absolute_value: N) -> Self {
         Self { absolute_value }
     }
 }
 
-impl StatTransform for OverrideTransform {
+impl<N: StatNumeric> StatTransform<N> for OverrideTransform<N> {
     fn depends_on(&self) -> Vec<StatId> {
         Vec::new()
     }
@@ -565,22 +723,30 @@ impl StatTransform for OverrideTransform {
 
     fn apply(
         &self,
-        _input: StatValue,
-        _dependencies: &HashMap<StatId, StatValue>,
+        _input: N,
+        _dependencies: &HashMap<StatId, N>,
         _context: &StatContext,
-    ) -> Result<StatValue, StatError> {
+    ) -> Result<N, StatError> {
         // Always return the absolute value, completely ignoring input
-        Ok(StatValue::from_f64(self.absolute_value))
+        Ok(self.absolute_value)
     }
 
     fn description(&self) -> String {
-        format!("override({:.2})", self.absolute_value)
+        format!("override({:.2})", self.absolute_value.to_f64())
+    }
+
+    fn contribution(
+        &self,
+        _dependencies: &HashMap<StatId, N>,
+        _context: &StatContext,
+    ) -> Result<Option<Contribution<N>>, StatError> {
+        Ok(Some(Contribution::Override(self.absolute_value)))
     }
 }
 
 // Helper implementation for BonusValue
 impl BonusValue {
-    fn to_f64(self) -> f64 {
+    fn amount(self) -> Decimal {
         match self {
             BonusValue::Flat(v) => v,
             BonusValue::Percent(v) => v,
@@ -0,0 +1,692 @@
+//! Timed buffs module.
+//!
+//! Tracks compiled bonuses that expire. The engine never reads a clock:
+//! the caller passes the current tick (a frame number, server tick or
+//! timestamp in whatever unit the game uses) to `BuffTracker::tick`, which
+//! removes expired bonuses in a deterministic order.
+//!
+//! On top of single bonuses, the tracker manages buff instances: a
+//! `BuffDefinition` applied by a caster, keyed by buff ID and caster ID,
+//! with a stack limit and a `StackPolicy` for reapplication.
+
+use crate::bonus::{apply_compiled_bonus, compile_bonus, Bonus, CompiledBonus};
+use crate::numeric::StatNumeric;
+use crate::resolver::{StatResolver, TransformHandle};
+use crate::stat_id::StatId;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+
+/// A point in game time, in caller-defined units.
+pub type Tick = u64;
+
+/// Identifier of the entity that applied a buff.
+pub type CasterId = u64;
+
+/// What happens when a buff is applied again by the same caster.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StackPolicy {
+    /// Add a stack (up to the limit) and reset the duration of all stacks.
+    Refresh,
+    /// Replace the existing instance: back to one stack with a new duration.
+    Replace,
+    /// Add a stack with its own duration. At the limit, the oldest stack
+    /// is removed to make room.
+    Independent,
+}
+
+/// A buff that can be applied to a character.
+///
+/// Each stack applies the definition's bonuses once; `n` stacks apply them
+/// scaled by `Bonus::scaled(n)`, so "+3% ATK per stack" gives +15% at five
+/// stacks. This holds for every `StackPolicy`: independent stacks expire
+/// separately but are registered together, scaled by their total count.
+///
+/// # Examples
+///
+/// ```rust
+/// use zzstat::bonus::Bonus;
+/// use zzstat::buff::{BuffDefinition, StackPolicy};
+/// use zzstat::StatId;
+/// use zzstat::transform::TransformPhase;
+///
+/// // Stacks up to 5 times, each stack +3% ATK, reapplying refreshes duration
+/// let frenzy = BuffDefinition::new(
+///     "frenzy",
+///     vec![Bonus::mul(StatId::from_str("ATK")).percent(0.03).in_phase(TransformPhase::Multiplicative)],
+///     600,
+/// )
+/// .with_max_stacks(5)
+/// .with_policy(StackPolicy::Refresh);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BuffDefinition {
+    /// The buff identifier.
+    pub id: String,
+    /// The bonuses applied by one stack.
+    pub bonuses: Vec<Bonus>,
+    /// How long a stack lasts, in ticks.
+    pub duration: Tick,
+    /// The maximum number of stacks per caster (at least 1).
+    pub max_stacks: u32,
+    /// What happens on reapplication.
+    pub policy: StackPolicy,
+}
+
+impl BuffDefinition {
+    /// Create a buff with a single stack that is refreshed on reapplication.
+    pub fn new(id: impl Into<String>, bonuses: Vec<Bonus>, duration: Tick) -> Self {
+        Self {
+            id: id.into(),
+            bonuses,
+            duration,
+            max_stacks: 1,
+            policy: StackPolicy::Refresh,
+        }
+    }
+
+    /// Set the maximum number of stacks (values below 1 are treated as 1).
+    pub fn with_max_stacks(mut self, max_stacks: u32) -> Self {
+        self.max_stacks = max_stacks.max(1);
+        self
+    }
+
+    /// Set the reapplication policy.
+    pub fn with_policy(mut self, policy: StackPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+}
+
+/// A buff instance key: buff ID and caster ID.
+type BuffKey = (String, CasterId);
+
+/// Something that expires.
+#[derive(Debug, Clone)]
+enum Expiring {
+    /// A single bonus applied with `apply_for` or `apply_until`.
+    Bonus(TransformHandle),
+    /// One stack entry of a buff instance.
+    Stack(BuffKey),
+}
+
+/// Stacks of a buff that share one duration.
+#[derive(Debug, Clone)]
+struct BuffStack {
+    /// Number of stacks represented by this entry.
+    count: u32,
+    /// Key of this entry in `BuffTracker::expiries`.
+    expiry: (Tick, u64),
+}
+
+/// An active buff instance from one caster.
+#[derive(Debug, Clone)]
+struct BuffInstance {
+    /// The bonuses applied by one stack.
+    bonuses: Vec<Bonus>,
+    /// Stack entries, oldest first.
+    stacks: VecDeque<BuffStack>,
+    /// Transforms registered for all stacks together.
+    handles: Vec<TransformHandle>,
+}
+
+impl BuffInstance {
+    /// Get the total number of stacks.
+    fn count(&self) -> u32 {
+        self.stacks.iter().map(|stack| stack.count).sum()
+    }
+
+    /// Register the bonuses scaled by the current stack count.
+    ///
+    /// Returns the handles of the transforms registered before, which the
+    /// caller removes from the resolver.
+    fn recompile<N: StatNumeric>(
+        &mut self,
+        resolver: &mut StatResolver<N>,
+    ) -> Vec<TransformHandle> {
+        let count = self.count();
+        let handles = if count == 0 {
+            Vec::new()
+        } else {
+            self.bonuses
+                .iter()
+                .map(|bonus| {
+                    let compiled: CompiledBonus<N> = compile_bonus(&bonus.scaled(count));
+                    apply_compiled_bonus(resolver, &compiled)
+                })
+                .collect()
+        };
+        std::mem::replace(&mut self.handles, handles)
+    }
+}
+
+/// Tracks applied bonuses together with the tick at which they expire.
+///
+/// Bonuses are applied to a `StatResolver` immediately and removed from it
+/// by `tick` once `now >= expires_at`. Bonuses expiring on the same tick are
+/// removed in the order they were applied.
+///
+/// # Examples
+///
+/// ```rust
+/// use zzstat::*;
+/// use zzstat::bonus::{Bonus, compile_bonus};
+/// use zzstat::buff::BuffTracker;
+/// use zzstat::source::ConstantSource;
+/// use zzstat::transform::TransformPhase;
+///
+/// let mut resolver = StatResolver::new();
+/// let atk_id = StatId::from_str("ATK");
+/// resolver.register_source(atk_id.clone(), Box::new(ConstantSource::new(100.0)));
+///
+/// let mut buffs = BuffTracker::new();
+/// let battle_cry = compile_bonus::<StatValue>(
+///     &Bonus::mul(atk_id.clone()).percent(0.5).in_phase(TransformPhase::Multiplicative),
+/// );
+/// buffs.apply_for(&mut resolver, &battle_cry, 100, 30); // from tick 100, lasts 30 ticks
+///
+/// let context = StatContext::new();
+/// assert_eq!(resolver.resolve(&atk_id, &context).unwrap().value, 150.0);
+///
+/// assert!(buffs.tick(&mut resolver, 129).is_empty());
+/// let changed = buffs.tick(&mut resolver, 130);
+/// assert!(changed.contains(&atk_id));
+/// assert_eq!(resolver.resolve(&atk_id, &context).unwrap().value, 100.0);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct BuffTracker {
+    /// Timed bonuses and buff stacks by (expiry tick, application order).
+    expiries: BTreeMap<(Tick, u64), Expiring>,
+    /// Expiry key of each timed bonus, for early removal.
+    keys: HashMap<TransformHandle, (Tick, u64)>,
+    /// Active buff instances.
+    buffs: BTreeMap<BuffKey, BuffInstance>,
+    /// Application counter, used to order bonuses expiring on the same tick.
+    next_seq: u64,
+}
+
+impl BuffTracker {
+    /// Create an empty tracker.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Apply a bonus that expires at a given tick.
+    ///
+    /// # Arguments
+    ///
+    /// * `resolver` - The resolver to apply the bonus to
+    /// * `compiled` - The compiled bonus to apply
+    /// * `expires_at` - The first tick at which the bonus is no longer active
+    ///
+    /// # Returns
+    ///
+    /// The handle of the registered transform.
+    pub fn apply_until<N: StatNumeric>(
+        &mut self,
+        resolver: &mut StatResolver<N>,
+        compiled: &CompiledBonus<N>,
+        expires_at: Tick,
+    ) -> TransformHandle {
+        let handle = apply_compiled_bonus(resolver, compiled);
+        let key = self.schedule(expires_at, Expiring::Bonus(handle.clone()));
+        self.keys.insert(handle.clone(), key);
+        handle
+    }
+
+    /// Apply a bonus for a duration starting at `now`.
+    ///
+    /// The bonus expires at `now + duration` (saturating).
+    ///
+    /// # Returns
+    ///
+    /// The handle of the registered transform.
+    pub fn apply_for<N: StatNumeric>(
+        &mut self,
+        resolver: &mut StatResolver<N>,
+        compiled: &CompiledBonus<N>,
+        now: Tick,
+        duration: Tick,
+    ) -> TransformHandle {
+        self.apply_until(resolver, compiled, now.saturating_add(duration))
+    }
+
+    /// Schedule something to expire, returning its expiry key.
+    fn schedule(&mut self, expires_at: Tick, expiring: Expiring) -> (Tick, u64) {
+        let key = (expires_at, self.next_seq);
+        self.next_seq += 1;
+        self.expiries.insert(key, expiring);
+        key
+    }
+
+    /// Apply a buff from a caster at `now`.
+    ///
+    /// Instances are keyed by buff ID and caster ID, so the same buff from
+    /// different casters stacks independently. Reapplying a buff from the
+    /// same caster follows the definition's `StackPolicy`, never exceeding
+    /// `max_stacks`.
+    ///
+    /// # Returns
+    ///
+    /// The number of stacks of this buff from this caster after applying.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use zzstat::*;
+    /// use zzstat::bonus::Bonus;
+    /// use zzstat::buff::{BuffDefinition, BuffTracker};
+    /// use zzstat::source::ConstantSource;
+    /// use zzstat::transform::TransformPhase;
+    ///
+    /// let mut resolver = StatResolver::new();
+    /// let atk_id = StatId::from_str("ATK");
+    /// resolver.register_source(atk_id.clone(), Box::new(ConstantSource::new(100.0)));
+    ///
+    /// let rage = BuffDefinition::new(
+    ///     "rage",
+    ///     vec![Bonus::add(atk_id.clone()).flat(10.0).in_phase(TransformPhase::Additive)],
+    ///     50,
+    /// )
+    /// .with_max_stacks(3);
+    ///
+    /// let mut buffs = BuffTracker::new();
+    /// let context = StatContext::new();
+    /// for now in 0..5 {
+    ///     buffs.apply_buff(&mut resolver, &rage, 1, now);
+    /// }
+    /// assert_eq!(buffs.stacks("rage", 1), 3);
+    /// assert_eq!(resolver.resolve(&atk_id, &context).unwrap().value, 130.0);
+    ///
+    /// // The last application refreshed the duration to tick 54
+    /// buffs.tick(&mut resolver, 53);
+    /// assert_eq!(buffs.stacks("rage", 1), 3);
+    /// buffs.tick(&mut resolver, 54);
+    /// assert_eq!(buffs.stacks("rage", 1), 0);
+    /// ```
+    pub fn apply_buff<N: StatNumeric>(
+        &mut self,
+        resolver: &mut StatResolver<N>,
+        buff: &BuffDefinition,
+        caster: CasterId,
+        now: Tick,
+    ) -> u32 {
+        let key = (buff.id.clone(), caster);
+        let max_stacks = buff.max_stacks.max(1);
+        let current = self.stacks(&buff.id, caster);
+
+        let count = match buff.policy {
+            StackPolicy::Refresh => {
+                self.clear_buff(resolver, &key);
+                (current + 1).min(max_stacks)
+            }
+            StackPolicy::Replace => {
+                self.clear_buff(resolver, &key);
+                1
+            }
+            StackPolicy::Independent => {
+                // At the limit, the oldest stack makes room
+                if current >= max_stacks {
+                    if let Some(stack) = self
+                        .buffs
+                        .get_mut(&key)
+                        .and_then(|instance| instance.stacks.pop_front())
+                    {
+                        self.expiries.remove(&stack.expiry);
+                    }
+                }
+                1
+            }
+        };
+
+        let expiry = self.schedule(
+            now.saturating_add(buff.duration),
+            Expiring::Stack(key.clone()),
+        );
+        let instance = self.buffs.entry(key).or_insert_with(|| BuffInstance {
+            bonuses: Vec::new(),
+            stacks: VecDeque::new(),
+            handles: Vec::new(),
+        });
+        instance.bonuses = buff.bonuses.clone();
+        instance.stacks.push_back(BuffStack { count, expiry });
+        let replaced = instance.recompile(resolver);
+        Self::remove_handles(resolver, &replaced);
+
+        self.stacks(&buff.id, caster)
+    }
+
+    /// Get the number of stacks of a buff from a caster.
+    pub fn stacks(&self, buff_id: &str, caster: CasterId) -> u32 {
+        self.buffs
+            .get(&(buff_id.to_string(), caster))
+            .map_or(0, BuffInstance::count)
+    }
+
+    /// Remove all stacks of a buff from a caster (e.g. when it is dispelled).
+    ///
+    /// # Returns
+    ///
+    /// `true` if the buff was active.
+    pub fn remove_buff<N: StatNumeric>(
+        &mut self,
+        resolver: &mut StatResolver<N>,
+        buff_id: &str,
+        caster: CasterId,
+    ) -> bool {
+        self.clear_buff(resolver, &(buff_id.to_string(), caster))
+    }
+
+    /// Remove every stack of a buff instance.
+    fn clear_buff<N: StatNumeric>(
+        &mut self,
+        resolver: &mut StatResolver<N>,
+        key: &BuffKey,
+    ) -> bool {
+        let Some(instance) = self.buffs.remove(key) else {
+            return false;
+        };
+        for stack in &instance.stacks {
+            self.expiries.remove(&stack.expiry);
+        }
+        Self::remove_handles(resolver, &instance.handles);
+        true
+    }
+
+    fn remove_handles<N: StatNumeric>(resolver: &mut StatResolver<N>, handles: &[TransformHandle]) {
+        for handle in handles {
+            resolver.remove_transform(handle);
+        }
+    }
+
+    /// Get the tick at which a tracked bonus expires.
+    ///
+    /// Returns `None` if the bonus isn't tracked (or has already expired).
+    pub fn expires_at(&self, handle: &TransformHandle) -> Option<Tick> {
+        self.keys.get(handle).map(|&(expires_at, _)| expires_at)
+    }
+
+    /// Get the earliest tick at which a tracked bonus expires.
+    ///
+    /// Useful for scheduling: nothing changes before this tick.
+    pub fn next_expiry(&self) -> Option<Tick> {
+        self.expiries
+            .keys()
+            .next()
+            .map(|&(expires_at, _)| expires_at)
+    }
+
+    /// Get the number of tracked timed bonuses and buff stack entries.
+    pub fn len(&self) -> usize {
+        self.expiries.len()
+    }
+
+    /// Check whether nothing is tracked.
+    pub fn is_empty(&self) -> bool {
+        self.expiries.is_empty()
+    }
+
+    /// Remove a tracked bonus before it expires (e.g. when it is dispelled).
+    ///
+    /// # Returns
+    ///
+    /// `true` if the bonus was tracked and has been removed.
+    pub fn remove<N: StatNumeric>(
+        &mut self,
+        resolver: &mut StatResolver<N>,
+        handle: &TransformHandle,
+    ) -> bool {
+        let Some(key) = self.keys.remove(handle) else {
+            return false;
+        };
+        self.expiries.remove(&key);
+        resolver.remove_transform(handle);
+        true
+    }
+
+    /// Remove every bonus that has expired by `now`.
+    ///
+    /// A bonus or buff stack has expired once `now >= expires_at`. Expired
+    /// entries are removed in expiry order, then application order.
+    ///
+    /// # Returns
+    ///
+    /// The stats whose values may have changed: the targets of the removed
+    /// bonuses and every stat that depends on them.
+    pub fn tick<N: StatNumeric>(
+        &mut self,
+        resolver: &mut StatResolver<N>,
+        now: Tick,
+    ) -> BTreeSet<StatId> {
+        let mut removed = Vec::new();
+        while let Some(entry) = self.expiries.first_entry() {
+            if entry.key().0 > now {
+                break;
+            }
+            let expiry = *entry.key();
+            match entry.remove() {
+                Expiring::Bonus(handle) => {
+                    self.keys.remove(&handle);
+                    removed.push(handle);
+                }
+                Expiring::Stack(key) => {
+                    let Some(instance) = self.buffs.get_mut(&key) else {
+                        continue;
+                    };
+                    // The remaining stacks are registered again for their count
+                    instance.stacks.retain(|stack| stack.expiry != expiry);
+                    removed.extend(instance.recompile(resolver));
+                    if instance.stacks.is_empty() {
+                        self.buffs.remove(&key);
+                    }
+                }
+            }
+        }
+
+        let mut changed = BTreeSet::new();
+        for handle in removed {
+            if resolver.remove_transform(&handle) {
+                changed.extend(resolver.dependents_of(handle.stat()));
+                changed.insert(handle.stat().clone());
+            }
+        }
+        changed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bonus::{compile_bonus, Bonus};
+    use crate::context::StatContext;
+    use crate::numeric::StatValue;
+    use crate::source::ConstantSource;
+    use crate::transform::{ScalingTransform, TransformPhase};
+
+    fn flat(stat: &StatId, value: f64) -> CompiledBonus<StatValue> {
+        compile_bonus(
+            &Bonus::add(stat.clone())
+                .flat(value)
+                .in_phase(TransformPhase::Additive),
+        )
+    }
+
+    #[test]
+    fn test_expiry_order_and_changed_stats() {
+        let mut resolver = StatResolver::new();
+        let str_id = StatId::from_str("STR");
+        let atk_id = StatId::from_str("ATK");
+        let def_id = StatId::from_str("DEF");
+        resolver.register_source(str_id.clone(), Box::new(ConstantSource::new(10.0)));
+        resolver.register_source(def_id.clone(), Box::new(ConstantSource::new(5.0)));
+        resolver.register_transform(
+            atk_id.clone(),
+            Box::new(ScalingTransform::new(str_id.clone(), 2.0)),
+        );
+
+        let mut buffs = BuffTracker::new();
+        let strength = buffs.apply_for(&mut resolver, &flat(&str_id, 5.0), 0, 10);
+        buffs.apply_until(&mut resolver, &flat(&def_id, 3.0), 20);
+        assert_eq!(buffs.len(), 2);
+        assert_eq!(buffs.expires_at(&strength), Some(10));
+        assert_eq!(buffs.next_expiry(), Some(10));
+
+        let context = StatContext::new();
+        assert_eq!(resolver.resolve(&atk_id, &context).unwrap().value, 30.0);
+
+        // STR expired: ATK changes with it, DEF doesn't
+        let changed = buffs.tick(&mut resolver, 15);
+        assert_eq!(changed, BTreeSet::from([atk_id.clone(), str_id.clone()]));
+        assert_eq!(resolver.resolve(&atk_id, &context).unwrap().value, 20.0);
+        assert_eq!(resolver.resolve(&def_id, &context).unwrap().value, 8.0);
+        assert_eq!(buffs.expires_at(&strength), None);
+        assert_eq!(buffs.next_expiry(), Some(20));
+
+        // Ticking again is a no-op
+        assert!(buffs.tick(&mut resolver, 15).is_empty());
+
+        assert_eq!(
+            buffs.tick(&mut resolver, 20),
+            BTreeSet::from([def_id.clone()])
+        );
+        assert!(buffs.is_empty());
+        assert_eq!(resolver.resolve(&def_id, &context).unwrap().value, 5.0);
+    }
+
+    fn frenzy(policy: StackPolicy) -> BuffDefinition {
+        BuffDefinition::new(
+            "frenzy",
+            vec![Bonus::mul(StatId::from_str("ATK"))
+                .percent(0.03)
+                .in_phase(TransformPhase::Multiplicative)],
+            100,
+        )
+        .with_max_stacks(5)
+        .with_policy(policy)
+    }
+
+    fn atk_resolver() -> (StatResolver, StatId) {
+        let mut resolver = StatResolver::new();
+        let atk_id = StatId::from_str("ATK");
+        resolver.register_source(atk_id.clone(), Box::new(ConstantSource::new(100.0)));
+        (resolver, atk_id)
+    }
+
+    fn atk(resolver: &mut StatResolver, atk_id: &StatId) -> f64 {
+        let value = resolver.resolve(atk_id, &StatContext::new()).unwrap().value;
+        (value.to_f64() * 1e6).round() / 1e6
+    }
+
+    #[test]
+    fn test_refresh_policy_scales_and_caps_stacks() {
+        let (mut resolver, atk_id) = atk_resolver();
+        let buff = frenzy(StackPolicy::Refresh);
+        let mut buffs = BuffTracker::new();
+
+        for now in 0..7 {
+            buffs.apply_buff(&mut resolver, &buff, 1, now * 10);
+        }
+        assert_eq!(buffs.stacks("frenzy", 1), 5);
+        // One entry holding all five stacks: +15%, not 1.03^5
+        assert_eq!(buffs.len(), 1);
+        assert_eq!(atk(&mut resolver, &atk_id), 115.0);
+
+        // Every stack shares the refreshed expiry (60 + 100)
+        assert!(buffs.tick(&mut resolver, 159).is_empty());
+        assert_eq!(
+            buffs.tick(&mut resolver, 160),
+            BTreeSet::from([atk_id.clone()])
+        );
+        assert_eq!(buffs.stacks("frenzy", 1), 0);
+        assert_eq!(atk(&mut resolver, &atk_id), 100.0);
+    }
+
+    #[test]
+    fn test_replace_policy_keys_by_caster() {
+        let (mut resolver, atk_id) = atk_resolver();
+        let buff = frenzy(StackPolicy::Replace);
+        let mut buffs = BuffTracker::new();
+
+        // The same caster doesn't stack...
+        buffs.apply_buff(&mut resolver, &buff, 1, 0);
+        assert_eq!(buffs.apply_buff(&mut resolver, &buff, 1, 50), 1);
+        // ...but another caster's copy is a separate instance
+        assert_eq!(buffs.apply_buff(&mut resolver, &buff, 2, 60), 1);
+        assert_eq!(atk(&mut resolver, &atk_id), 106.09);
+
+        buffs.tick(&mut resolver, 150);
+        assert_eq!(buffs.stacks("frenzy", 1), 0);
+        assert_eq!(buffs.stacks("frenzy", 2), 1);
+        assert_eq!(atk(&mut resolver, &atk_id), 103.0);
+
+        assert!(buffs.remove_buff(&mut resolver, "frenzy", 2));
+        assert!(!buffs.remove_buff(&mut resolver, "frenzy", 2));
+        assert!(buffs.is_empty());
+        assert_eq!(atk(&mut resolver, &atk_id), 100.0);
+    }
+
+    #[test]
+    fn test_independent_policy_expires_stacks_separately() {
+        let (mut resolver, atk_id) = atk_resolver();
+        let buff = frenzy(StackPolicy::Independent).with_max_stacks(2);
+        let mut buffs = BuffTracker::new();
+
+        buffs.apply_buff(&mut resolver, &buff, 1, 0);
+        buffs.apply_buff(&mut resolver, &buff, 1, 10);
+        // At the limit: the stack from tick 0 makes room
+        assert_eq!(buffs.apply_buff(&mut resolver, &buff, 1, 20), 2);
+        assert_eq!(buffs.next_expiry(), Some(110));
+
+        buffs.tick(&mut resolver, 110);
+        assert_eq!(buffs.stacks("frenzy", 1), 1);
+        assert_eq!(atk(&mut resolver, &atk_id), 103.0);
+
+        buffs.tick(&mut resolver, 120);
+        assert_eq!(buffs.stacks("frenzy", 1), 0);
+        assert!(buffs.is_empty());
+    }
+
+    #[test]
+    fn test_independent_stacks_scale_like_refresh() {
+        let (mut resolver, atk_id) = atk_resolver();
+        let buff = frenzy(StackPolicy::Independent);
+        let mut buffs = BuffTracker::new();
+
+        for now in 0..5 {
+            buffs.apply_buff(&mut resolver, &buff, 1, now * 10);
+        }
+        assert_eq!(buffs.stacks("frenzy", 1), 5);
+        assert_eq!(buffs.len(), 5);
+        // +15% as with Refresh, not 1.03^5
+        assert_eq!(atk(&mut resolver, &atk_id), 115.0);
+
+        // Stacks still expire one at a time
+        assert_eq!(
+            buffs.tick(&mut resolver, 110),
+            BTreeSet::from([atk_id.clone()])
+        );
+        assert_eq!(buffs.stacks("frenzy", 1), 3);
+        assert_eq!(atk(&mut resolver, &atk_id), 109.0);
+
+        assert!(buffs.remove_buff(&mut resolver, "frenzy", 1));
+        assert!(buffs.is_empty());
+        assert_eq!(atk(&mut resolver, &atk_id), 100.0);
+    }
+
+    #[test]
+    fn test_remove_before_expiry() {
+        let mut resolver = StatResolver::new();
+        let hp_id = StatId::from_str("HP");
+        resolver.register_source(hp_id.clone(), Box::new(ConstantSource::new(100.0)));
+
+        let mut buffs = BuffTracker::new();
+        let handle = buffs.apply_for(&mut resolver, &flat(&hp_id, 50.0), 0, 100);
+
+        let context = StatContext::new();
+        assert_eq!(resolver.resolve(&hp_id, &context).unwrap().value, 150.0);
+
+        assert!(buffs.remove(&mut resolver, &handle));
+        assert!(!buffs.remove(&mut resolver, &handle));
+        assert_eq!(resolver.resolve(&hp_id, &context).unwrap().value, 100.0);
+        assert!(buffs.tick(&mut resolver, 100).is_empty());
+    }
+}
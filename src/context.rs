@@ -109,6 +109,34 @@ impl StatContext {
     pub fn contains_key(&self, key: &str) -> bool {
         self.data.contains_key(key)
     }
+
+    /// Capture the current values of the given keys.
+    ///
+    /// Used by the resolver to detect when a cached stat was computed
+    /// under a different context.
+    pub(crate) fn fingerprint(&self, keys: &[String]) -> ContextFingerprint {
+        ContextFingerprint(
+            keys.iter()
+                .map(|key| (key.clone(), self.data.get(key).cloned()))
+                .collect(),
+        )
+    }
+}
+
+/// Snapshot of the context values a resolved stat was computed from.
+///
+/// Missing keys are recorded as `None`, so adding a key that was previously
+/// absent also counts as a change.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ContextFingerprint(Vec<(String, Option<serde_json::Value>)>);
+
+impl ContextFingerprint {
+    /// Check whether the given context still has the recorded values.
+    pub(crate) fn matches(&self, context: &StatContext) -> bool {
+        self.0
+            .iter()
+            .all(|(key, value)| context.data.get(key) == value.as_ref())
+    }
 }
 
 #[cfg(test)]
@@ -135,13 +163,13 @@ mod tests {
     fn test_context_different_types() {
         let mut ctx = StatContext::new();
         ctx.set("int", 42);
-        ctx.set("float", 3.14);
+        ctx.set("float", 2.5);
         ctx.set("bool", true);
         ctx.set("string", "hello");
         ctx.set("vec", vec![1, 2, 3]);
 
         assert_eq!(ctx.get::<i32>("int"), Some(42));
-        assert_eq!(ctx.get::<f64>("float"), Some(3.14));
+        assert_eq!(ctx.get::<f64>("float"), Some(2.5));
         assert_eq!(ctx.get::<bool>("bool"), Some(true));
         assert_eq!(ctx.get::<String>("string"), Some("hello".to_string()));
         assert_eq!(ctx.get::<Vec<i32>>("vec"), Some(vec![1, 2, 3]));
@@ -212,4 +240,28 @@ mod tests {
         // Clone should be unchanged
         assert_eq!(ctx2.get::<i32>("value"), Some(42));
     }
+
+    #[test]
+    fn test_context_fingerprint() {
+        let mut ctx = StatContext::new();
+        ctx.set("in_combat", false);
+        ctx.set("zone", "town");
+
+        let keys = vec!["in_combat".to_string(), "difficulty".to_string()];
+        let fingerprint = ctx.fingerprint(&keys);
+        assert!(fingerprint.matches(&ctx));
+
+        // Unrelated keys don't matter
+        ctx.set("zone", "dungeon");
+        assert!(fingerprint.matches(&ctx));
+
+        // Changed value
+        ctx.set("in_combat", true);
+        assert!(!fingerprint.matches(&ctx));
+        ctx.set("in_combat", false);
+
+        // Previously missing key appears
+        ctx.set("difficulty", 3);
+        assert!(!fingerprint.matches(&ctx));
+    }
 }
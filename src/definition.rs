@@ -0,0 +1,972 @@
+//! Data-driven stat definitions.
+//!
+//! Describes stats, their base sources and their transforms as plain data,
+//! so formulas can live in content files instead of Rust code. The schema
+//! is serde-based; `StatDefinitions::from_json_str` and
+//! `StatDefinitions::from_json_file` load it from JSON and report errors
+//! with their location. The `toml` and `ron` features add the same loaders
+//! for TOML and RON files.
+//!
+//! Definitions may declare named phases. They build the resolver's
+//! `PhaseRegistry`, and transforms can refer to them by name
+//! (`"phase": "gear"`).
+//!
+//! Formulas can be written with the `expression` transform type, using the
+//! syntax of `ExpressionTransform`.
+//!
+//! Values are read as exact `Decimal`s and converted when they are
+//! registered, so the same definitions load into an `f64` or a
+//! fixed-point resolver. They may be written as numbers or as decimal
+//! strings (`"value": "0.1"`), and serialize as decimal strings in every
+//! build.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use zzstat::*;
+//! use zzstat::definition::StatDefinitions;
+//!
+//! let definitions = StatDefinitions::from_json_str(r#"{
+//!     "stats": [
+//!         { "id": "STR", "sources": [{ "type": "constant", "value": 10 }] },
+//!         { "id": "DEX", "sources": [{ "type": "constant", "value": 5 }] },
+//!         {
+//!             "id": "ATK",
+//!             "transforms": [
+//!                 { "type": "scaling", "stat": "STR", "factor": 2 },
+//!                 { "type": "scaling", "stat": "DEX", "factor": 1 }
+//!             ]
+//!         }
+//!     ]
+//! }"#).unwrap();
+//!
+//! let mut resolver = definitions.build_resolver().unwrap();
+//! let atk = resolver.resolve(&StatId::from_str("ATK"), &StatContext::new()).unwrap();
+//! assert_eq!(atk.value, 25.0); // STR * 2 + DEX
+//! ```
+
+use crate::error::StatError;
+use crate::expression::ExpressionTransform;
+use crate::numeric::{Decimal, StatNumeric};
+use crate::phase::PhaseRegistry;
+use crate::resolver::StatResolver;
+use crate::source::{ConstantSource, LabeledSource, StatSource};
+use crate::stat_id::StatId;
+use crate::transform::{
+    AdditiveTransform, ClampTransform, ConditionalTransform, MultiplicativeTransform,
+    OriginTransform, ScalingTransform, StackRule, StatTransform, TransformPhase,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
+
+/// A set of stat definitions, usually loaded from one content file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StatDefinitions {
+    /// Named phases in application order (see `PhaseRegistry`). Empty
+    /// keeps the resolver's phase order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub phases: Vec<PhaseDefinition>,
+
+    /// The defined stats, registered in order.
+    pub stats: Vec<StatDefinition>,
+
+    /// Where the definitions were loaded from, used in error locations.
+    #[serde(skip)]
+    pub origin: Option<String>,
+}
+
+/// Declaration of a named phase.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PhaseDefinition {
+    /// The name transforms refer to the phase by.
+    pub name: String,
+
+    /// The phase transforms declared with this name are registered in.
+    pub phase: TransformPhase,
+}
+
+/// The phase of a defined transform.
+///
+/// Written either as a `TransformPhase` (`"Additive"`, `{ "Custom": 3 }`)
+/// or as the name of a phase declared in `StatDefinitions::phases`
+/// (`"gear"`). The built-in variant names always mean the built-in phases.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum PhaseRef {
+    /// A phase given directly.
+    Phase(TransformPhase),
+    /// The name of a declared phase.
+    Named(String),
+}
+
+impl PhaseRef {
+    /// Look up the phase, using `phases` for names.
+    pub fn resolve(&self, phases: &PhaseRegistry) -> Option<TransformPhase> {
+        match self {
+            PhaseRef::Phase(phase) => Some(*phase),
+            PhaseRef::Named(name) => phases.phase(name),
+        }
+    }
+}
+
+impl From<TransformPhase> for PhaseRef {
+    fn from(phase: TransformPhase) -> Self {
+        PhaseRef::Phase(phase)
+    }
+}
+
+/// Definition of a single stat.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StatDefinition {
+    /// The stat identifier.
+    pub id: String,
+
+    /// Base sources (summed).
+    #[serde(default)]
+    pub sources: Vec<SourceDefinition>,
+
+    /// Transforms, applied by phase and stack rule.
+    #[serde(default)]
+    pub transforms: Vec<TransformDefinition>,
+}
+
+/// Definition of a stat source.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SourceDefinition {
+    /// A constant base value.
+    Constant {
+        /// The value.
+        value: Decimal,
+        /// Description shown in the breakdown.
+        #[serde(default)]
+        description: Option<String>,
+        /// Tags attributing the source.
+        #[serde(default)]
+        tags: BTreeMap<String, String>,
+    },
+}
+
+/// Definition of a stat transform.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TransformDefinition {
+    /// What the transform does.
+    #[serde(flatten)]
+    pub kind: TransformKind,
+
+    /// Phase override; defaults to the transform's own phase.
+    #[serde(default)]
+    pub phase: Option<PhaseRef>,
+
+    /// Stack rule override; defaults to the rule inferred from the phase.
+    #[serde(default)]
+    pub rule: Option<StackRule>,
+
+    /// Origin reported in the typed breakdown.
+    #[serde(default)]
+    pub origin: Option<String>,
+}
+
+/// The kind of a defined transform.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TransformKind {
+    /// Adds a flat value (see `AdditiveTransform`).
+    Additive {
+        /// The value to add.
+        value: Decimal,
+    },
+    /// Multiplies by a factor (see `MultiplicativeTransform`).
+    Multiplicative {
+        /// The multiplier.
+        value: Decimal,
+    },
+    /// Adds another stat scaled by a factor (see `ScalingTransform`).
+    Scaling {
+        /// The stat to scale with.
+        stat: String,
+        /// The scale factor.
+        factor: Decimal,
+    },
+    /// Clamps the value (see `ClampTransform`).
+    Clamp {
+        /// Lower bound.
+        #[serde(default)]
+        min: Option<Decimal>,
+        /// Upper bound.
+        #[serde(default)]
+        max: Option<Decimal>,
+    },
+    /// Applies the inner transform only while a context key has a given
+    /// value (see `ConditionalTransform`).
+    Conditional {
+        /// The `StatContext` key to check.
+        key: String,
+        /// The value the key must have.
+        equals: serde_json::Value,
+        /// The transform to apply while the condition holds. Its `phase`
+        /// and `rule` must be left unset; set them on the conditional.
+        transform: Box<TransformDefinition>,
+    },
+    /// Adds the value of a formula (see `ExpressionTransform`).
+    Expression {
+        /// The formula, e.g. `"DEF / (DEF + 100)"`.
+        formula: String,
+    },
+}
+
+impl StatDefinitions {
+    /// Parse stat definitions from a JSON string.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(StatDefinitions)` - The parsed definitions
+    /// * `Err(StatError::Definition)` - If the JSON is malformed or doesn't
+    ///   match the schema; the location is `line:column`
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use zzstat::StatError;
+    /// use zzstat::definition::StatDefinitions;
+    ///
+    /// let err = StatDefinitions::from_json_str(r#"{ "stats": [{ "id": 5 }] }"#).unwrap_err();
+    /// assert!(matches!(err, StatError::Definition { .. }));
+    /// assert!(err.to_string().contains("1:"));
+    /// ```
+    pub fn from_json_str(json: &str) -> Result<Self, StatError> {
+        Self::parse_json(json, None)
+    }
+
+    /// Load stat definitions from a JSON file.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(StatDefinitions)` - The parsed definitions
+    /// * `Err(StatError::Definition)` - If the file can't be read or parsed;
+    ///   the location is `path:line:column`
+    pub fn from_json_file(path: impl AsRef<Path>) -> Result<Self, StatError> {
+        let path = path.as_ref();
+        let name = path.display().to_string();
+        let json = std::fs::read_to_string(path).map_err(|err| StatError::Definition {
+            location: name.clone(),
+            message: err.to_string(),
+        })?;
+        Self::parse_json(&json, Some(name))
+    }
+
+    /// Parse stat definitions from a TOML string.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(StatDefinitions)` - The parsed definitions
+    /// * `Err(StatError::Definition)` - If the TOML is malformed or doesn't
+    ///   match the schema; the location is `line:column`
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use zzstat::definition::StatDefinitions;
+    ///
+    /// let definitions = StatDefinitions::from_toml_str(r#"
+    ///     [[stats]]
+    ///     id = "HP"
+    ///     sources = [{ type = "constant", value = "100.5" }]
+    /// "#).unwrap();
+    /// assert_eq!(definitions.stats[0].id, "HP");
+    ///
+    /// let err = StatDefinitions::from_toml_str("[[stats]]\nid = 5").unwrap_err();
+    /// assert!(err.to_string().contains("2:6"));
+    /// ```
+    #[cfg(feature = "toml")]
+    pub fn from_toml_str(toml: &str) -> Result<Self, StatError> {
+        Self::parse_toml(toml, None)
+    }
+
+    /// Load stat definitions from a TOML file.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(StatDefinitions)` - The parsed definitions
+    /// * `Err(StatError::Definition)` - If the file can't be read or parsed;
+    ///   the location is `path:line:column`
+    #[cfg(feature = "toml")]
+    pub fn from_toml_file(path: impl AsRef<Path>) -> Result<Self, StatError> {
+        let (text, name) = Self::read_file(path.as_ref())?;
+        Self::parse_toml(&text, Some(name))
+    }
+
+    /// Parse stat definitions from a RON string.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(StatDefinitions)` - The parsed definitions
+    /// * `Err(StatError::Definition)` - If the RON is malformed or doesn't
+    ///   match the schema; the location is `line:column`
+    #[cfg(feature = "ron")]
+    pub fn from_ron_str(ron: &str) -> Result<Self, StatError> {
+        Self::parse_ron(ron, None)
+    }
+
+    /// Load stat definitions from a RON file.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(StatDefinitions)` - The parsed definitions
+    /// * `Err(StatError::Definition)` - If the file can't be read or parsed;
+    ///   the location is `path:line:column`
+    #[cfg(feature = "ron")]
+    pub fn from_ron_file(path: impl AsRef<Path>) -> Result<Self, StatError> {
+        let (text, name) = Self::read_file(path.as_ref())?;
+        Self::parse_ron(&text, Some(name))
+    }
+
+    #[cfg(any(feature = "toml", feature = "ron"))]
+    fn read_file(path: &Path) -> Result<(String, String), StatError> {
+        let name = path.display().to_string();
+        match std::fs::read_to_string(path) {
+            Ok(text) => Ok((text, name)),
+            Err(err) => Err(StatError::Definition {
+                location: name,
+                message: err.to_string(),
+            }),
+        }
+    }
+
+    fn parse_json(json: &str, origin: Option<String>) -> Result<Self, StatError> {
+        let parsed = serde_json::from_str(json)
+            .map_err(|err| (Some((err.line(), err.column())), err.to_string()));
+        Self::loaded(parsed, origin)
+    }
+
+    #[cfg(feature = "toml")]
+    fn parse_toml(toml: &str, origin: Option<String>) -> Result<Self, StatError> {
+        let parsed = toml::from_str(toml).map_err(|err: toml::de::Error| {
+            let position = err.span().map(|span| line_column(toml, span.start));
+            (position, err.message().to_string())
+        });
+        Self::loaded(parsed, origin)
+    }
+
+    #[cfg(feature = "ron")]
+    fn parse_ron(ron: &str, origin: Option<String>) -> Result<Self, StatError> {
+        // Optional fields can be written without `Some(...)`
+        let options = ron::Options::default()
+            .with_default_extension(ron::extensions::Extensions::IMPLICIT_SOME);
+        let parsed = options
+            .from_str(ron)
+            .map_err(|err: ron::error::SpannedError| {
+                (
+                    Some((err.span.start.line, err.span.start.col)),
+                    err.code.to_string(),
+                )
+            });
+        Self::loaded(parsed, origin)
+    }
+
+    /// Attach the origin to parsed definitions, or turn a parse error at
+    /// `(line, column)` into a located `StatError::Definition`.
+    fn loaded(
+        parsed: Result<Self, (Option<(usize, usize)>, String)>,
+        origin: Option<String>,
+    ) -> Result<Self, StatError> {
+        let mut definitions = parsed.map_err(|(position, message)| {
+            let position = position.map(|(line, column)| format!("{}:{}", line, column));
+            let location = match (&origin, position) {
+                (Some(name), Some(position)) => format!("{}:{}", name, position),
+                (Some(name), None) => name.clone(),
+                (None, Some(position)) => position,
+                (None, None) => "definitions".to_string(),
+            };
+            StatError::Definition { location, message }
+        })?;
+        definitions.origin = origin;
+        Ok(definitions)
+    }
+
+    /// Build the phase registry declared by `phases`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(PhaseRegistry)` - The registry, empty if no phases are declared
+    /// * `Err(StatError::Definition)` - If a name or phase is declared twice
+    pub fn phase_registry(&self) -> Result<PhaseRegistry, StatError> {
+        let mut registry = PhaseRegistry::new();
+        for (index, definition) in self.phases.iter().enumerate() {
+            registry
+                .define(definition.name.clone(), definition.phase)
+                .map_err(|err| self.error(format!("phases[{}]", index), err.to_string()))?;
+        }
+        Ok(registry)
+    }
+
+    /// Build a new resolver from these definitions, applying the declared
+    /// phases in order.
+    ///
+    /// See `register_into` for the checks performed.
+    pub fn build_resolver(&self) -> Result<StatResolver, StatError> {
+        let mut resolver = StatResolver::new();
+        self.register_into(&mut resolver)?;
+        Ok(resolver)
+    }
+
+    /// Register these definitions with an existing resolver.
+    ///
+    /// The definitions are validated before anything is registered: stat
+    /// IDs must be unique, referenced stats must be defined, and clamps need
+    /// at least one bound with `min <= max`. Transforms are registered with
+    /// `try_register_transform_with_rule`, so dependency cycles are reported
+    /// here too. Errors are `StatError::Definition` with a location such as
+    /// `stats.json: stats[2] (ATK).transforms[0]`.
+    ///
+    /// Registration is all-or-nothing: everything is registered in a fork
+    /// of `resolver` first and only committed once all definitions have
+    /// been accepted, so an error leaves `resolver` unchanged.
+    ///
+    /// If the definitions declare phases, they replace the resolver's
+    /// phase order (see `StatResolver::set_phases`). Transforms naming a
+    /// phase must name a declared one.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - All stats were registered
+    /// * `Err(StatError::Definition)` - The first invalid definition found
+    pub fn register_into<N: StatNumeric>(
+        &self,
+        resolver: &mut StatResolver<N>,
+    ) -> Result<(), StatError> {
+        let phases = self.validate()?;
+
+        let mut staged = resolver.fork();
+        for (index, stat) in self.stats.iter().enumerate() {
+            let stat_id = StatId::from_str(&stat.id);
+            for source in &stat.sources {
+                staged.register_source(stat_id.clone(), source.build());
+            }
+            for (t_index, definition) in stat.transforms.iter().enumerate() {
+                let transform = definition.build();
+                let phase = match &definition.phase {
+                    Some(phase) => phase.resolve(&phases).expect("phase checked by validate"),
+                    None => transform.phase(),
+                };
+                let rule = definition.rule.clone().unwrap_or_else(|| {
+                    crate::transform::infer_stack_rule_in_phase(transform.as_ref(), phase)
+                });
+                staged
+                    .try_register_transform_with_rule(stat_id.clone(), phase, rule, transform)
+                    .map_err(|err| {
+                        self.error(
+                            format!("{}.transforms[{}]", self.stat_path(index), t_index),
+                            err.to_string(),
+                        )
+                    })?;
+            }
+        }
+
+        staged.commit_into(resolver);
+        if !self.phases.is_empty() {
+            resolver.set_phases(phases);
+        }
+        Ok(())
+    }
+
+    /// Check the definitions without registering anything, returning the
+    /// declared phases.
+    fn validate(&self) -> Result<PhaseRegistry, StatError> {
+        let phases = self.phase_registry()?;
+        let defined: HashSet<&str> = self.stats.iter().map(|stat| stat.id.as_str()).collect();
+        let mut seen = HashSet::new();
+
+        for (index, stat) in self.stats.iter().enumerate() {
+            if !seen.insert(stat.id.as_str()) {
+                return Err(self.error(
+                    self.stat_path(index),
+                    format!("stat {} is defined more than once", stat.id),
+                ));
+            }
+            for (t_index, transform) in stat.transforms.iter().enumerate() {
+                let path = format!("{}.transforms[{}]", self.stat_path(index), t_index);
+                transform
+                    .validate(&defined, &phases)
+                    .map_err(|message| self.error(path, message))?;
+            }
+        }
+
+        Ok(phases)
+    }
+
+    fn stat_path(&self, index: usize) -> String {
+        format!("stats[{}] ({})", index, self.stats[index].id)
+    }
+
+    fn error(&self, path: String, message: String) -> StatError {
+        let location = match &self.origin {
+            Some(name) => format!("{}: {}", name, path),
+            None => path,
+        };
+        StatError::Definition { location, message }
+    }
+}
+
+impl SourceDefinition {
+    fn build<N: StatNumeric>(&self) -> Box<dyn StatSource<N>> {
+        match self {
+            SourceDefinition::Constant {
+                value,
+                description,
+                tags,
+            } => {
+                let source = Box::new(ConstantSource(N::from_decimal(*value)));
+                if description.is_none() && tags.is_empty() {
+                    return source;
+                }
+                let description = description.clone().unwrap_or_else(|| "Constant".into());
+                let mut labeled = LabeledSource::new(description, source);
+                for (key, value) in tags {
+                    labeled = labeled.with_tag(key.clone(), value.clone());
+                }
+                Box::new(labeled)
+            }
+        }
+    }
+}
+
+impl TransformDefinition {
+    fn validate(&self, defined: &HashSet<&str>, phases: &PhaseRegistry) -> Result<(), String> {
+        if let Some(rule) = &self.rule {
+            rule.validate().map_err(|err| err.to_string())?;
+        }
+        if let Some(PhaseRef::Named(name)) = &self.phase {
+            if phases.phase(name).is_none() {
+                return Err(format!("references undeclared phase {}", name));
+            }
+        }
+        match &self.kind {
+            TransformKind::Scaling { stat, .. } if !defined.contains(stat.as_str()) => {
+                Err(format!("references undefined stat {}", stat))
+            }
+            TransformKind::Clamp {
+                min: None,
+                max: None,
+            } => Err("clamp needs at least one of min and max".to_string()),
+            TransformKind::Clamp {
+                min: Some(min),
+                max: Some(max),
+            } if min > max => Err(format!("clamp min {} is greater than max {}", min, max)),
+            TransformKind::Conditional { transform, .. }
+                if transform.phase.is_some() || transform.rule.is_some() =>
+            {
+                Err("conditional inner transform can't set phase or rule; \
+                     set them on the conditional itself"
+                    .to_string())
+            }
+            TransformKind::Conditional { transform, .. } => transform.validate(defined, phases),
+            TransformKind::Expression { formula } => {
+                let expression =
+                    ExpressionTransform::parse(formula).map_err(|err| err.to_string())?;
+                match expression
+                    .depends_on()
+                    .into_iter()
+                    .find(|stat| !defined.contains(stat.as_str()))
+                {
+                    Some(stat) => Err(format!("references undefined stat {}", stat)),
+                    None => Ok(()),
+                }
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn build<N: StatNumeric>(&self) -> Box<dyn StatTransform<N>> {
+        let transform: Box<dyn StatTransform<N>> = match &self.kind {
+            TransformKind::Additive { value } => {
+                Box::new(AdditiveTransform::from_value(N::from_decimal(*value)))
+            }
+            TransformKind::Multiplicative { value } => {
+                Box::new(MultiplicativeTransform::from_value(N::from_decimal(*value)))
+            }
+            TransformKind::Scaling { stat, factor } => Box::new(ScalingTransform::from_value(
+                StatId::from_str(stat),
+                N::from_decimal(*factor),
+            )),
+            TransformKind::Clamp { min, max } => Box::new(ClampTransform::with_bounds(
+                min.map(N::from_decimal),
+                max.map(N::from_decimal),
+            )),
+            TransformKind::Conditional {
+                key,
+                equals,
+                transform,
+            } => {
+                let condition_key = key.clone();
+                let expected = equals.clone();
+                Box::new(
+                    ConditionalTransform::new(
+                        move |ctx| {
+                            ctx.get::<serde_json::Value>(&condition_key) == Some(expected.clone())
+                        },
+                        transform.build(),
+                        format!("if {} == {}", key, equals),
+                    )
+                    .with_context_keys([key.clone()]),
+                )
+            }
+            TransformKind::Expression { formula } => {
+                Box::new(ExpressionTransform::parse(formula).expect("formula checked by validate"))
+            }
+        };
+        match &self.origin {
+            Some(origin) => Box::new(OriginTransform::new(origin.clone(), transform)),
+            None => transform,
+        }
+    }
+}
+
+/// The 1-based line and column of a byte offset in `text`.
+#[cfg(feature = "toml")]
+fn line_column(text: &str, offset: usize) -> (usize, usize) {
+    let before = &text[..offset.min(text.len())];
+    let line_start = before.rfind('\n').map_or(0, |at| at + 1);
+    (
+        before.matches('\n').count() + 1,
+        before[line_start..].chars().count() + 1,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::StatContext;
+    use crate::numeric::StatValue;
+
+    const SHEET: &str = r#"{
+        "stats": [
+            { "id": "VIT", "sources": [{ "type": "constant", "value": 10, "description": "Base" }] },
+            {
+                "id": "REGEN",
+                "transforms": [{ "type": "expression", "formula": "sqrt(VIT * 10) / 2" }]
+            },
+            {
+                "id": "HP",
+                "transforms": [
+                    { "type": "scaling", "stat": "VIT", "factor": 10, "origin": "vitality" },
+                    {
+                        "type": "conditional",
+                        "key": "in_combat",
+                        "equals": true,
+                        "transform": { "type": "multiplicative", "value": 1.5 }
+                    },
+                    { "type": "clamp", "max": 120 }
+                ]
+            }
+        ]
+    }"#;
+
+    #[test]
+    fn test_build_resolver_from_json() {
+        let definitions = StatDefinitions::from_json_str(SHEET).unwrap();
+        let mut resolver = definitions.build_resolver().unwrap();
+        let hp_id = StatId::from_str("HP");
+
+        let mut context = StatContext::new();
+        let resolved = resolver.resolve(&hp_id, &context).unwrap();
+        assert_eq!(resolved.value, StatValue::from_f64(100.0));
+        assert_eq!(
+            resolved.breakdown[0].stacks[0].transforms[0]
+                .origin
+                .as_deref(),
+            Some("vitality")
+        );
+
+        // The conditional follows the context and the clamp caps the result
+        context.set("in_combat", true);
+        let resolved = resolver.resolve(&hp_id, &context).unwrap();
+        assert_eq!(resolved.value, StatValue::from_f64(120.0));
+
+        let regen = resolver
+            .resolve(&StatId::from_str("REGEN"), &context)
+            .unwrap();
+        assert_eq!(regen.value, StatValue::from_f64(5.0));
+
+        let vit = resolver
+            .resolve(&StatId::from_str("VIT"), &context)
+            .unwrap();
+        assert_eq!(vit.sources[0].0, "Base");
+    }
+
+    #[test]
+    fn test_parse_error_has_line_and_column() {
+        let json = "{\n  \"stats\": [\n    { \"id\": \"HP\", \"sources\": [{ \"type\": \"nope\" }] }\n  ]\n}";
+        match StatDefinitions::from_json_str(json) {
+            Err(StatError::Definition { location, .. }) => assert!(location.starts_with("3:")),
+            other => panic!("Expected Definition error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_validation_errors_have_paths() {
+        let mut definitions = StatDefinitions::from_json_str(
+            r#"{ "stats": [
+                { "id": "ATK", "transforms": [{ "type": "scaling", "stat": "STRR", "factor": 2 }] }
+            ] }"#,
+        )
+        .unwrap();
+        definitions.origin = Some("stats.json".to_string());
+
+        match definitions.build_resolver() {
+            Err(StatError::Definition { location, message }) => {
+                assert_eq!(location, "stats.json: stats[0] (ATK).transforms[0]");
+                assert!(message.contains("STRR"));
+            }
+            other => panic!("Expected Definition error, got {:?}", other.err()),
+        }
+    }
+
+    #[test]
+    fn test_invalid_formula_is_reported() {
+        let mut definitions = StatDefinitions::from_json_str(
+            r#"{ "stats": [
+                { "id": "DEF", "sources": [{ "type": "constant", "value": 1 }] },
+                { "id": "DR", "transforms": [{ "type": "expression", "formula": "DEF / (DEF +" }] },
+                { "id": "EVA", "transforms": [{ "type": "expression", "formula": "AGI * 2" }] }
+            ] }"#,
+        )
+        .unwrap();
+
+        match definitions.build_resolver() {
+            Err(StatError::Definition { location, message }) => {
+                assert_eq!(location, "stats[1] (DR).transforms[0]");
+                assert!(message.contains("DEF / (DEF +"));
+            }
+            other => panic!("Expected Definition error, got {:?}", other.err()),
+        }
+
+        definitions.stats.remove(1);
+        match definitions.build_resolver() {
+            Err(StatError::Definition { message, .. }) => assert!(message.contains("AGI")),
+            other => panic!("Expected Definition error, got {:?}", other.err()),
+        }
+    }
+
+    #[test]
+    fn test_cycle_is_reported_with_location() {
+        let definitions = StatDefinitions::from_json_str(
+            r#"{ "stats": [
+                { "id": "A", "transforms": [{ "type": "scaling", "stat": "B", "factor": 1 }] },
+                { "id": "B", "transforms": [{ "type": "scaling", "stat": "A", "factor": 1 }] }
+            ] }"#,
+        )
+        .unwrap();
+
+        match definitions.build_resolver() {
+            Err(StatError::Definition { location, message }) => {
+                assert_eq!(location, "stats[1] (B).transforms[0]");
+                assert!(message.contains("B -> A -> B"));
+            }
+            other => panic!("Expected Definition error, got {:?}", other.err()),
+        }
+    }
+
+    #[test]
+    fn test_failed_registration_leaves_resolver_unchanged() {
+        let definitions = StatDefinitions::from_json_str(
+            r#"{ "stats": [
+                { "id": "A", "sources": [{ "type": "constant", "value": 5 }] },
+                { "id": "B", "transforms": [{ "type": "scaling", "stat": "A", "factor": 1 }] }
+            ] }"#,
+        )
+        .unwrap();
+        let mut resolver = definitions.build_resolver().unwrap();
+
+        let cyclic = StatDefinitions::from_json_str(
+            r#"{ "stats": [
+                { "id": "A", "sources": [{ "type": "constant", "value": 100 }] },
+                { "id": "B", "transforms": [{ "type": "additive", "value": 1 }] },
+                { "id": "C", "transforms": [{ "type": "scaling", "stat": "D", "factor": 1 }] },
+                { "id": "D", "transforms": [{ "type": "scaling", "stat": "C", "factor": 1 }] }
+            ] }"#,
+        )
+        .unwrap();
+        assert!(cyclic.register_into(&mut resolver).is_err());
+
+        // Nothing from the failed definitions was registered
+        let context = StatContext::new();
+        let b = resolver.resolve(&StatId::from_str("B"), &context).unwrap();
+        assert_eq!(b.value, StatValue::from_f64(5.0));
+        assert!(resolver.resolve(&StatId::from_str("C"), &context).is_err());
+    }
+
+    #[test]
+    fn test_phase_override_infers_rule_from_phase() {
+        let definitions = StatDefinitions::from_json_str(
+            r#"{ "stats": [
+                {
+                    "id": "ATK",
+                    "transforms": [{
+                        "type": "conditional",
+                        "key": "in_combat",
+                        "equals": true,
+                        "phase": "Additive",
+                        "transform": { "type": "additive", "value": 10 }
+                    }]
+                },
+                {
+                    "id": "DEF",
+                    "transforms": [{ "type": "additive", "value": 10, "phase": "Multiplicative" }]
+                }
+            ] }"#,
+        )
+        .unwrap();
+        let mut resolver = definitions.build_resolver().unwrap();
+
+        let mut context = StatContext::new();
+        context.set("in_combat", true);
+        let atk = resolver
+            .resolve(&StatId::from_str("ATK"), &context)
+            .unwrap();
+        assert_eq!(atk.value, StatValue::from_f64(10.0));
+        let def = resolver
+            .resolve(&StatId::from_str("DEF"), &context)
+            .unwrap();
+        assert_eq!(def.value, StatValue::from_f64(10.0));
+    }
+
+    #[test]
+    fn test_conditional_inner_phase_rejected() {
+        let definitions = StatDefinitions::from_json_str(
+            r#"{ "stats": [{
+                "id": "ATK",
+                "transforms": [{
+                    "type": "conditional",
+                    "key": "in_combat",
+                    "equals": true,
+                    "transform": { "type": "additive", "value": 10, "phase": "Final" }
+                }]
+            }] }"#,
+        )
+        .unwrap();
+
+        match definitions.build_resolver() {
+            Err(StatError::Definition { location, message }) => {
+                assert_eq!(location, "stats[0] (ATK).transforms[0]");
+                assert!(message.contains("phase or rule"));
+            }
+            other => panic!("Expected Definition error, got {:?}", other.err()),
+        }
+    }
+
+    const PHASED_SHEET: &str = r#"{
+        "phases": [
+            { "name": "gear", "phase": { "Custom": 3 } },
+            { "name": "cap", "phase": "Final" }
+        ],
+        "stats": [{
+            "id": "HP",
+            "sources": [{ "type": "constant", "value": 100 }],
+            "transforms": [
+                { "type": "additive", "value": 50, "phase": "gear" },
+                { "type": "clamp", "max": 120, "phase": "Final" }
+            ]
+        }]
+    }"#;
+
+    #[test]
+    fn test_named_phases() {
+        let definitions = StatDefinitions::from_json_str(PHASED_SHEET).unwrap();
+        let mut resolver = definitions.build_resolver().unwrap();
+        assert_eq!(
+            resolver.phases().phase("gear"),
+            Some(TransformPhase::Custom(3))
+        );
+
+        // The gear phase runs before the cap instead of after it
+        let hp = resolver
+            .resolve(&StatId::from_str("HP"), &StatContext::new())
+            .unwrap();
+        assert_eq!(hp.value, StatValue::from_f64(120.0));
+
+        let undeclared = PHASED_SHEET.replace(r#""phase": "gear""#, r#""phase": "buff""#);
+        match StatDefinitions::from_json_str(&undeclared)
+            .unwrap()
+            .build_resolver()
+        {
+            Err(StatError::Definition { location, message }) => {
+                assert_eq!(location, "stats[0] (HP).transforms[0]");
+                assert!(message.contains("undeclared phase buff"));
+            }
+            other => panic!("Expected Definition error, got {:?}", other.err()),
+        }
+
+        let twice = PHASED_SHEET.replace(r#""name": "cap""#, r#""name": "gear""#);
+        match StatDefinitions::from_json_str(&twice)
+            .unwrap()
+            .build_resolver()
+        {
+            Err(StatError::Definition { location, .. }) => assert_eq!(location, "phases[1]"),
+            other => panic!("Expected Definition error, got {:?}", other.err()),
+        }
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_toml_definitions() {
+        let definitions = StatDefinitions::from_toml_str(
+            r#"
+            phases = [
+                { name = "gear", phase = { Custom = 3 } },
+                { name = "cap", phase = "Final" },
+            ]
+
+            [[stats]]
+            id = "HP"
+            sources = [{ type = "constant", value = 100 }]
+            transforms = [
+                { type = "additive", value = "50", phase = "gear" },
+                { type = "clamp", max = 120.0, phase = "Final" },
+            ]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            definitions,
+            StatDefinitions::from_json_str(PHASED_SHEET).unwrap()
+        );
+
+        match StatDefinitions::from_toml_str("[[stats]]\nid = \"HP\"\nsources = 5\n") {
+            Err(StatError::Definition { location, .. }) => assert_eq!(location, "3:11"),
+            other => panic!("Expected Definition error, got {:?}", other),
+        }
+    }
+
+    #[cfg(feature = "ron")]
+    #[test]
+    fn test_ron_definitions() {
+        let definitions = StatDefinitions::from_ron_str(
+            r#"(
+                phases: [
+                    (name: "gear", phase: Custom(3)),
+                    (name: "cap", phase: Final),
+                ],
+                stats: [(
+                    id: "HP",
+                    sources: [{ "type": "constant", "value": 100 }],
+                    transforms: [
+                        { "type": "additive", "value": "50", "phase": "gear" },
+                        { "type": "clamp", "max": 120, "phase": Final },
+                    ],
+                )],
+            )"#,
+        )
+        .unwrap();
+        assert_eq!(
+            definitions,
+            StatDefinitions::from_json_str(PHASED_SHEET).unwrap()
+        );
+
+        match StatDefinitions::from_ron_str("(\n    stats: 5,\n)") {
+            Err(StatError::Definition { location, .. }) => assert!(location.starts_with("2:")),
+            other => panic!("Expected Definition error, got {:?}", other),
+        }
+    }
+}
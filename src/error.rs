@@ -58,6 +58,51 @@ pub enum StatError {
     /// Contains the stat ID and a description of what went wrong.
     #[error("Invalid transform for stat {0}: {1}")]
     InvalidTransform(StatId, String),
+
+    /// A data-driven stat definition is invalid.
+    ///
+    /// Contains where the problem is (file, line and column for parse
+    /// errors, or the path to the offending entry) and what is wrong.
+    #[error("Invalid stat definition at {location}: {message}")]
+    Definition { location: String, message: String },
+
+    /// A stat formula could not be parsed.
+    ///
+    /// Contains the formula, the byte offset of the problem and what is wrong.
+    #[error("Invalid expression `{expression}` at position {position}: {message}")]
+    ExpressionParse {
+        expression: String,
+        position: usize,
+        message: String,
+    },
+
+    /// A stat formula could not be evaluated, e.g. because it divides by zero.
+    #[error("Cannot evaluate expression `{expression}`: {message}")]
+    ExpressionEval { expression: String, message: String },
+
+    /// Serialized data was written in a format version this release can't read.
+    #[error("Unsupported format version {found} (supported: {supported})")]
+    UnsupportedVersion { found: u32, supported: u32 },
+
+    /// A diminishing returns curve is malformed.
+    #[error("Invalid curve: {0}")]
+    InvalidCurve(String),
+
+    /// A phase registry declares a name or phase twice.
+    #[error("Invalid phase: {0}")]
+    InvalidPhase(String),
+
+    /// Fixed-point arithmetic overflowed or divided by zero.
+    #[error("Arithmetic overflow: {0}")]
+    Overflow(String),
+
+    /// A decimal number could not be parsed exactly.
+    #[error("Invalid number: {0}")]
+    InvalidNumber(String),
+
+    /// A deserialized resource pool is outside `0 <= current <= max`.
+    #[error("Invalid resource pool: {0}")]
+    InvalidResource(String),
 }
 
 #[cfg(test)]
@@ -0,0 +1,825 @@
+//! Expression transforms.
+//!
+//! A small formula language for derived stats, so `ATK = STR * 2 + DEX`
+//! or `DEF / (DEF + 100)` can be written as one transform instead of a
+//! chain of built-ins.
+//!
+//! # Syntax
+//!
+//! - Numbers: `2`, `0.5`, `1e3` (parsed as exact decimals and converted
+//!   to the resolver's numeric type)
+//! - Stat references: identifiers such as `STR` or `FIRE_RES`
+//! - Operators: `+`, `-`, `*`, `/`, `^` (integer power), unary `-`,
+//!   parentheses
+//! - Functions: `min(a, b, ...)`, `max(a, b, ...)`, `abs(x)`, `sqrt(x)`,
+//!   `floor(x)`, `ceil(x)`, `round(x)`, `clamp(x, lo, hi)`
+//!
+//! Evaluation has no side effects, no loops and no access to anything but
+//! the referenced stats, so formulas from content files are safe to run.
+//! Nesting is limited to [`MAX_EXPRESSION_DEPTH`] levels.
+//!
+//! Powers are computed by repeated multiplication, so the exponent must be
+//! an integer between -64 and 64; other functions only use operations that
+//! IEEE 754 defines exactly, so results are the same on every platform.
+
+use crate::context::StatContext;
+use crate::error::StatError;
+use crate::numeric::{Decimal, StatNumeric};
+use crate::stat_id::StatId;
+use crate::transform::{Contribution, StatTransform, TransformPhase};
+use std::collections::HashMap;
+
+/// Maximum nesting depth of a formula, counting parentheses, function
+/// calls, unary minus, powers and each operator in a chain such as
+/// `a + b + c`.
+pub const MAX_EXPRESSION_DEPTH: usize = 64;
+
+/// A transform that adds the value of a formula over other stats.
+///
+/// Dependencies are taken from the stat identifiers used in the formula,
+/// so `depends_on()` never has to be written by hand. Like
+/// `ScalingTransform`, the transform is additive: it adds the formula's
+/// value to the input.
+///
+/// # Examples
+///
+/// ```rust
+/// use zzstat::expression::ExpressionTransform;
+/// use zzstat::transform::StatTransform;
+/// use zzstat::{StatContext, StatId};
+/// use std::collections::HashMap;
+///
+/// let transform = ExpressionTransform::parse("STR * 2 + DEX").unwrap();
+/// assert_eq!(
+///     transform.depends_on(),
+///     vec![StatId::from_str("STR"), StatId::from_str("DEX")]
+/// );
+///
+/// let mut deps = HashMap::new();
+/// deps.insert(StatId::from_str("STR"), 10.0);
+/// deps.insert(StatId::from_str("DEX"), 5.0);
+///
+/// let context = StatContext::new();
+/// // 100 (base) + 10 * 2 + 5 = 125
+/// assert_eq!(transform.apply(100.0, &deps, &context).unwrap(), 125.0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ExpressionTransform {
+    source: String,
+    expr: Expr,
+    dependencies: Vec<StatId>,
+}
+
+impl ExpressionTransform {
+    /// Parse a formula into a transform.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The formula, e.g. `"sqrt(LEVEL) * AGI"`
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ExpressionTransform)` - The parsed transform
+    /// * `Err(StatError::ExpressionParse)` - If the formula is malformed or
+    ///   nested deeper than [`MAX_EXPRESSION_DEPTH`], with the byte offset
+    ///   of the problem
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use zzstat::expression::ExpressionTransform;
+    /// use zzstat::StatError;
+    ///
+    /// assert!(ExpressionTransform::parse("DEF / (DEF + 100)").is_ok());
+    ///
+    /// match ExpressionTransform::parse("STR * (2 + DEX") {
+    ///     Err(StatError::ExpressionParse { position, .. }) => assert_eq!(position, 14),
+    ///     other => panic!("unexpected {:?}", other),
+    /// }
+    /// ```
+    pub fn parse(source: &str) -> Result<Self, StatError> {
+        let tokens = tokenize(source)?;
+        let mut parser = Parser {
+            source,
+            tokens,
+            pos: 0,
+            depth: 0,
+        };
+        let expr = parser.expression()?;
+        if let Some(token) = parser.peek() {
+            return Err(parse_error(
+                source,
+                token.position,
+                format!("unexpected {}", token.kind),
+            ));
+        }
+
+        let mut dependencies = Vec::new();
+        expr.collect_stats(&mut dependencies);
+
+        Ok(Self {
+            source: source.to_string(),
+            expr,
+            dependencies,
+        })
+    }
+
+    /// Get the formula this transform was parsed from.
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Get the stats the formula reads, in order of first use.
+    ///
+    /// The same as `StatTransform::depends_on`, without naming a numeric
+    /// backend (a formula works with all of them).
+    pub fn depends_on(&self) -> Vec<StatId> {
+        self.dependencies.clone()
+    }
+
+    /// Evaluate the formula against resolved dependency values.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(N)` - The formula's value
+    /// * `Err(StatError::MissingDependency)` - If a referenced stat is missing
+    /// * `Err(StatError::ExpressionEval)` - On division by zero, the square
+    ///   root of a negative number, or `clamp` with `lo > hi`
+    pub fn evaluate<N: StatNumeric>(
+        &self,
+        dependencies: &HashMap<StatId, N>,
+    ) -> Result<N, StatError> {
+        self.expr.evaluate(dependencies).map_err(|err| match err {
+            EvalError::Missing(id) => StatError::MissingDependency(id),
+            EvalError::Invalid(message) => StatError::ExpressionEval {
+                expression: self.source.clone(),
+                message,
+            },
+        })
+    }
+}
+
+impl<N: StatNumeric> StatTransform<N> for ExpressionTransform {
+    fn depends_on(&self) -> Vec<StatId> {
+        self.dependencies.clone()
+    }
+
+    fn phase(&self) -> TransformPhase {
+        TransformPhase::Additive
+    }
+
+    fn apply(
+        &self,
+        input: N,
+        dependencies: &HashMap<StatId, N>,
+        _context: &StatContext,
+    ) -> Result<N, StatError> {
+        Ok(input + self.evaluate(dependencies)?)
+    }
+
+    fn description(&self) -> String {
+        format!("expr({})", self.source)
+    }
+
+    fn contribution(
+        &self,
+        dependencies: &HashMap<StatId, N>,
+        _context: &StatContext,
+    ) -> Result<Option<Contribution<N>>, StatError> {
+        Ok(Some(Contribution::Delta(self.evaluate(dependencies)?)))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Pow,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Function {
+    Min,
+    Max,
+    Abs,
+    Sqrt,
+    Floor,
+    Ceil,
+    Round,
+    Clamp,
+}
+
+impl Function {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "min" => Some(Function::Min),
+            "max" => Some(Function::Max),
+            "abs" => Some(Function::Abs),
+            "sqrt" => Some(Function::Sqrt),
+            "floor" => Some(Function::Floor),
+            "ceil" => Some(Function::Ceil),
+            "round" => Some(Function::Round),
+            "clamp" => Some(Function::Clamp),
+            _ => None,
+        }
+    }
+
+    /// Check the argument count, returning the expected arity on mismatch.
+    fn check_arity(self, count: usize) -> Result<(), &'static str> {
+        match self {
+            Function::Min | Function::Max if count < 2 => Err("at least 2 arguments"),
+            Function::Min | Function::Max => Ok(()),
+            Function::Clamp if count != 3 => Err("3 arguments"),
+            Function::Clamp => Ok(()),
+            _ if count != 1 => Err("1 argument"),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Expr {
+    Number(Decimal),
+    Stat(StatId),
+    Neg(Box<Expr>),
+    Binary(BinaryOp, Box<Expr>, Box<Expr>),
+    Call(Function, Vec<Expr>),
+}
+
+enum EvalError {
+    Missing(StatId),
+    Invalid(String),
+}
+
+impl Expr {
+    fn collect_stats(&self, out: &mut Vec<StatId>) {
+        match self {
+            Expr::Number(_) => {}
+            Expr::Stat(id) => {
+                if !out.contains(id) {
+                    out.push(id.clone());
+                }
+            }
+            Expr::Neg(inner) => inner.collect_stats(out),
+            Expr::Binary(_, lhs, rhs) => {
+                lhs.collect_stats(out);
+                rhs.collect_stats(out);
+            }
+            Expr::Call(_, args) => args.iter().for_each(|arg| arg.collect_stats(out)),
+        }
+    }
+
+    fn evaluate<N: StatNumeric>(&self, deps: &HashMap<StatId, N>) -> Result<N, EvalError> {
+        match self {
+            Expr::Number(n) => Ok(N::from_decimal(*n)),
+            Expr::Stat(id) => deps
+                .get(id)
+                .copied()
+                .ok_or_else(|| EvalError::Missing(id.clone())),
+            Expr::Neg(inner) => Ok(N::zero() - inner.evaluate(deps)?),
+            Expr::Binary(op, lhs, rhs) => {
+                let lhs = lhs.evaluate(deps)?;
+                let rhs = rhs.evaluate(deps)?;
+                match op {
+                    BinaryOp::Add => Ok(lhs + rhs),
+                    BinaryOp::Sub => Ok(lhs - rhs),
+                    BinaryOp::Mul => Ok(lhs * rhs),
+                    BinaryOp::Div if rhs == N::zero() => {
+                        Err(EvalError::Invalid("division by zero".to_string()))
+                    }
+                    BinaryOp::Div => Ok(lhs / rhs),
+                    BinaryOp::Pow => power(lhs, rhs),
+                }
+            }
+            Expr::Call(function, args) => {
+                let values = args
+                    .iter()
+                    .map(|arg| arg.evaluate(deps))
+                    .collect::<Result<Vec<_>, _>>()?;
+                call(*function, &values)
+            }
+        }
+    }
+}
+
+fn power<N: StatNumeric>(base: N, exponent: N) -> Result<N, EvalError> {
+    let exp = exponent.to_f64();
+    if exp.fract() != 0.0 || exp.abs() > 64.0 {
+        return Err(EvalError::Invalid(format!(
+            "exponent {} is not an integer between -64 and 64",
+            exponent
+        )));
+    }
+    // Repeated multiplication keeps powers exact and portable
+    let mut result = N::from_int(1);
+    for _ in 0..exp.abs() as u32 {
+        result *= base;
+    }
+    if exp < 0.0 {
+        if result == N::zero() {
+            return Err(EvalError::Invalid("division by zero".to_string()));
+        }
+        result = N::from_int(1) / result;
+    }
+    Ok(result)
+}
+
+fn call<N: StatNumeric>(function: Function, args: &[N]) -> Result<N, EvalError> {
+    let first = args[0];
+    let result = match function {
+        Function::Min => args[1..]
+            .iter()
+            .fold(first, |acc, &v| if v < acc { v } else { acc }),
+        Function::Max => args[1..]
+            .iter()
+            .fold(first, |acc, &v| if v > acc { v } else { acc }),
+        Function::Abs if first < N::zero() => N::zero() - first,
+        Function::Abs => first,
+        Function::Sqrt if first < N::zero() => {
+            return Err(EvalError::Invalid(format!(
+                "sqrt of negative number {}",
+                first
+            )));
+        }
+        Function::Sqrt => N::from_f64(first.to_f64().sqrt()),
+        Function::Floor => N::from_f64(first.to_f64().floor()),
+        Function::Ceil => N::from_f64(first.to_f64().ceil()),
+        Function::Round => N::from_f64(first.to_f64().round()),
+        Function::Clamp if args[1] > args[2] => {
+            return Err(EvalError::Invalid(format!(
+                "clamp lower bound {} is greater than upper bound {}",
+                args[1], args[2]
+            )));
+        }
+        Function::Clamp => StatNumeric::clamp(first, args[1], args[2]),
+    };
+    Ok(result)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum TokenKind {
+    Number(Decimal),
+    Ident(String),
+    Op(char),
+    LParen,
+    RParen,
+    Comma,
+}
+
+impl std::fmt::Display for TokenKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TokenKind::Number(n) => write!(f, "number {}", n),
+            TokenKind::Ident(name) => write!(f, "identifier {}", name),
+            TokenKind::Op(op) => write!(f, "'{}'", op),
+            TokenKind::LParen => write!(f, "'('"),
+            TokenKind::RParen => write!(f, "')'"),
+            TokenKind::Comma => write!(f, "','"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    kind: TokenKind,
+    position: usize,
+}
+
+fn parse_error(source: &str, position: usize, message: String) -> StatError {
+    StatError::ExpressionParse {
+        expression: source.to_string(),
+        position,
+        message,
+    }
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>, StatError> {
+    let bytes = source.as_bytes();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let c = bytes[i] as char;
+        let start = i;
+        let kind = match c {
+            ' ' | '\t' | '\n' | '\r' => {
+                i += 1;
+                continue;
+            }
+            '+' | '-' | '*' | '/' | '^' => TokenKind::Op(c),
+            '(' => TokenKind::LParen,
+            ')' => TokenKind::RParen,
+            ',' => TokenKind::Comma,
+            '0'..='9' | '.' => {
+                while i < bytes.len() && (bytes[i].is_ascii_digit() || bytes[i] == b'.') {
+                    i += 1;
+                }
+                // Optional exponent: 1e3, 2.5E-2
+                if i < bytes.len() && (bytes[i] == b'e' || bytes[i] == b'E') {
+                    let mut j = i + 1;
+                    if j < bytes.len() && (bytes[j] == b'+' || bytes[j] == b'-') {
+                        j += 1;
+                    }
+                    if j < bytes.len() && bytes[j].is_ascii_digit() {
+                        i = j;
+                        while i < bytes.len() && bytes[i].is_ascii_digit() {
+                            i += 1;
+                        }
+                    }
+                }
+                let text = &source[start..i];
+                let value = text
+                    .parse::<Decimal>()
+                    .map_err(|_| parse_error(source, start, format!("invalid number {}", text)))?;
+                tokens.push(Token {
+                    kind: TokenKind::Number(value),
+                    position: start,
+                });
+                continue;
+            }
+            c if c.is_ascii_alphabetic() || c == '_' => {
+                while i < bytes.len() && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'_') {
+                    i += 1;
+                }
+                tokens.push(Token {
+                    kind: TokenKind::Ident(source[start..i].to_string()),
+                    position: start,
+                });
+                continue;
+            }
+            _ => {
+                let c = source[start..].chars().next().unwrap_or(c);
+                return Err(parse_error(
+                    source,
+                    start,
+                    format!("unexpected character '{}'", c),
+                ));
+            }
+        };
+        tokens.push(Token {
+            kind,
+            position: start,
+        });
+        i += 1;
+    }
+
+    Ok(tokens)
+}
+
+/// Recursive descent parser.
+///
+/// ```text
+/// expression = term (('+' | '-') term)*
+/// term       = unary (('*' | '/') unary)*
+/// unary      = '-' unary | power
+/// power      = primary ('^' unary)?
+/// primary    = number | ident | ident '(' args ')' | '(' expression ')'
+/// ```
+struct Parser<'a> {
+    source: &'a str,
+    tokens: Vec<Token>,
+    pos: usize,
+    depth: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn eat_op(&mut self, ops: &[char]) -> Option<char> {
+        match self.peek() {
+            Some(Token {
+                kind: TokenKind::Op(op),
+                ..
+            }) if ops.contains(op) => {
+                let op = *op;
+                self.pos += 1;
+                Some(op)
+            }
+            _ => None,
+        }
+    }
+
+    fn expect(&mut self, kind: TokenKind) -> Result<(), StatError> {
+        match self.next() {
+            Some(token) if token.kind == kind => Ok(()),
+            Some(token) => Err(parse_error(
+                self.source,
+                token.position,
+                format!("expected {}, found {}", kind, token.kind),
+            )),
+            None => Err(self.end_error(format!("expected {}", kind))),
+        }
+    }
+
+    fn end_error(&self, message: String) -> StatError {
+        parse_error(
+            self.source,
+            self.source.len(),
+            format!("{} at end of expression", message),
+        )
+    }
+
+    fn expression(&mut self) -> Result<Expr, StatError> {
+        let depth = self.depth;
+        let mut lhs = self.term()?;
+        while let Some(op) = self.eat_op(&['+', '-']) {
+            // Each operator puts the chain so far one level deeper
+            self.enter()?;
+            let rhs = self.term()?;
+            let op = if op == '+' {
+                BinaryOp::Add
+            } else {
+                BinaryOp::Sub
+            };
+            lhs = Expr::Binary(op, Box::new(lhs), Box::new(rhs));
+        }
+        self.depth = depth;
+        Ok(lhs)
+    }
+
+    fn term(&mut self) -> Result<Expr, StatError> {
+        let depth = self.depth;
+        let mut lhs = self.unary()?;
+        while let Some(op) = self.eat_op(&['*', '/']) {
+            self.enter()?;
+            let rhs = self.unary()?;
+            let op = if op == '*' {
+                BinaryOp::Mul
+            } else {
+                BinaryOp::Div
+            };
+            lhs = Expr::Binary(op, Box::new(lhs), Box::new(rhs));
+        }
+        self.depth = depth;
+        Ok(lhs)
+    }
+
+    /// Go one level deeper, failing past `MAX_EXPRESSION_DEPTH`.
+    ///
+    /// Every recursive rule and every chained operator comes through here,
+    /// which bounds the stack used to parse, evaluate and drop the tree.
+    fn enter(&mut self) -> Result<(), StatError> {
+        if self.depth == MAX_EXPRESSION_DEPTH {
+            let position = self
+                .peek()
+                .map_or(self.source.len(), |token| token.position);
+            return Err(parse_error(
+                self.source,
+                position,
+                format!(
+                    "expression is nested more than {} levels deep",
+                    MAX_EXPRESSION_DEPTH
+                ),
+            ));
+        }
+        self.depth += 1;
+        Ok(())
+    }
+
+    fn unary(&mut self) -> Result<Expr, StatError> {
+        self.enter()?;
+        let result = if self.eat_op(&['-']).is_some() {
+            self.unary().map(|inner| Expr::Neg(Box::new(inner)))
+        } else {
+            self.power()
+        };
+        self.depth -= 1;
+        result
+    }
+
+    fn power(&mut self) -> Result<Expr, StatError> {
+        let base = self.primary()?;
+        if self.eat_op(&['^']).is_some() {
+            // Right-associative: 2 ^ 3 ^ 2 == 2 ^ 9
+            let exponent = self.unary()?;
+            return Ok(Expr::Binary(
+                BinaryOp::Pow,
+                Box::new(base),
+                Box::new(exponent),
+            ));
+        }
+        Ok(base)
+    }
+
+    fn primary(&mut self) -> Result<Expr, StatError> {
+        let token = self
+            .next()
+            .ok_or_else(|| self.end_error("expected a value".to_string()))?;
+        match token.kind {
+            TokenKind::Number(n) => Ok(Expr::Number(n)),
+            TokenKind::LParen => {
+                let inner = self.expression()?;
+                self.expect(TokenKind::RParen)?;
+                Ok(inner)
+            }
+            TokenKind::Ident(name) => {
+                if self.peek().map(|t| &t.kind) != Some(&TokenKind::LParen) {
+                    return Ok(Expr::Stat(StatId::from_str(&name)));
+                }
+                let function = Function::from_name(&name).ok_or_else(|| {
+                    parse_error(
+                        self.source,
+                        token.position,
+                        format!("unknown function {}", name),
+                    )
+                })?;
+                self.pos += 1; // '('
+                let args = self.arguments()?;
+                function.check_arity(args.len()).map_err(|expected| {
+                    parse_error(
+                        self.source,
+                        token.position,
+                        format!("{} takes {}, got {}", name, expected, args.len()),
+                    )
+                })?;
+                Ok(Expr::Call(function, args))
+            }
+            kind => Err(parse_error(
+                self.source,
+                token.position,
+                format!("expected a value, found {}", kind),
+            )),
+        }
+    }
+
+    fn arguments(&mut self) -> Result<Vec<Expr>, StatError> {
+        let mut args = Vec::new();
+        if self.peek().map(|t| &t.kind) == Some(&TokenKind::RParen) {
+            self.pos += 1;
+            return Ok(args);
+        }
+        loop {
+            args.push(self.expression()?);
+            match self.next() {
+                Some(Token {
+                    kind: TokenKind::Comma,
+                    ..
+                }) => continue,
+                Some(Token {
+                    kind: TokenKind::RParen,
+                    ..
+                }) => return Ok(args),
+                Some(token) => {
+                    return Err(parse_error(
+                        self.source,
+                        token.position,
+                        format!("expected ',' or ')', found {}", token.kind),
+                    ))
+                }
+                None => return Err(self.end_error("expected ')'".to_string())),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::numeric::StatValue;
+
+    fn eval(source: &str, deps: &[(&str, f64)]) -> Result<f64, StatError> {
+        let deps = deps
+            .iter()
+            .map(|(id, value)| (StatId::from_str(id), StatValue::from_f64(*value)))
+            .collect();
+        ExpressionTransform::parse(source)?
+            .evaluate(&deps)
+            .map(|v| v.to_f64())
+    }
+
+    #[test]
+    fn test_precedence_and_associativity() {
+        assert_eq!(eval("1 + 2 * 3", &[]).unwrap(), 7.0);
+        assert_eq!(eval("(1 + 2) * 3", &[]).unwrap(), 9.0);
+        assert_eq!(eval("10 - 4 - 3", &[]).unwrap(), 3.0);
+        assert_eq!(eval("2 ^ 3 ^ 2", &[]).unwrap(), 512.0);
+        assert_eq!(eval("-2 ^ 2", &[]).unwrap(), -4.0);
+        assert_eq!(eval("2 ^ -1", &[]).unwrap(), 0.5);
+        assert_eq!(eval("1.5e2", &[]).unwrap(), 150.0);
+    }
+
+    #[test]
+    fn test_stats_and_functions() {
+        let deps = [("DEF", 100.0), ("LEVEL", 16.0), ("AGI", 3.0)];
+        assert_eq!(eval("DEF / (DEF + 100)", &deps).unwrap(), 0.5);
+        assert_eq!(eval("sqrt(LEVEL) * AGI", &deps).unwrap(), 12.0);
+        assert_eq!(eval("min(DEF, LEVEL, AGI)", &deps).unwrap(), 3.0);
+        assert_eq!(eval("max(DEF, LEVEL)", &deps).unwrap(), 100.0);
+        assert_eq!(eval("clamp(DEF, 0, 50)", &deps).unwrap(), 50.0);
+        assert_eq!(eval("abs(AGI - LEVEL)", &deps).unwrap(), 13.0);
+        assert_eq!(
+            eval("floor(LEVEL / 3) + ceil(0.2) + round(2.5)", &deps).unwrap(),
+            9.0
+        );
+    }
+
+    #[test]
+    fn test_dependencies_are_extracted() {
+        let transform = ExpressionTransform::parse("max(STR, DEX) * 2 + STR / LEVEL").unwrap();
+        assert_eq!(
+            transform.depends_on(),
+            vec![
+                StatId::from_str("STR"),
+                StatId::from_str("DEX"),
+                StatId::from_str("LEVEL")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let cases = [
+            ("", 0),
+            ("1 +", 3),
+            ("STR * (2 + DEX", 14),
+            ("STR $ 2", 4),
+            ("foo(1)", 0),
+            ("sqrt(1, 2)", 0),
+            ("1 2", 2),
+            ("min(1,)", 6),
+        ];
+        for (source, expected) in cases {
+            match ExpressionTransform::parse(source) {
+                Err(StatError::ExpressionParse { position, .. }) => {
+                    assert_eq!(position, expected, "wrong position for {:?}", source)
+                }
+                other => panic!("Expected parse error for {:?}, got {:?}", source, other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_nesting_limit() {
+        let nested = |depth: usize| format!("{}1{}", "(".repeat(depth), ")".repeat(depth));
+        assert_eq!(eval(&nested(MAX_EXPRESSION_DEPTH - 1), &[]).unwrap(), 1.0);
+
+        let source = nested(MAX_EXPRESSION_DEPTH);
+        match ExpressionTransform::parse(&source) {
+            Err(StatError::ExpressionParse { position, .. }) => {
+                assert_eq!(position, MAX_EXPRESSION_DEPTH)
+            }
+            other => panic!("Expected parse error, got {:?}", other),
+        }
+
+        // Deep input must not overflow the stack
+        let source = "-".repeat(100_000) + "1";
+        assert!(matches!(
+            ExpressionTransform::parse(&source),
+            Err(StatError::ExpressionParse { .. })
+        ));
+    }
+
+    #[test]
+    fn test_operator_chain_limit() {
+        let chain = |op: &str, terms: usize| format!("1{}", op).repeat(terms - 1) + "1";
+        assert_eq!(eval(&chain("+", 32), &[]).unwrap(), 32.0);
+        assert_eq!(eval(&chain("*", 32), &[]).unwrap(), 1.0);
+
+        // A flat chain builds a tree as deep as it is long
+        for op in ["+", "*", "-", "/"] {
+            let source = chain(op, 1_000_000);
+            assert!(matches!(
+                ExpressionTransform::parse(&source),
+                Err(StatError::ExpressionParse { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_evaluation_errors() {
+        assert!(matches!(
+            eval("1 / (STR - 10)", &[("STR", 10.0)]),
+            Err(StatError::ExpressionEval { .. })
+        ));
+        assert!(matches!(
+            eval("sqrt(-1)", &[]),
+            Err(StatError::ExpressionEval { .. })
+        ));
+        assert!(matches!(
+            eval("clamp(1, 5, 0)", &[]),
+            Err(StatError::ExpressionEval { .. })
+        ));
+        assert!(matches!(
+            eval("LEVEL ^ 0.5", &[("LEVEL", 16.0)]),
+            Err(StatError::ExpressionEval { .. })
+        ));
+        assert!(matches!(
+            eval("2 ^ 65", &[]),
+            Err(StatError::ExpressionEval { .. })
+        ));
+        assert_eq!(
+            eval("STR + 1", &[]),
+            Err(StatError::MissingDependency(StatId::from_str("STR")))
+        );
+    }
+}
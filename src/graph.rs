@@ -7,7 +7,8 @@
 use crate::error::StatError;
 use crate::stat_id::StatId;
 use petgraph::algo::toposort;
-use petgraph::graph::{DiGraph, NodeIndex};
+use petgraph::graph::NodeIndex;
+use petgraph::stable_graph::StableDiGraph;
 use std::collections::HashMap;
 
 /// A directed acyclic graph (DAG) representing stat dependencies.
@@ -16,7 +17,8 @@ use std::collections::HashMap;
 /// on stat B, then B must be resolved before A.
 ///
 /// The graph automatically detects cycles and provides topological sorting
-/// to determine resolution order.
+/// to determine resolution order. Nodes and edges can be removed again, so
+/// the graph can be kept up to date incrementally as transforms come and go.
 ///
 /// # Examples
 ///
@@ -34,8 +36,9 @@ use std::collections::HashMap;
 /// // Get resolution order (STR before ATK)
 /// let order = graph.topological_sort().unwrap();
 /// ```
+#[derive(Clone)]
 pub struct StatGraph {
-    graph: DiGraph<StatId, ()>,
+    graph: StableDiGraph<StatId, ()>,
     node_map: HashMap<StatId, NodeIndex>,
 }
 
@@ -51,7 +54,7 @@ impl StatGraph {
     /// ```
     pub fn new() -> Self {
         Self {
-            graph: DiGraph::new(),
+            graph: StableDiGraph::new(),
             node_map: HashMap::new(),
         }
     }
@@ -107,6 +110,98 @@ impl StatGraph {
         self.graph.add_edge(to_idx, from_idx, ());
     }
 
+    /// Remove one edge representing a dependency.
+    ///
+    /// The graph keeps one edge per registered dependency, so if `from`
+    /// depends on `to` several times only one of those edges is removed.
+    /// Nodes are kept even if they end up without edges.
+    ///
+    /// # Arguments
+    ///
+    /// * `from` - The stat that depends on `to`
+    /// * `to` - The stat that `from` depends on
+    ///
+    /// # Returns
+    ///
+    /// `true` if an edge was removed, `false` if there was none.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use zzstat::graph::StatGraph;
+    /// use zzstat::StatId;
+    ///
+    /// let mut graph = StatGraph::new();
+    /// let atk_id = StatId::from_str("ATK");
+    /// let str_id = StatId::from_str("STR");
+    ///
+    /// graph.add_edge(atk_id.clone(), str_id.clone());
+    /// assert!(graph.remove_edge(&atk_id, &str_id));
+    /// assert!(!graph.has_edges(&atk_id));
+    /// ```
+    pub fn remove_edge(&mut self, from: &StatId, to: &StatId) -> bool {
+        let (Some(&from_idx), Some(&to_idx)) = (self.node_map.get(from), self.node_map.get(to))
+        else {
+            return false;
+        };
+        match self.graph.find_edge(to_idx, from_idx) {
+            Some(edge) => {
+                self.graph.remove_edge(edge);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Remove a node and all of its edges.
+    ///
+    /// # Arguments
+    ///
+    /// * `stat_id` - The stat ID to remove
+    ///
+    /// # Returns
+    ///
+    /// `true` if the node was removed, `false` if it did not exist.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use zzstat::graph::StatGraph;
+    /// use zzstat::StatId;
+    ///
+    /// let mut graph = StatGraph::new();
+    /// let hp_id = StatId::from_str("HP");
+    /// graph.add_node(hp_id.clone());
+    ///
+    /// assert!(graph.remove_node(&hp_id));
+    /// assert!(!graph.contains_node(&hp_id));
+    /// ```
+    pub fn remove_node(&mut self, stat_id: &StatId) -> bool {
+        match self.node_map.remove(stat_id) {
+            Some(idx) => {
+                self.graph.remove_node(idx);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Check if a node has any incoming or outgoing edges.
+    ///
+    /// # Arguments
+    ///
+    /// * `stat_id` - The stat ID to check
+    ///
+    /// # Returns
+    ///
+    /// `true` if the stat depends on another stat or another stat depends
+    /// on it, `false` otherwise (including when the node doesn't exist).
+    pub fn has_edges(&self, stat_id: &StatId) -> bool {
+        self.node_map
+            .get(stat_id)
+            .is_some_and(|&idx| self.graph.neighbors_undirected(idx).next().is_some())
+    }
+
     /// Detect cycles in the graph.
     ///
     /// Uses depth-first search to detect any circular dependencies.
@@ -331,7 +426,7 @@ impl StatGraph {
     /// graph.add_edge(atk_id.clone(), str_id.clone());
     ///
     /// // Extract subgraph for ATK (includes STR as dependency)
-    /// let subgraph = graph.subgraph_for_targets(&[atk_id.clone()]);
+    /// let subgraph = graph.subgraph_for_targets(std::slice::from_ref(&atk_id));
     /// assert!(subgraph.contains_node(&atk_id));
     /// assert!(subgraph.contains_node(&str_id));
     /// assert!(!subgraph.contains_node(&hp_id)); // HP not reachable from ATK
@@ -372,6 +467,271 @@ impl StatGraph {
 
         subgraph
     }
+
+    /// Get a resolution order for the given targets and their dependencies.
+    ///
+    /// Walks the graph backward from the targets (from dependent to
+    /// dependency) and returns only the stats they transitively depend on,
+    /// dependencies first. Unlike `subgraph_for_targets` followed by
+    /// `topological_sort`, nothing outside the dependency closure is
+    /// visited. Targets that are not in the graph are skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `targets` - The target stat IDs
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<StatId>)` - The targets and their dependencies (dependencies first)
+    /// * `Err(StatError::Cycle)` - If a cycle is reachable from the targets
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use zzstat::graph::StatGraph;
+    /// use zzstat::StatId;
+    ///
+    /// let mut graph = StatGraph::new();
+    /// let str_id = StatId::from_str("STR");
+    /// let atk_id = StatId::from_str("ATK");
+    /// let hp_id = StatId::from_str("HP");
+    ///
+    /// graph.add_edge(atk_id.clone(), str_id.clone());
+    /// graph.add_node(hp_id.clone());
+    ///
+    /// let order = graph.dependency_order(std::slice::from_ref(&atk_id)).unwrap();
+    /// assert_eq!(order, vec![str_id, atk_id]); // HP is never visited
+    /// ```
+    pub fn dependency_order(&self, targets: &[StatId]) -> Result<Vec<StatId>, StatError> {
+        let mut order = Vec::new();
+        let mut done = std::collections::HashSet::new();
+        let mut path = Vec::new();
+
+        for target in targets {
+            if let Some(&node_idx) = self.node_map.get(target) {
+                self.dfs_dependency_order(node_idx, &mut done, &mut path, &mut order)?;
+            }
+        }
+
+        Ok(order)
+    }
+
+    fn dfs_dependency_order(
+        &self,
+        node: NodeIndex,
+        done: &mut std::collections::HashSet<NodeIndex>,
+        path: &mut Vec<NodeIndex>,
+        order: &mut Vec<StatId>,
+    ) -> Result<(), StatError> {
+        if done.contains(&node) {
+            return Ok(());
+        }
+        if let Some(pos) = path.iter().position(|&idx| idx == node) {
+            // Each stat on the path depends on the next; close the loop
+            let mut cycle: Vec<StatId> = path[pos..]
+                .iter()
+                .map(|&idx| self.graph[idx].clone())
+                .collect();
+            cycle.push(self.graph[node].clone());
+            return Err(StatError::Cycle { path: cycle });
+        }
+
+        path.push(node);
+        // Edges go from dependency to dependent, so dependencies are incoming
+        for dep_idx in self
+            .graph
+            .neighbors_directed(node, petgraph::Direction::Incoming)
+        {
+            self.dfs_dependency_order(dep_idx, done, path, order)?;
+        }
+        path.pop();
+
+        done.insert(node);
+        order.push(self.graph[node].clone());
+        Ok(())
+    }
+
+    /// Find a chain of dependencies leading from one stat to another.
+    ///
+    /// Follows dependencies breadth-first, so the shortest chain is returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `from` - The stat to start from
+    /// * `to` - The stat to look for among `from`'s transitive dependencies
+    ///
+    /// # Returns
+    ///
+    /// `Some(path)` where `path` starts with `from`, ends with `to`, and each
+    /// stat depends on the next one; `None` if `from` doesn't (transitively)
+    /// depend on `to`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use zzstat::graph::StatGraph;
+    /// use zzstat::StatId;
+    ///
+    /// let mut graph = StatGraph::new();
+    /// let str_id = StatId::from_str("STR");
+    /// let atk_id = StatId::from_str("ATK");
+    /// let dps_id = StatId::from_str("DPS");
+    ///
+    /// graph.add_edge(atk_id.clone(), str_id.clone());
+    /// graph.add_edge(dps_id.clone(), atk_id.clone());
+    ///
+    /// let path = graph.dependency_path(&dps_id, &str_id).unwrap();
+    /// assert_eq!(path, vec![dps_id.clone(), atk_id, str_id.clone()]);
+    /// assert!(graph.dependency_path(&str_id, &dps_id).is_none());
+    /// ```
+    pub fn dependency_path(&self, from: &StatId, to: &StatId) -> Option<Vec<StatId>> {
+        let (&start, &goal) = (self.node_map.get(from)?, self.node_map.get(to)?);
+        if start == goal {
+            return Some(vec![from.clone()]);
+        }
+
+        let mut reached_from = HashMap::new();
+        let mut queue = std::collections::VecDeque::from([start]);
+        while let Some(node_idx) = queue.pop_front() {
+            // Edges go from dependency to dependent, so dependencies are incoming
+            for dep_idx in self
+                .graph
+                .neighbors_directed(node_idx, petgraph::Direction::Incoming)
+            {
+                if dep_idx == start || reached_from.contains_key(&dep_idx) {
+                    continue;
+                }
+                reached_from.insert(dep_idx, node_idx);
+                if dep_idx == goal {
+                    let mut path = vec![self.graph[goal].clone()];
+                    let mut current = goal;
+                    while let Some(&prev) = reached_from.get(&current) {
+                        path.push(self.graph[prev].clone());
+                        current = prev;
+                    }
+                    path.reverse();
+                    return Some(path);
+                }
+                queue.push_back(dep_idx);
+            }
+        }
+
+        None
+    }
+
+    /// Get all stats that transitively depend on the given stat.
+    ///
+    /// Walks the graph forward from `stat_id` (from dependency to dependent)
+    /// and collects every stat it reaches. The stat itself is not included
+    /// unless it is part of a cycle.
+    ///
+    /// # Arguments
+    ///
+    /// * `stat_id` - The stat to find dependents of
+    ///
+    /// # Returns
+    ///
+    /// The set of stats whose value is (directly or indirectly) computed
+    /// from `stat_id`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use zzstat::graph::StatGraph;
+    /// use zzstat::StatId;
+    ///
+    /// let mut graph = StatGraph::new();
+    /// let str_id = StatId::from_str("STR");
+    /// let atk_id = StatId::from_str("ATK");
+    /// let dps_id = StatId::from_str("DPS");
+    /// let hp_id = StatId::from_str("HP");
+    ///
+    /// graph.add_edge(atk_id.clone(), str_id.clone());
+    /// graph.add_edge(dps_id.clone(), atk_id.clone());
+    /// graph.add_node(hp_id.clone());
+    ///
+    /// let dependents = graph.dependents_of(&str_id);
+    /// assert!(dependents.contains(&atk_id));
+    /// assert!(dependents.contains(&dps_id));
+    /// assert!(!dependents.contains(&hp_id));
+    /// ```
+    pub fn dependents_of(&self, stat_id: &StatId) -> std::collections::HashSet<StatId> {
+        let mut dependents = std::collections::HashSet::new();
+        let Some(&start) = self.node_map.get(stat_id) else {
+            return dependents;
+        };
+
+        let mut stack = vec![start];
+        while let Some(node_idx) = stack.pop() {
+            // Edges go from dependency to dependent
+            for neighbor_idx in self
+                .graph
+                .neighbors_directed(node_idx, petgraph::Direction::Outgoing)
+            {
+                if dependents.insert(self.graph[neighbor_idx].clone()) {
+                    stack.push(neighbor_idx);
+                }
+            }
+        }
+
+        dependents
+    }
+
+    /// Group a resolution order into dependency levels.
+    ///
+    /// Level 0 holds stats that depend on nothing in `order`; every other
+    /// stat sits one level above its deepest dependency. Stats in the same
+    /// level don't depend on each other, so they can be resolved
+    /// concurrently. Within a level, stats keep their order from `order`.
+    ///
+    /// # Arguments
+    ///
+    /// * `order` - A resolution order (dependencies first), such as one
+    ///   returned by `topological_sort` or `dependency_order`
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use zzstat::graph::StatGraph;
+    /// use zzstat::StatId;
+    ///
+    /// let mut graph = StatGraph::new();
+    /// let str_id = StatId::from_str("STR");
+    /// let dex_id = StatId::from_str("DEX");
+    /// let atk_id = StatId::from_str("ATK");
+    ///
+    /// graph.add_edge(atk_id.clone(), str_id.clone());
+    /// graph.add_node(dex_id.clone());
+    ///
+    /// let order = vec![str_id.clone(), dex_id.clone(), atk_id.clone()];
+    /// assert_eq!(graph.levels(&order), vec![vec![str_id, dex_id], vec![atk_id]]);
+    /// ```
+    pub fn levels(&self, order: &[StatId]) -> Vec<Vec<StatId>> {
+        let mut depths: HashMap<NodeIndex, usize> = HashMap::new();
+        let mut levels: Vec<Vec<StatId>> = Vec::new();
+        for stat_id in order {
+            let depth = match self.node_map.get(stat_id) {
+                Some(&node_idx) => {
+                    // Edges go from dependency to dependent
+                    let depth = self
+                        .graph
+                        .neighbors_directed(node_idx, petgraph::Direction::Incoming)
+                        .filter_map(|dep_idx| depths.get(&dep_idx))
+                        .map(|depth| depth + 1)
+                        .max()
+                        .unwrap_or(0);
+                    depths.insert(node_idx, depth);
+                    depth
+                }
+                None => 0,
+            };
+            if levels.len() <= depth {
+                levels.resize_with(depth + 1, Vec::new);
+            }
+            levels[depth].push(stat_id.clone());
+        }
+        levels
+    }
 }
 
 impl Default for StatGraph {
@@ -424,6 +784,32 @@ mod tests {
         assert!(graph.detect_cycles().is_ok());
     }
 
+    #[test]
+    fn test_graph_remove_edge_and_node() {
+        let mut graph = StatGraph::new();
+        let a = StatId::from_str("A");
+        let b = StatId::from_str("B");
+
+        // A depends on B twice (two transforms)
+        graph.add_edge(a.clone(), b.clone());
+        graph.add_edge(a.clone(), b.clone());
+        graph.add_edge(b.clone(), a.clone());
+        assert!(graph.detect_cycles().is_err());
+
+        assert!(graph.remove_edge(&b, &a));
+        assert!(!graph.remove_edge(&b, &a));
+        assert!(graph.detect_cycles().is_ok());
+
+        assert!(graph.remove_edge(&a, &b));
+        assert!(graph.has_edges(&a));
+        assert!(graph.remove_edge(&a, &b));
+        assert!(!graph.has_edges(&a));
+
+        assert!(graph.remove_node(&b));
+        assert!(!graph.contains_node(&b));
+        assert_eq!(graph.topological_sort().unwrap(), vec![a]);
+    }
+
     #[test]
     fn test_graph_detect_cycle() {
         let mut graph = StatGraph::new();
@@ -463,6 +849,58 @@ mod tests {
         assert!(dex_pos < crit_pos);
     }
 
+    #[test]
+    fn test_dependency_order() {
+        let mut graph = StatGraph::new();
+        let str_id = StatId::from_str("STR");
+        let atk_id = StatId::from_str("ATK");
+        let dps_id = StatId::from_str("DPS");
+        let dex_id = StatId::from_str("DEX");
+        let crit_id = StatId::from_str("CRIT");
+
+        graph.add_edge(atk_id.clone(), str_id.clone());
+        graph.add_edge(dps_id.clone(), atk_id.clone());
+        graph.add_edge(dps_id.clone(), str_id.clone());
+        graph.add_edge(crit_id.clone(), dex_id.clone());
+
+        let order = graph
+            .dependency_order(std::slice::from_ref(&dps_id))
+            .unwrap();
+        assert_eq!(order, vec![str_id.clone(), atk_id.clone(), dps_id.clone()]);
+
+        // Shared dependencies are listed once, unknown targets are skipped
+        let order = graph
+            .dependency_order(&[atk_id.clone(), crit_id.clone(), StatId::from_str("MP")])
+            .unwrap();
+        assert_eq!(order, vec![str_id, atk_id, dex_id, crit_id]);
+    }
+
+    #[test]
+    fn test_dependency_order_detects_reachable_cycle() {
+        let mut graph = StatGraph::new();
+        let a = StatId::from_str("A");
+        let b = StatId::from_str("B");
+        let c = StatId::from_str("C");
+        let hp = StatId::from_str("HP");
+
+        // A depends on B, B depends on C, C depends on A
+        graph.add_edge(a.clone(), b.clone());
+        graph.add_edge(b.clone(), c.clone());
+        graph.add_edge(c.clone(), a.clone());
+        graph.add_node(hp.clone());
+
+        match graph.dependency_order(std::slice::from_ref(&a)) {
+            Err(StatError::Cycle { path }) => {
+                assert_eq!(path, vec![a.clone(), b, c, a]);
+            }
+            other => panic!("Expected Cycle error, got {:?}", other),
+        }
+
+        // The cycle is not reachable from HP
+        let order = graph.dependency_order(std::slice::from_ref(&hp)).unwrap();
+        assert_eq!(order, vec![hp]);
+    }
+
     #[test]
     fn test_subgraph_for_targets() {
         let mut graph = StatGraph::new();
@@ -479,7 +917,7 @@ mod tests {
         // HP has no dependencies
 
         // Extract subgraph for ATK
-        let subgraph = graph.subgraph_for_targets(&[atk_id.clone()]);
+        let subgraph = graph.subgraph_for_targets(std::slice::from_ref(&atk_id));
 
         // Should contain ATK and STR
         assert!(subgraph.contains_node(&atk_id));
@@ -534,7 +972,7 @@ mod tests {
         graph.add_edge(top2_id.clone(), mid2_id.clone());
 
         // Extract subgraph for TOP1 only
-        let subgraph = graph.subgraph_for_targets(&[top1_id.clone()]);
+        let subgraph = graph.subgraph_for_targets(std::slice::from_ref(&top1_id));
 
         // Should contain TOP1, MID1, and BASE
         assert!(subgraph.contains_node(&top1_id));
@@ -562,7 +1000,7 @@ mod tests {
         graph.add_node(existing_id.clone());
 
         // Extract subgraph for non-existent node
-        let subgraph = graph.subgraph_for_targets(&[nonexistent_id.clone()]);
+        let subgraph = graph.subgraph_for_targets(std::slice::from_ref(&nonexistent_id));
 
         // Should not contain the non-existent node
         assert!(!subgraph.contains_node(&nonexistent_id));
@@ -801,4 +1239,57 @@ mod tests {
             panic!("Expected Cycle errors");
         }
     }
+
+    #[test]
+    fn test_dependents_of() {
+        let mut graph = StatGraph::new();
+        let str_id = StatId::from_str("STR");
+        let dex_id = StatId::from_str("DEX");
+        let atk_id = StatId::from_str("ATK");
+        let crit_id = StatId::from_str("CRIT");
+        let dps_id = StatId::from_str("DPS");
+
+        // STR -> ATK -> DPS, DEX -> CRIT -> DPS
+        graph.add_edge(atk_id.clone(), str_id.clone());
+        graph.add_edge(crit_id.clone(), dex_id.clone());
+        graph.add_edge(dps_id.clone(), atk_id.clone());
+        graph.add_edge(dps_id.clone(), crit_id.clone());
+
+        let dependents = graph.dependents_of(&str_id);
+        assert_eq!(dependents.len(), 2);
+        assert!(dependents.contains(&atk_id));
+        assert!(dependents.contains(&dps_id));
+
+        assert!(graph.dependents_of(&dps_id).is_empty());
+        assert!(graph.dependents_of(&StatId::from_str("MISSING")).is_empty());
+    }
+
+    #[test]
+    fn test_levels() {
+        let mut graph = StatGraph::new();
+        let str_id = StatId::from_str("STR");
+        let dex_id = StatId::from_str("DEX");
+        let atk_id = StatId::from_str("ATK");
+        let crit_id = StatId::from_str("CRIT");
+        let dps_id = StatId::from_str("DPS");
+
+        // STR -> ATK -> DPS, DEX -> CRIT -> DPS, and DPS reads STR directly
+        graph.add_edge(atk_id.clone(), str_id.clone());
+        graph.add_edge(crit_id.clone(), dex_id.clone());
+        graph.add_edge(dps_id.clone(), atk_id.clone());
+        graph.add_edge(dps_id.clone(), crit_id.clone());
+        graph.add_edge(dps_id.clone(), str_id.clone());
+
+        let order = graph.topological_sort().unwrap();
+        let levels = graph.levels(&order);
+        assert_eq!(levels.len(), 3);
+        let mut first = levels[0].clone();
+        first.sort();
+        assert_eq!(first, vec![dex_id.clone(), str_id.clone()]);
+        assert_eq!(levels[2], vec![dps_id.clone()]);
+
+        // Dependencies outside the order don't count
+        let partial = graph.levels(&[atk_id.clone(), dps_id.clone()]);
+        assert_eq!(partial, vec![vec![atk_id], vec![dps_id]]);
+    }
 }
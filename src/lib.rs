@@ -39,8 +39,8 @@
 //! let hp_id = StatId::from_str("HP");
 //!
 //! // Register sources (additive)
-//! resolver.register_source(hp_id.clone(), Box::new(ConstantSource(100.0)));
-//! resolver.register_source(hp_id.clone(), Box::new(ConstantSource(50.0)));
+//! resolver.register_source(hp_id.clone(), Box::new(ConstantSource::new(100.0)));
+//! resolver.register_source(hp_id.clone(), Box::new(ConstantSource::new(50.0)));
 //!
 //! // Register transform
 //! resolver.register_transform(hp_id.clone(), Box::new(MultiplicativeTransform::new(1.5)));
@@ -56,19 +56,29 @@
 //! - [`stat_id`] - Stat identifier type
 //! - [`source`] - Stat sources (produce base values)
 //! - [`transform`] - Stat transforms (modify values)
+//! - [`phase`] - Named phases and their application order
 //! - [`resolver`] - Main stat resolver
 //! - [`resolved`] - Resolved stat results
+//! - [`resource`] - Resource pools (current HP/MP) bound to a max stat
 //! - [`context`] - Context for conditional calculations
+//! - [`buff`] - Timed buffs driven by a caller-supplied clock
+//! - [`definition`] - Data-driven stat definitions
 //! - [`graph`] - Dependency graph management
+//! - [`expression`] - Formula transforms for derived stats
 //! - [`error`] - Error types
 
 pub mod bonus;
+pub mod buff;
 pub mod context;
+pub mod definition;
 pub mod error;
+pub mod expression;
 pub mod graph;
 pub mod numeric;
+pub mod phase;
 pub mod resolved;
 pub mod resolver;
+pub mod resource;
 pub mod source;
 pub mod stat_id;
 pub mod transform;
@@ -76,24 +86,33 @@ pub mod transform;
 // Re-export main types for convenience
 pub use context::StatContext;
 pub use error::StatError;
-pub use resolved::ResolvedStat;
-pub use resolver::StatResolver;
+pub use resolved::{
+    DiminishingBreakdown, PhaseBreakdown, ResolvedStat, StackBreakdown, TransformBreakdown,
+};
+pub use resolver::{SourceHandle, StatResolver, TransformHandle};
+pub use resource::{MaxChangePolicy, ResourcePool};
 pub use stat_id::StatId;
 
 // Re-export common sources and transforms
-pub use source::{ConstantSource, MapSource, StatSource};
+pub use source::{ConstantSource, LabeledSource, MapSource, StatSource};
 pub use transform::{
-    AdditiveTransform, ClampTransform, ConditionalTransform, MultiplicativeTransform,
-    ScalingTransform, StackRule, StatTransform, TransformEntry, TransformPhase,
+    AdditiveTransform, ClampTransform, ConditionalTransform, Contribution, DiminishingMode,
+    MultiplicativeTransform, OriginTransform, PiecewiseCurve, ScalingTransform, StackRule,
+    StatTransform, TransformEntry, TransformPhase,
 };
 
 // Re-export numeric types
-#[cfg(feature = "fixed-point")]
-pub use numeric::FixedPoint;
+pub use numeric::{Decimal, FixedPoint, RoundingMode};
 pub use numeric::{StatNumeric, StatValue};
 
+// Re-export phase types
+pub use phase::PhaseRegistry;
+
 // Re-export bonus types
 pub use bonus::{
-    apply_compiled_bonus, apply_compiled_bonuses, compile_bonus, Bonus, BonusOp, BonusValue,
-    CompiledBonus,
+    apply_compiled_bonus, apply_compiled_bonuses, compile_bonus, Bonus, BonusOp, BonusTemplate,
+    BonusValue, CompiledBonus,
 };
+
+// Re-export buff types
+pub use buff::{BuffDefinition, BuffTracker, CasterId, StackPolicy, Tick};
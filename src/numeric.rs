@@ -1,20 +1,32 @@
 //! Numeric types for stat values.
 //!
-//! Provides a fixed-point numeric type for deterministic calculations
-//! when the `fixed-point` feature is enabled, or uses `f64` by default.
+//! Stat types are generic over a `StatNumeric` backend: `f64`, or the
+//! `FixedPoint` type for deterministic calculations. `StatValue` is the
+//! default backend, `FixedPoint` when the `fixed-point` feature is enabled
+//! and `f64` otherwise; both backends are always available.
 
+use crate::error::StatError;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize, Serializer};
+use std::cmp::Ordering;
 use std::fmt;
-use std::ops::{Add, Div, Mul, Sub};
-
-#[cfg(feature = "fixed-point")]
-use serde::{Deserialize, Serialize};
+use std::hash::{Hash, Hasher};
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use std::str::FromStr;
 
 /// Trait for numeric operations required by stat calculations.
 ///
 /// This trait abstracts over `f64` and `FixedPoint` to allow
-/// the stat system to work with either numeric backend.
+/// the stat system to work with either numeric backend. Resolvers for
+/// different backends can be used side by side, for example `f64` on a
+/// client for previews and `FixedPoint` on the server for authority.
 pub trait StatNumeric:
-    Clone
+    Send
+    + Sync
+    + 'static
+    + Serialize
+    + DeserializeOwned
+    + Clone
     + Copy
     + PartialEq
     + PartialOrd
@@ -24,6 +36,10 @@ pub trait StatNumeric:
     + Sub<Output = Self>
     + Mul<Output = Self>
     + Div<Output = Self>
+    + AddAssign
+    + SubAssign
+    + MulAssign
+    + DivAssign
     + Default
 {
     /// Create a zero value.
@@ -40,9 +56,42 @@ pub trait StatNumeric:
 
     /// Clamp the value between min and max (inclusive).
     fn clamp(self, min: Self, max: Self) -> Self;
+
+    /// Round the value to `scale` decimal places.
+    ///
+    /// Backends without a fixed scale, such as `f64`, return the value
+    /// unchanged.
+    fn with_scale(self, _scale: u8) -> Self {
+        self
+    }
+
+    /// Convert an exact decimal from content, such as a bonus amount or a
+    /// formula literal.
+    ///
+    /// `FixedPoint` converts exactly; the default, used by `f64`, is the
+    /// nearest value.
+    fn from_decimal(value: Decimal) -> Self {
+        Self::from_f64(value.to_f64())
+    }
+
+    /// Compute `self × mul / div` as one operation.
+    ///
+    /// Fixed-point backends keep the intermediate product at full width,
+    /// so the result only saturates when the final value is out of range.
+    fn mul_div(self, mul: Self, div: Self) -> Self {
+        self * mul / div
+    }
+
+    /// Compute `e^-self`.
+    ///
+    /// `FixedPoint` computes it with integer arithmetic, so the result is
+    /// the same on every platform. The default, used by `f64`, calls
+    /// `f64::exp`, whose last bits may differ between platforms.
+    fn exp_neg(self) -> Self {
+        Self::from_f64((-self.to_f64()).exp())
+    }
 }
 
-#[cfg(not(feature = "fixed-point"))]
 impl StatNumeric for f64 {
     fn zero() -> Self {
         0.0
@@ -65,11 +114,74 @@ impl StatNumeric for f64 {
     }
 }
 
+/// How a fixed-point result is rounded to its scale.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum RoundingMode {
+    /// Round to nearest, ties away from zero (2.5 → 3, -2.5 → -3).
+    HalfAwayFromZero,
+    /// Round to nearest, ties to even (2.5 → 2, 3.5 → 4).
+    HalfEven,
+    /// Round toward negative infinity (2.7 → 2, -2.3 → -3).
+    Floor,
+    /// Round toward zero (2.7 → 2, -2.7 → -2).
+    Truncate,
+}
+
+impl RoundingMode {
+    /// Divide `n` by `d` (non-zero), rounding the quotient.
+    fn div(self, n: i128, d: i128) -> i128 {
+        let (q, r) = (n / d, n % d);
+        if r == 0 {
+            return q;
+        }
+        // Step away from zero: the sign of the exact quotient
+        let away = if (n < 0) != (d < 0) { -1 } else { 1 };
+        let twice = 2 * r.unsigned_abs();
+        let half_away = match twice.cmp(&d.unsigned_abs()) {
+            std::cmp::Ordering::Greater => true,
+            std::cmp::Ordering::Equal => self == RoundingMode::HalfAwayFromZero || q % 2 != 0,
+            std::cmp::Ordering::Less => false,
+        };
+        match self {
+            RoundingMode::Truncate => q,
+            RoundingMode::Floor if away < 0 => q - 1,
+            RoundingMode::Floor => q,
+            RoundingMode::HalfAwayFromZero | RoundingMode::HalfEven if half_away => q + away,
+            RoundingMode::HalfAwayFromZero | RoundingMode::HalfEven => q,
+        }
+    }
+
+    /// Round an f64 to an integral value.
+    fn round_f64(self, f: f64) -> f64 {
+        match self {
+            RoundingMode::HalfAwayFromZero => f.round(),
+            RoundingMode::HalfEven => f.round_ties_even(),
+            RoundingMode::Floor => f.floor(),
+            RoundingMode::Truncate => f.trunc(),
+        }
+    }
+}
+
 /// Fixed-point number for deterministic stat calculations.
 ///
 /// Uses `i64` for the value and `u8` for the scale (number of decimal places).
 /// For example, with scale 4, the value 12345 represents 1.2345.
 ///
+/// Arithmetic is computed with `i128` intermediates, so products of
+/// large values don't overflow before they are scaled back down. The
+/// operators (`+`, `-`, `*`, `/`) saturate at the `i64` range, truncate
+/// products and quotients toward zero, and saturate on division by zero.
+/// Use the `checked_*` methods to get a `StatError::Overflow` instead,
+/// and `mul_rounded` / `div_rounded` to pick a `RoundingMode`.
+///
+/// Equality, ordering, hashing and `Display` go by the represented value,
+/// so the same number at different scales compares equal.
+///
+/// Parsing a decimal string (`"1.25".parse()`) is exact. Besides the
+/// `{ "value", "scale" }` form it serializes to, a `FixedPoint` also
+/// deserializes from a decimal string or a plain number, so content can be
+/// authored as `"0.1"` without going through `f64`.
+///
 /// # Examples
 ///
 /// ```rust
@@ -77,9 +189,10 @@ impl StatNumeric for f64 {
 ///
 /// let fp = FixedPoint::new(12345, 4);
 /// assert_eq!(fp.to_f64(), 1.2345);
+/// assert_eq!(FixedPoint::new(10000, 4), FixedPoint::new(100, 2));
 /// ```
-#[cfg(feature = "fixed-point")]
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(try_from = "FixedPointRepr")]
 pub struct FixedPoint {
     /// The integer value (scaled by 10^scale).
     value: i64,
@@ -87,11 +200,13 @@ pub struct FixedPoint {
     scale: u8,
 }
 
-#[cfg(feature = "fixed-point")]
 impl FixedPoint {
     /// Default scale for fixed-point numbers (4 decimal places).
     pub const DEFAULT_SCALE: u8 = 4;
 
+    /// Largest supported scale (18 decimal places).
+    pub const MAX_SCALE: u8 = 18;
+
     /// Create a new fixed-point number.
     ///
     /// # Arguments
@@ -99,6 +214,10 @@ impl FixedPoint {
     /// * `value` - The integer value (scaled by 10^scale)
     /// * `scale` - The number of decimal places (0-18)
     ///
+    /// A `scale` above `MAX_SCALE` is brought down to `MAX_SCALE`,
+    /// rounding the dropped digits half away from zero; use `try_new` to
+    /// reject it instead.
+    ///
     /// # Examples
     ///
     /// ```rust
@@ -106,9 +225,51 @@ impl FixedPoint {
     ///
     /// // 1.2345 with 4 decimal places
     /// let fp = FixedPoint::new(12345, 4);
+    ///
+    /// assert_eq!(FixedPoint::new(25, 19), FixedPoint::new(3, 18));
     /// ```
     pub fn new(value: i64, scale: u8) -> Self {
-        Self { value, scale }
+        if scale <= Self::MAX_SCALE {
+            return Self { value, scale };
+        }
+        let dropped = scale - Self::MAX_SCALE;
+        // |value| < 10^19, so dropping 20 or more digits always leaves zero
+        let value = if dropped >= 20 {
+            0
+        } else {
+            RoundingMode::HalfAwayFromZero.div(value as i128, pow10(dropped)) as i64
+        };
+        Self {
+            value,
+            scale: Self::MAX_SCALE,
+        }
+    }
+
+    /// Create a new fixed-point number, rejecting unsupported scales.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(FixedPoint)` - The number
+    /// * `Err(StatError::InvalidNumber)` - If `scale` is greater than
+    ///   `MAX_SCALE`
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use zzstat::numeric::FixedPoint;
+    ///
+    /// assert_eq!(FixedPoint::try_new(15, 1).unwrap(), FixedPoint::new(150, 2));
+    /// assert!(FixedPoint::try_new(1, 60).is_err());
+    /// ```
+    pub fn try_new(value: i64, scale: u8) -> Result<Self, StatError> {
+        if scale > Self::MAX_SCALE {
+            return Err(StatError::InvalidNumber(format!(
+                "scale {} exceeds the maximum of {}",
+                scale,
+                Self::MAX_SCALE
+            )));
+        }
+        Ok(Self { value, scale })
     }
 
     /// Create a fixed-point number from an f64.
@@ -128,6 +289,10 @@ impl FixedPoint {
 
     /// Create a fixed-point number from an f64 with a specific scale.
     ///
+    /// Rounds half away from zero; use `from_f64_rounded` to choose
+    /// another mode. Values outside the `i64` range saturate and NaN
+    /// becomes zero.
+    ///
     /// # Examples
     ///
     /// ```rust
@@ -136,8 +301,32 @@ impl FixedPoint {
     /// let fp = FixedPoint::from_f64_with_scale(1.234567, 6);
     /// ```
     pub fn from_f64_with_scale(f: f64, scale: u8) -> Self {
-        let multiplier = 10_i64.pow(scale as u32);
-        let value = (f * multiplier as f64).round() as i64;
+        Self::from_f64_rounded(f, scale, RoundingMode::HalfAwayFromZero)
+    }
+
+    /// Create a fixed-point number from an f64 with a specific scale and
+    /// rounding mode.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - The value to convert
+    /// * `scale` - The number of decimal places (0-18)
+    /// * `mode` - How to round digits beyond `scale`
+    ///
+    /// A `scale` above `MAX_SCALE` is treated as `MAX_SCALE`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use zzstat::numeric::{FixedPoint, RoundingMode};
+    ///
+    /// assert_eq!(FixedPoint::from_f64_rounded(2.5, 0, RoundingMode::HalfEven).value(), 2);
+    /// assert_eq!(FixedPoint::from_f64_rounded(-2.5, 0, RoundingMode::Floor).value(), -3);
+    /// assert_eq!(FixedPoint::from_f64_rounded(-2.5, 0, RoundingMode::Truncate).value(), -2);
+    /// ```
+    pub fn from_f64_rounded(f: f64, scale: u8, mode: RoundingMode) -> Self {
+        let scale = scale.min(Self::MAX_SCALE);
+        let value = mode.round_f64(f * 10_f64.powi(scale as i32)) as i64;
         Self { value, scale }
     }
 
@@ -166,32 +355,398 @@ impl FixedPoint {
         self.scale
     }
 
-    /// Normalize to a common scale for arithmetic operations.
+    /// Add, reporting overflow as an error.
     ///
-    /// Returns (value1, value2, common_scale) where both values
-    /// are scaled to the same factor.
-    fn normalize(self, other: Self) -> (i64, i64, u8) {
-        let common_scale = self.scale.max(other.scale);
-        let scale_diff1 = common_scale as i32 - self.scale as i32;
-        let scale_diff2 = common_scale as i32 - other.scale as i32;
+    /// # Returns
+    ///
+    /// * `Ok(FixedPoint)` - The sum, at the larger of the two scales
+    /// * `Err(StatError::Overflow)` - If the sum doesn't fit in an `i64`
+    pub fn checked_add(self, other: Self) -> Result<Self, StatError> {
+        let (v1, v2, scale) = self.normalize(other);
+        self.checked(v1 + v2, scale, "+", other)
+    }
+
+    /// Subtract, reporting overflow as an error.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(FixedPoint)` - The difference, at the larger of the two scales
+    /// * `Err(StatError::Overflow)` - If the difference doesn't fit in an `i64`
+    pub fn checked_sub(self, other: Self) -> Result<Self, StatError> {
+        let (v1, v2, scale) = self.normalize(other);
+        self.checked(v1 - v2, scale, "-", other)
+    }
+
+    /// Multiply (truncating), reporting overflow as an error.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(FixedPoint)` - The product, at the larger of the two scales
+    /// * `Err(StatError::Overflow)` - If the product doesn't fit in an `i64`
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use zzstat::numeric::FixedPoint;
+    ///
+    /// let big = FixedPoint::from_f64(1e12);
+    /// assert_eq!(big.checked_mul(FixedPoint::from_f64(2.0)).unwrap().to_f64(), 2e12);
+    /// assert!(big.checked_mul(big).is_err());
+    /// ```
+    pub fn checked_mul(self, other: Self) -> Result<Self, StatError> {
+        let (value, scale) = self.mul_raw(other, RoundingMode::Truncate);
+        self.checked(value, scale, "*", other)
+    }
+
+    /// Divide (truncating), reporting overflow and division by zero as an
+    /// error.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(FixedPoint)` - The quotient, at the larger of the two scales
+    /// * `Err(StatError::Overflow)` - If `other` is zero or the quotient
+    ///   doesn't fit in an `i64`
+    pub fn checked_div(self, other: Self) -> Result<Self, StatError> {
+        let (value, scale) = self
+            .div_raw(other, RoundingMode::Truncate)
+            .ok_or_else(|| StatError::Overflow(format!("{} / {} divides by zero", self, other)))?;
+        self.checked(value, scale, "/", other)
+    }
 
-        let value1 = if scale_diff1 > 0 {
-            self.value * 10_i64.pow(scale_diff1 as u32)
+    /// Multiply, rounding the product with `mode` and saturating at the
+    /// `i64` range.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use zzstat::numeric::{FixedPoint, RoundingMode};
+    ///
+    /// // 0.05 × 0.5 = 0.025, at scale 2
+    /// let a = FixedPoint::new(5, 2);
+    /// let b = FixedPoint::new(50, 2);
+    /// assert_eq!(a.mul_rounded(b, RoundingMode::HalfEven).value(), 2);
+    /// assert_eq!(a.mul_rounded(b, RoundingMode::HalfAwayFromZero).value(), 3);
+    /// ```
+    pub fn mul_rounded(self, other: Self, mode: RoundingMode) -> Self {
+        let (value, scale) = self.mul_raw(other, mode);
+        Self::saturating(value, scale)
+    }
+
+    /// Divide, rounding the quotient with `mode` and saturating at the
+    /// `i64` range.
+    ///
+    /// Dividing by zero saturates toward the sign of `self` (zero stays
+    /// zero).
+    pub fn div_rounded(self, other: Self, mode: RoundingMode) -> Self {
+        match self.div_raw(other, mode) {
+            Some((value, scale)) => Self::saturating(value, scale),
+            None => {
+                let scale = self.scale.max(other.scale);
+                Self::saturating(self.value.signum() as i128 * i128::MAX, scale)
+            }
+        }
+    }
+
+    /// Compute `self × mul / div` at the largest of the three scales,
+    /// rounding once with `mode` and saturating at the `i64` range.
+    ///
+    /// The product is kept in an `i128`, so `self × mul` may be far outside
+    /// the `i64` range as long as the quotient isn't. Dividing by zero
+    /// saturates toward the sign of the product.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use zzstat::numeric::{FixedPoint, RoundingMode};
+    ///
+    /// let running: FixedPoint = "500000000".parse().unwrap();
+    /// let after: FixedPoint = "750000000".parse().unwrap();
+    /// let before: FixedPoint = "500000000".parse().unwrap();
+    ///
+    /// // running × after alone saturates at scale 4
+    /// assert_ne!(running * after / before, after);
+    /// assert_eq!(
+    ///     running.mul_div_rounded(after, before, RoundingMode::HalfAwayFromZero),
+    ///     after
+    /// );
+    /// ```
+    pub fn mul_div_rounded(self, mul: Self, div: Self, mode: RoundingMode) -> Self {
+        let scale = self.scale.max(mul.scale).max(div.scale);
+        let product = self.value as i128 * mul.value as i128;
+        if div.value == 0 {
+            return Self::saturating(product.signum() * i128::MAX, scale);
+        }
+        // result = product × 10^(scale + div.scale - self.scale - mul.scale) / div.value
+        let shift = scale as i32 + div.scale as i32 - self.scale as i32 - mul.scale as i32;
+        let value = if shift >= 0 {
+            match product.checked_mul(pow10(shift as u8)) {
+                Some(numerator) => mode.div(numerator, div.value as i128),
+                // |numerator| > i128::MAX and |div.value| < 2^63, so the
+                // quotient is outside the i64 range
+                None => (product.signum() * (div.value as i128).signum()) * i128::MAX,
+            }
         } else {
-            self.value
+            match (div.value as i128).checked_mul(pow10((-shift) as u8)) {
+                Some(denominator) => mode.div(product, denominator),
+                // |denominator| > i128::MAX > 2 × |product|: rounds to zero,
+                // or to one step away from zero under Floor
+                None => mode.div(product.signum(), i128::MAX),
+            }
         };
+        Self::saturating(value, scale)
+    }
+
+    /// Compute `e^-self` with integer arithmetic only, so every platform
+    /// gets the same result.
+    ///
+    /// The result has the scale of `self`, at least `DEFAULT_SCALE`, and is
+    /// rounded half away from zero. Large negative inputs saturate at the
+    /// `i64` range.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use zzstat::numeric::FixedPoint;
+    ///
+    /// let one: FixedPoint = "1".parse().unwrap();
+    /// assert_eq!(one.exp_neg(), "0.3679".parse::<FixedPoint>().unwrap());
+    /// assert_eq!((-one).exp_neg(), "2.7183".parse::<FixedPoint>().unwrap());
+    /// ```
+    pub fn exp_neg(self) -> Self {
+        // ln 2 at scale 18
+        const LN_2: i128 = 693_147_180_559_945_309;
+        let scale = self.scale.max(Self::DEFAULT_SCALE);
+        let growing = self.value < 0;
+        let one = pow10(Self::MAX_SCALE);
+        let x = (self.value as i128).abs() * pow10(Self::MAX_SCALE - self.scale);
+        // e^-44 rounds to zero and e^44 saturates at every scale
+        if x > 44 * one {
+            return Self::saturating(if growing { i128::MAX } else { 0 }, scale);
+        }
+        // e^∓x = 2^∓n × e^∓r with 0 <= r < ln 2, where the series
+        // converges fast
+        let n = x / LN_2;
+        let r = x - n * LN_2;
+        let r = if growing { r } else { -r };
+        let (mut term, mut sum, mut k) = (one, one, 1);
+        while term != 0 {
+            term = RoundingMode::HalfAwayFromZero.div(term * r, k * one);
+            sum += term;
+            k += 1;
+        }
+        let unscale = pow10(Self::MAX_SCALE - scale);
+        let value = if growing {
+            RoundingMode::HalfAwayFromZero.div(sum << n, unscale)
+        } else {
+            RoundingMode::HalfAwayFromZero.div(sum, unscale << n)
+        };
+        Self::saturating(value, scale)
+    }
+
+    /// Add, saturating at the `i64` range.
+    pub fn saturating_add(self, other: Self) -> Self {
+        let (v1, v2, scale) = self.normalize(other);
+        Self::saturating(v1 + v2, scale)
+    }
+
+    /// Subtract, saturating at the `i64` range.
+    pub fn saturating_sub(self, other: Self) -> Self {
+        let (v1, v2, scale) = self.normalize(other);
+        Self::saturating(v1 - v2, scale)
+    }
+
+    /// Multiply (truncating), saturating at the `i64` range.
+    pub fn saturating_mul(self, other: Self) -> Self {
+        self.mul_rounded(other, RoundingMode::Truncate)
+    }
+
+    /// Divide (truncating), saturating at the `i64` range.
+    ///
+    /// Dividing by zero saturates toward the sign of `self`.
+    pub fn saturating_div(self, other: Self) -> Self {
+        self.div_rounded(other, RoundingMode::Truncate)
+    }
 
-        let value2 = if scale_diff2 > 0 {
-            other.value * 10_i64.pow(scale_diff2 as u32)
+    /// Convert to another scale, rounding with `mode` when digits are
+    /// dropped and saturating at the `i64` range.
+    ///
+    /// A `scale` above `MAX_SCALE` is treated as `MAX_SCALE`.
+    pub fn rescale(self, scale: u8, mode: RoundingMode) -> Self {
+        let scale = scale.min(Self::MAX_SCALE);
+        let value = if scale >= self.scale {
+            self.value as i128 * pow10(scale - self.scale)
         } else {
-            other.value
+            mode.div(self.value as i128, pow10(self.scale - scale))
+        };
+        Self::saturating(value, scale)
+    }
+
+    /// Scaled product of two values, at the larger scale.
+    fn mul_raw(self, other: Self, mode: RoundingMode) -> (i128, u8) {
+        let scale = self.scale.max(other.scale);
+        // Multiply at the operands' own scales: two i64 factors always fit
+        // in an i128. The product has scale `self.scale + other.scale`, so
+        // dropping the smaller of the two brings it to `scale`.
+        let product = self.value as i128 * other.value as i128;
+        let dropped = self.scale.min(other.scale);
+        (mode.div(product, pow10(dropped)), scale)
+    }
+
+    /// Scaled quotient of two values, at the larger scale (`None` when
+    /// dividing by zero).
+    ///
+    /// Quotients too large for an `i128` intermediate are returned as
+    /// `i128::MAX` with the quotient's sign; they are far outside the
+    /// `i64` range, so they saturate or fail the overflow check.
+    fn div_raw(self, other: Self, mode: RoundingMode) -> Option<(i128, u8)> {
+        if other.value == 0 {
+            return None;
+        }
+        let scale = self.scale.max(other.scale);
+        // self / other at `scale` is self.value × 10^(scale - self.scale +
+        // other.scale) / other.value
+        let shift = pow10(scale - self.scale + other.scale);
+        let quotient = match (self.value as i128).checked_mul(shift) {
+            Some(numerator) => mode.div(numerator, other.value as i128),
+            None if (self.value < 0) != (other.value < 0) => -i128::MAX,
+            None => i128::MAX,
         };
+        Some((quotient, scale))
+    }
+
+    fn saturating(value: i128, scale: u8) -> Self {
+        Self {
+            value: value.clamp(i64::MIN as i128, i64::MAX as i128) as i64,
+            scale,
+        }
+    }
+
+    fn checked(self, value: i128, scale: u8, op: &str, other: Self) -> Result<Self, StatError> {
+        let value = i64::try_from(value).map_err(|_| {
+            StatError::Overflow(format!("{} {} {} exceeds the i64 range", self, op, other))
+        })?;
+        Ok(Self { value, scale })
+    }
+
+    /// The smallest-scale representation of this value (trailing zero
+    /// digits dropped), identical for equal values.
+    fn canonical(self) -> (i64, u8) {
+        let (mut value, mut scale) = (self.value, self.scale);
+        if value == 0 {
+            return (0, 0);
+        }
+        while scale > 0 && value % 10 == 0 {
+            value /= 10;
+            scale -= 1;
+        }
+        (value, scale)
+    }
 
+    /// Normalize to a common scale for arithmetic operations.
+    ///
+    /// Returns (value1, value2, common_scale) where both values
+    /// are scaled to the same factor.
+    fn normalize(self, other: Self) -> (i128, i128, u8) {
+        let common_scale = self.scale.max(other.scale);
+        let value1 = self.value as i128 * pow10(common_scale - self.scale);
+        let value2 = other.value as i128 * pow10(common_scale - other.scale);
         (value1, value2, common_scale)
     }
 }
 
-#[cfg(feature = "fixed-point")]
+fn pow10(exp: u8) -> i128 {
+    10_i128.pow(exp as u32)
+}
+
+/// Parses a decimal (`12`, `-0.25`, `1.5e3`) exactly, at `DEFAULT_SCALE`
+/// or the number of decimal places given if more.
+///
+/// # Examples
+///
+/// ```rust
+/// use zzstat::numeric::FixedPoint;
+///
+/// let fp: FixedPoint = "0.1".parse().unwrap();
+/// assert_eq!(fp, FixedPoint::new(1, 1));
+/// assert_eq!("2.5e-3".parse::<FixedPoint>().unwrap(), FixedPoint::new(25, 4));
+/// assert!("1e-19".parse::<FixedPoint>().is_err());
+/// ```
+impl FromStr for FixedPoint {
+    type Err = StatError;
+
+    fn from_str(s: &str) -> Result<Self, StatError> {
+        let invalid = |reason: &str| StatError::InvalidNumber(format!("`{}` {}", s, reason));
+        let (negative, rest) = match s.as_bytes().first() {
+            Some(b'-') => (true, &s[1..]),
+            Some(b'+') => (false, &s[1..]),
+            _ => (false, s),
+        };
+        let (mantissa, exponent) = match rest.find(['e', 'E']) {
+            Some(at) => {
+                let exponent = rest[at + 1..]
+                    .parse::<i32>()
+                    .map_err(|_| invalid("has an invalid exponent"))?;
+                (&rest[..at], exponent)
+            }
+            None => (rest, 0),
+        };
+        let (int, frac) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+        let digits = || int.bytes().chain(frac.bytes());
+        if int.is_empty() && frac.is_empty() || !digits().all(|b| b.is_ascii_digit()) {
+            return Err(invalid("is not a decimal number"));
+        }
+
+        let overflow = || invalid("exceeds the i64 range");
+        // Decimal places of the exact value (negative: trailing zeros)
+        let places = frac.len() as i64 - exponent as i64;
+        let mut value = 0_i128;
+        for digit in digits() {
+            value = value
+                .checked_mul(10)
+                .and_then(|v| v.checked_add((digit - b'0') as i128))
+                .ok_or_else(overflow)?;
+        }
+        // Drop trailing zeros beyond the default scale
+        let mut places = places;
+        while places > Self::DEFAULT_SCALE as i64 && value % 10 == 0 {
+            value /= 10;
+            places -= 1;
+        }
+        if places > Self::MAX_SCALE as i64 {
+            return Err(invalid("has more than 18 decimal places"));
+        }
+        let scale = places.max(Self::DEFAULT_SCALE as i64) as u8;
+        let shift = u32::try_from(scale as i64 - places).map_err(|_| overflow())?;
+        let value = 10_i128
+            .checked_pow(shift)
+            .and_then(|factor| value.checked_mul(factor))
+            .ok_or_else(overflow)?;
+        let value = i64::try_from(if negative { -value } else { value }).map_err(|_| overflow())?;
+        Ok(Self { value, scale })
+    }
+}
+
+/// The forms a `FixedPoint` deserializes from.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum FixedPointRepr {
+    Raw { value: i64, scale: u8 },
+    Decimal(String),
+    Number(f64),
+}
+
+impl TryFrom<FixedPointRepr> for FixedPoint {
+    type Error = StatError;
+
+    fn try_from(repr: FixedPointRepr) -> Result<Self, StatError> {
+        match repr {
+            FixedPointRepr::Raw { value, scale } => Self::try_new(value, scale),
+            FixedPointRepr::Decimal(text) => text.parse(),
+            FixedPointRepr::Number(f) => Ok(Self::from_f64(f)),
+        }
+    }
+}
+
 impl Default for FixedPoint {
     fn default() -> Self {
         Self {
@@ -201,77 +756,128 @@ impl Default for FixedPoint {
     }
 }
 
-#[cfg(feature = "fixed-point")]
+impl PartialEq for FixedPoint {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for FixedPoint {}
+
+impl PartialOrd for FixedPoint {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for FixedPoint {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let (v1, v2, _) = self.normalize(*other);
+        v1.cmp(&v2)
+    }
+}
+
+impl Hash for FixedPoint {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.canonical().hash(state);
+    }
+}
+
+/// Compares by value with an `f64`, after converting to `f64`.
+///
+/// Lets the same assertion (`assert_eq!(hp.value, 150.0)`) work with
+/// either backend.
+impl PartialEq<f64> for FixedPoint {
+    fn eq(&self, other: &f64) -> bool {
+        self.to_f64() == *other
+    }
+}
+
+impl PartialEq<FixedPoint> for f64 {
+    fn eq(&self, other: &FixedPoint) -> bool {
+        *self == other.to_f64()
+    }
+}
+
 impl From<f64> for FixedPoint {
     fn from(f: f64) -> Self {
         Self::from_f64(f)
     }
 }
 
-#[cfg(feature = "fixed-point")]
 impl From<FixedPoint> for f64 {
     fn from(fp: FixedPoint) -> Self {
         fp.to_f64()
     }
 }
 
-#[cfg(feature = "fixed-point")]
 impl Add for FixedPoint {
     type Output = Self;
 
     fn add(self, other: Self) -> Self {
-        let (v1, v2, scale) = self.normalize(other);
-        Self {
-            value: v1 + v2,
-            scale,
-        }
+        self.saturating_add(other)
     }
 }
 
-#[cfg(feature = "fixed-point")]
 impl Sub for FixedPoint {
     type Output = Self;
 
     fn sub(self, other: Self) -> Self {
-        let (v1, v2, scale) = self.normalize(other);
-        Self {
-            value: v1 - v2,
-            scale,
-        }
+        self.saturating_sub(other)
     }
 }
 
-#[cfg(feature = "fixed-point")]
 impl Mul for FixedPoint {
     type Output = Self;
 
     fn mul(self, other: Self) -> Self {
-        let (v1, v2, scale) = self.normalize(other);
-        // Result needs to be divided by 10^scale to maintain scale
-        let result = (v1 * v2) / 10_i64.pow(scale as u32);
-        Self {
-            value: result,
-            scale,
-        }
+        self.saturating_mul(other)
     }
 }
 
-#[cfg(feature = "fixed-point")]
 impl Div for FixedPoint {
     type Output = Self;
 
     fn div(self, other: Self) -> Self {
-        let (v1, v2, scale) = self.normalize(other);
-        // Result needs to be multiplied by 10^scale to maintain scale
-        let result = (v1 * 10_i64.pow(scale as u32)) / v2;
+        self.saturating_div(other)
+    }
+}
+
+impl Neg for FixedPoint {
+    type Output = Self;
+
+    fn neg(self) -> Self {
         Self {
-            value: result,
-            scale,
+            value: self.value.saturating_neg(),
+            scale: self.scale,
         }
     }
 }
 
-#[cfg(feature = "fixed-point")]
+impl AddAssign for FixedPoint {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for FixedPoint {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl MulAssign for FixedPoint {
+    fn mul_assign(&mut self, other: Self) {
+        *self = *self * other;
+    }
+}
+
+impl DivAssign for FixedPoint {
+    fn div_assign(&mut self, other: Self) {
+        *self = *self / other;
+    }
+}
+
 impl StatNumeric for FixedPoint {
     fn zero() -> Self {
         Self {
@@ -281,10 +887,7 @@ impl StatNumeric for FixedPoint {
     }
 
     fn from_int(i: i64) -> Self {
-        Self {
-            value: i * 10_i64.pow(Self::DEFAULT_SCALE as u32),
-            scale: Self::DEFAULT_SCALE,
-        }
+        Self::saturating(i as i128 * pow10(Self::DEFAULT_SCALE), Self::DEFAULT_SCALE)
     }
 
     fn from_f64(f: f64) -> Self {
@@ -304,30 +907,223 @@ impl StatNumeric for FixedPoint {
             self
         }
     }
+
+    fn with_scale(self, scale: u8) -> Self {
+        self.rescale(scale, RoundingMode::HalfAwayFromZero)
+    }
+
+    fn from_decimal(value: Decimal) -> Self {
+        value.to_fixed()
+    }
+
+    fn mul_div(self, mul: Self, div: Self) -> Self {
+        self.mul_div_rounded(mul, div, RoundingMode::HalfAwayFromZero)
+    }
+
+    fn exp_neg(self) -> Self {
+        self.exp_neg()
+    }
 }
 
-#[cfg(feature = "fixed-point")]
+/// Writes the exact decimal value without trailing zeros (`1.5`, `-0.25`,
+/// `150`), the same for every scale. A precision rounds half away from
+/// zero and pads to that many places (`{:.2}` writes `1.50`).
 impl fmt::Display for FixedPoint {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{:.4}", self.to_f64())
+        let (value, scale) = match f.precision() {
+            Some(places) => {
+                let places = places.min(Self::MAX_SCALE as usize) as u8;
+                let rounded = self.rescale(places, RoundingMode::HalfAwayFromZero);
+                (rounded.value, rounded.scale)
+            }
+            None => self.canonical(),
+        };
+        let digits = value.unsigned_abs().to_string();
+        let scale = scale as usize;
+        let text = if scale == 0 {
+            digits
+        } else {
+            let digits = format!("{:0>width$}", digits, width = scale + 1);
+            let (int, frac) = digits.split_at(digits.len() - scale);
+            format!("{}.{}", int, frac)
+        };
+        f.pad_integral(value >= 0, "", &text)
     }
 }
 
-/// Type alias for stat values.
+/// Type alias for the default stat value type.
 ///
 /// Uses `FixedPoint` when the `fixed-point` feature is enabled,
-/// otherwise uses `f64`.
+/// otherwise uses `f64`. Generic types such as `StatResolver<N>` default
+/// to it.
 #[cfg(feature = "fixed-point")]
 pub type StatValue = FixedPoint;
 
 #[cfg(not(feature = "fixed-point"))]
 pub type StatValue = f64;
 
+/// An exact decimal for content: bonus amounts, definition values, stack
+/// rule parameters and formula literals.
+///
+/// Content is stored the same way whichever backend `StatValue` is, and
+/// converted with `StatNumeric::from_decimal` when it is compiled into a
+/// resolver: exactly for `FixedPoint`, to the nearest value for `f64`. A
+/// `StatResolver<FixedPoint>` in an `f64` build gets exact content too.
+/// A `Decimal` has the range and precision of a `FixedPoint` (up to 18
+/// decimal places).
+///
+/// Floats convert through the shortest decimal that reads back as the
+/// same float, i.e. the literal that was written.
+///
+/// A `Decimal` serializes as its shortest decimal string (`"0.1"`,
+/// `"30"`) in every build, and deserializes from a decimal string, a
+/// number or the raw `{ "value", "scale" }` form of a `FixedPoint`.
+///
+/// # Examples
+///
+/// ```rust
+/// use zzstat::numeric::{Decimal, FixedPoint, StatNumeric};
+///
+/// let rate = Decimal::from(0.12345);
+/// assert_eq!(FixedPoint::from_decimal(rate), "0.12345".parse::<FixedPoint>().unwrap());
+/// assert_eq!(f64::from_decimal(rate), 0.12345);
+/// assert_eq!("0.1".parse::<Decimal>().unwrap(), Decimal::from(0.1));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize)]
+#[serde(try_from = "FixedPointRepr")]
+pub struct Decimal(FixedPoint);
+
+impl Decimal {
+    /// Zero.
+    pub const ZERO: Decimal = Decimal(FixedPoint {
+        value: 0,
+        scale: FixedPoint::DEFAULT_SCALE,
+    });
+
+    /// Get the exact value as a `FixedPoint`.
+    pub fn to_fixed(self) -> FixedPoint {
+        self.0
+    }
+
+    /// Get the nearest `f64`.
+    pub fn to_f64(self) -> f64 {
+        let (value, scale) = (self.0.value, self.0.scale);
+        if value.unsigned_abs() < 1 << 53 {
+            // Both operands are exact, so the quotient is rounded once
+            value as f64 / 10_f64.powi(scale as i32)
+        } else {
+            self.0
+                .to_string()
+                .parse()
+                .expect("a decimal string is a valid f64")
+        }
+    }
+}
+
+/// Converts through the shortest decimal that reads back as `f`, at
+/// `FixedPoint::DEFAULT_SCALE` or more places. Digits beyond 18 places
+/// are rounded, values outside the range saturate and NaN becomes zero.
+impl From<f64> for Decimal {
+    fn from(f: f64) -> Self {
+        if !f.is_finite() {
+            return Self(FixedPoint::from_f64(f));
+        }
+        match f.to_string().parse() {
+            Ok(exact) => Self(exact),
+            // More than 18 decimal places
+            Err(_) if f.abs() < 1.0 => {
+                Self(FixedPoint::from_f64_with_scale(f, FixedPoint::MAX_SCALE))
+            }
+            // Outside the i64 range
+            Err(_) => Self(FixedPoint::from_f64(f)),
+        }
+    }
+}
+
+impl From<i32> for Decimal {
+    fn from(i: i32) -> Self {
+        Self(FixedPoint::from_int(i as i64))
+    }
+}
+
+impl From<i64> for Decimal {
+    fn from(i: i64) -> Self {
+        Self(FixedPoint::from_int(i))
+    }
+}
+
+impl From<FixedPoint> for Decimal {
+    fn from(fp: FixedPoint) -> Self {
+        Self(fp)
+    }
+}
+
+impl From<Decimal> for FixedPoint {
+    fn from(decimal: Decimal) -> Self {
+        decimal.0
+    }
+}
+
+impl From<Decimal> for f64 {
+    fn from(decimal: Decimal) -> Self {
+        decimal.to_f64()
+    }
+}
+
+impl Serialize for Decimal {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl TryFrom<FixedPointRepr> for Decimal {
+    type Error = StatError;
+
+    fn try_from(repr: FixedPointRepr) -> Result<Self, StatError> {
+        match repr {
+            FixedPointRepr::Number(f) => Ok(Self::from(f)),
+            repr => FixedPoint::try_from(repr).map(Self),
+        }
+    }
+}
+
+/// Parses a decimal exactly, like `FixedPoint`.
+impl FromStr for Decimal {
+    type Err = StatError;
+
+    fn from_str(s: &str) -> Result<Self, StatError> {
+        s.parse().map(Self)
+    }
+}
+
+impl fmt::Display for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+/// The larger of two values (`a` when they compare equal).
+pub(crate) fn max_value<N: StatNumeric>(a: N, b: N) -> N {
+    if b > a {
+        b
+    } else {
+        a
+    }
+}
+
+/// The smaller of two values (`a` when they compare equal).
+pub(crate) fn min_value<N: StatNumeric>(a: N, b: N) -> N {
+    if b < a {
+        b
+    } else {
+        a
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[cfg(feature = "fixed-point")]
     #[test]
     fn test_fixed_point_creation() {
         let fp = FixedPoint::new(12345, 4);
@@ -335,14 +1131,12 @@ mod tests {
         assert_eq!(fp.scale(), 4);
     }
 
-    #[cfg(feature = "fixed-point")]
     #[test]
     fn test_fixed_point_from_f64() {
         let fp = FixedPoint::from_f64(1.2345);
         assert!((fp.to_f64() - 1.2345).abs() < 0.0001);
     }
 
-    #[cfg(feature = "fixed-point")]
     #[test]
     fn test_fixed_point_arithmetic() {
         let a = FixedPoint::from_f64(10.0);
@@ -354,7 +1148,6 @@ mod tests {
         assert!((a.div(b).to_f64() - 2.0).abs() < 0.1); // Division has precision loss
     }
 
-    #[cfg(feature = "fixed-point")]
     #[test]
     fn test_fixed_point_different_scales() {
         let a = FixedPoint::new(12345, 4); // 1.2345
@@ -365,6 +1158,252 @@ mod tests {
         assert_eq!(sum.scale(), 5);
     }
 
+    #[test]
+    fn test_fixed_point_large_products() {
+        // 5e9 × 3e9 at scale 4 overflows an i64 intermediate
+        let a = FixedPoint::from_f64(5_000_000_000.0);
+        let b = FixedPoint::from_f64(3.0);
+        assert_eq!((a * b).to_f64(), 15_000_000_000.0);
+        assert_eq!((a * b / b).to_f64(), 5_000_000_000.0);
+        assert_eq!(a.checked_mul(b).unwrap(), a * b);
+
+        // A result beyond the i64 range saturates or errors
+        let huge = a * a;
+        assert_eq!(huge.value(), i64::MAX);
+        assert!(matches!(a.checked_mul(a), Err(StatError::Overflow(_))));
+        let max = FixedPoint::new(i64::MAX, 0);
+        assert_eq!((max + max).value(), i64::MAX);
+        assert!(max.checked_add(max).is_err());
+        assert_eq!((-max - max).value(), i64::MIN);
+        assert!((-max).checked_sub(max).is_err());
+    }
+
+    #[test]
+    fn test_fixed_point_mixed_scale_extremes() {
+        let big: FixedPoint = "100000000000000".parse().unwrap();
+        let fine: FixedPoint = "900000000.0000000001".parse().unwrap();
+        assert!(big.checked_mul(fine).is_err());
+        assert_eq!((big * fine).value(), i64::MAX);
+        assert_eq!((-big * fine).value(), i64::MIN);
+
+        let max = FixedPoint::new(i64::MAX, 0);
+        let tiny: FixedPoint = "0.000000000000000001".parse().unwrap();
+        assert!(max.checked_div(tiny).is_err());
+        assert_eq!((max / tiny).value(), i64::MAX);
+        assert_eq!((max / -tiny).value(), i64::MIN);
+
+        // In range, mixed scales keep the larger scale and stay exact
+        let a = FixedPoint::new(25, 1); // 2.5
+        let b = FixedPoint::new(1_500_000, 6); // 1.5
+        assert_eq!(a.checked_mul(b).unwrap(), FixedPoint::new(375, 2));
+        assert_eq!(a.checked_mul(b).unwrap().scale(), 6);
+        assert_eq!(b.checked_div(a).unwrap(), FixedPoint::new(6, 1));
+        assert_eq!(
+            FixedPoint::new(1, 6).mul_rounded(FixedPoint::new(5, 1), RoundingMode::HalfEven),
+            FixedPoint::new(0, 6)
+        );
+        assert_eq!(
+            FixedPoint::new(1, 0).div_rounded(FixedPoint::new(3, 6), RoundingMode::Floor),
+            FixedPoint::new(333_333_333_333, 6)
+        );
+    }
+
+    #[test]
+    fn test_fixed_point_division_by_zero() {
+        let zero = FixedPoint::from_f64(0.0);
+        let one = FixedPoint::from_f64(1.0);
+        assert!(matches!(one.checked_div(zero), Err(StatError::Overflow(_))));
+        assert_eq!((one / zero).value(), i64::MAX);
+        assert_eq!((-one / zero).value(), i64::MIN);
+        assert_eq!((zero / zero).value(), 0);
+    }
+
+    #[test]
+    fn test_fixed_point_rounding_modes() {
+        let cases = [
+            // (value, half away, half even, floor, truncate)
+            (2.5, 3, 2, 2, 2),
+            (3.5, 4, 4, 3, 3),
+            (-2.5, -3, -2, -3, -2),
+            (2.7, 3, 3, 2, 2),
+            (-2.3, -2, -2, -3, -2),
+        ];
+        for (f, away, even, floor, trunc) in cases {
+            let round = |mode| FixedPoint::from_f64_rounded(f, 0, mode).value();
+            assert_eq!(round(RoundingMode::HalfAwayFromZero), away, "{}", f);
+            assert_eq!(round(RoundingMode::HalfEven), even, "{}", f);
+            assert_eq!(round(RoundingMode::Floor), floor, "{}", f);
+            assert_eq!(round(RoundingMode::Truncate), trunc, "{}", f);
+
+            // Dropping a digit with `rescale` rounds the same way
+            let tenths = FixedPoint::new((f * 10.0) as i64, 1);
+            let rescale = |mode| tenths.rescale(0, mode).value();
+            assert_eq!(rescale(RoundingMode::HalfAwayFromZero), away, "{}", f);
+            assert_eq!(rescale(RoundingMode::HalfEven), even, "{}", f);
+            assert_eq!(rescale(RoundingMode::Floor), floor, "{}", f);
+            assert_eq!(rescale(RoundingMode::Truncate), trunc, "{}", f);
+        }
+
+        // 1 / 3 at scale 4, and -2 / 3
+        let one = FixedPoint::from_f64(1.0);
+        let three = FixedPoint::from_f64(3.0);
+        assert_eq!(one.div_rounded(three, RoundingMode::Floor).value(), 3333);
+        let minus_two = FixedPoint::from_f64(-2.0);
+        assert_eq!(
+            minus_two.div_rounded(three, RoundingMode::Floor).value(),
+            -6667
+        );
+        assert_eq!(
+            minus_two.div_rounded(three, RoundingMode::Truncate).value(),
+            -6666
+        );
+        assert_eq!(
+            minus_two.div_rounded(three, RoundingMode::HalfEven).value(),
+            -6667
+        );
+    }
+
+    #[test]
+    fn test_fixed_point_compares_across_scales() {
+        use std::collections::HashSet;
+
+        let a = FixedPoint::new(10000, 4); // 1.0
+        let b = FixedPoint::new(100, 2); // 1.0
+        let c = FixedPoint::new(15, 1); // 1.5
+        assert_eq!(a, b);
+        assert_eq!(a.cmp(&b), Ordering::Equal);
+        assert!(b < c && c > a);
+        assert!(FixedPoint::new(-5, 1) < FixedPoint::new(-4000, 4));
+        assert_eq!(max_value(c, a), c);
+        assert_eq!(min_value(c, b), b);
+        assert_eq!(StatNumeric::clamp(c, a, b), b);
+
+        let set: HashSet<_> = [a, b, FixedPoint::new(0, 0), FixedPoint::new(0, 6)]
+            .into_iter()
+            .collect();
+        assert_eq!(set.len(), 2);
+    }
+
+    #[test]
+    fn test_fixed_point_display() {
+        assert_eq!(FixedPoint::new(10000, 4).to_string(), "1");
+        assert_eq!(FixedPoint::new(100, 2).to_string(), "1");
+        assert_eq!(FixedPoint::new(-2500, 4).to_string(), "-0.25");
+        assert_eq!(FixedPoint::new(1234567, 6).to_string(), "1.234567");
+        assert_eq!(FixedPoint::new(15, 1).to_string(), "1.5");
+        assert_eq!(format!("{:.2}", FixedPoint::new(15, 1)), "1.50");
+        assert_eq!(format!("{:.1}", FixedPoint::new(-125, 2)), "-1.3");
+        assert_eq!(format!("{:>6}", FixedPoint::new(15, 1)), "   1.5");
+    }
+
+    #[test]
+    fn test_fixed_point_parse_is_exact() {
+        let parse = |s: &str| s.parse::<FixedPoint>();
+        assert_eq!(parse("0.1").unwrap(), FixedPoint::new(1000, 4));
+        assert_eq!(parse("0.1").unwrap().scale(), 4);
+        assert_eq!(parse("-12.345678").unwrap(), FixedPoint::new(-12345678, 6));
+        assert_eq!(parse("+7").unwrap(), FixedPoint::new(7, 0));
+        assert_eq!(parse("1.5E3").unwrap(), FixedPoint::new(1500, 0));
+        assert_eq!(parse("1.50000000").unwrap().scale(), 4);
+        assert_eq!(parse(".5").unwrap(), FixedPoint::new(5, 1));
+        for bad in ["", "-", ".", "1.2.3", "abc", "1e", "1e-19", "1e30"] {
+            assert!(
+                matches!(parse(bad), Err(StatError::InvalidNumber(_))),
+                "{}",
+                bad
+            );
+        }
+    }
+
+    #[test]
+    fn test_fixed_point_deserialize_forms() {
+        let from = |json: &str| serde_json::from_str::<FixedPoint>(json).unwrap();
+        let exact = FixedPoint::new(1, 1);
+        assert_eq!(from(r#""0.1""#), exact);
+        assert_eq!(from("0.1"), exact);
+        assert_eq!(from(r#"{"value": 1, "scale": 1}"#), exact);
+
+        let raw = FixedPoint::new(123456789, 6);
+        let json = serde_json::to_string(&raw).unwrap();
+        let back = from(&json);
+        assert_eq!((back.value(), back.scale()), (raw.value(), raw.scale()));
+        assert!(serde_json::from_str::<FixedPoint>(r#""ten""#).is_err());
+    }
+
+    #[test]
+    fn test_mul_div_rounded() {
+        let fp = |s: &str| s.parse::<FixedPoint>().unwrap();
+        let half = RoundingMode::HalfAwayFromZero;
+        // 1 × 2 / 3 rounds once, at the largest scale
+        assert_eq!(
+            fp("1").mul_div_rounded(fp("2"), fp("3"), half),
+            fp("0.6667")
+        );
+        assert_eq!(
+            fp("-1").mul_div_rounded(fp("2"), fp("3"), half),
+            fp("-0.6667")
+        );
+        // Mixed scales: the result takes the largest
+        let r = FixedPoint::new(1, 0).mul_div_rounded(
+            FixedPoint::new(1_000_000_000_000_000_000, 18),
+            FixedPoint::new(3, 0),
+            half,
+        );
+        assert_eq!((r.value(), r.scale()), (333_333_333_333_333_333, 18));
+        // Out-of-range quotients and division by zero saturate
+        let max = FixedPoint::new(i64::MAX, 0);
+        assert_eq!(max.mul_div_rounded(max, fp("1"), half).value(), i64::MAX);
+        assert_eq!(
+            fp("-5").mul_div_rounded(fp("1"), fp("0"), half).value(),
+            i64::MIN
+        );
+        assert_eq!(StatNumeric::mul_div(fp("6"), fp("7"), fp("2")), fp("21"));
+    }
+
+    #[test]
+    fn test_fixed_point_exp_neg() {
+        let fp = |s: &str| s.parse::<FixedPoint>().unwrap();
+        assert_eq!(fp("0").exp_neg(), fp("1"));
+        // e^-0.5 = 0.606530659712633423..., to a few steps at scale 18
+        let half = FixedPoint::new(500_000_000_000_000_000, 18).exp_neg();
+        assert_eq!(half.scale(), 18);
+        assert!((half.value() - 606_530_659_712_633_423).abs() <= 5);
+        // e^-10 = 0.0000453999297625: rounds to zero at the default scale
+        assert_eq!(fp("10").exp_neg(), fp("0"));
+        let ten = FixedPoint::new(10_000_000_000_000_000, 15).exp_neg();
+        assert_eq!(ten, fp("0.000045399929762"));
+        // Far below the smallest step, and far above the largest value
+        assert_eq!(fp("100").exp_neg(), fp("0"));
+        assert_eq!(fp("-100").exp_neg().value(), i64::MAX);
+        // Agrees with f64 to its precision
+        for x in [0.001, 0.3, 0.69, 1.0, 2.5, 7.0, 20.0] {
+            let exact = FixedPoint::from_f64_with_scale(x, 12).exp_neg();
+            assert!((exact.to_f64() - (-x).exp()).abs() < 1e-12, "e^-{}", x);
+            let growing = FixedPoint::from_f64_with_scale(-x, 6).exp_neg();
+            assert!((growing.to_f64() / x.exp() - 1.0).abs() < 1e-6, "e^{}", x);
+        }
+    }
+
+    #[test]
+    fn test_fixed_point_scale_limit() {
+        let max = FixedPoint::new(1, FixedPoint::MAX_SCALE);
+        assert_eq!(max.to_f64(), 1e-18);
+        assert!(matches!(
+            FixedPoint::try_new(1, 19),
+            Err(StatError::InvalidNumber(_))
+        ));
+        assert_eq!(FixedPoint::new(15, 19), FixedPoint::new(2, 18));
+        assert_eq!(FixedPoint::new(-15, 19), FixedPoint::new(-2, 18));
+        assert_eq!(FixedPoint::new(i64::MAX, 60), FixedPoint::zero());
+        assert_eq!(max.rescale(19, RoundingMode::Truncate).scale(), 18);
+        assert_eq!(FixedPoint::from_f64_with_scale(0.5, 30).scale(), 18);
+
+        match serde_json::from_str::<FixedPoint>(r#"{"value": 1, "scale": 60}"#) {
+            Err(err) => assert!(err.to_string().contains("scale 60")),
+            Ok(fp) => panic!("Expected an error, got {:?}", fp),
+        }
+    }
+
     #[test]
     fn test_stat_numeric_trait() {
         #[cfg(not(feature = "fixed-point"))]
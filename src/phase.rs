@@ -0,0 +1,202 @@
+//! Phase registry module.
+//!
+//! By default phases run in `TransformPhase::value()` order, which puts
+//! every custom phase after `Final`. A `PhaseRegistry` lets a project name
+//! its phases and choose their order, for example
+//! "base, gear, buff, aura, cap" with the caps last.
+
+use crate::error::StatError;
+use crate::transform::TransformPhase;
+
+/// Named phases in the order a resolver applies them.
+///
+/// Declared phases run in declaration order. A phase that was not declared
+/// keeps its default place: it runs just before the first declared phase
+/// with a higher `value()`, or after all declared phases if there is none.
+/// An undeclared `Additive` phase therefore still runs before a declared
+/// `Final` cap.
+///
+/// # Examples
+///
+/// ```rust
+/// use zzstat::*;
+/// use zzstat::phase::PhaseRegistry;
+/// use zzstat::source::ConstantSource;
+///
+/// let phases = PhaseRegistry::from_phases([
+///     ("base", TransformPhase::Additive),
+///     ("gear", TransformPhase::Custom(3)),
+///     ("buff", TransformPhase::Custom(4)),
+///     ("aura", TransformPhase::Multiplicative),
+///     ("cap", TransformPhase::Final),
+/// ])
+/// .unwrap();
+///
+/// let mut resolver = StatResolver::with_phases(phases);
+/// let hp_id = StatId::from_str("HP");
+/// resolver.register_source(hp_id.clone(), Box::new(ConstantSource::new(100.0)));
+///
+/// let gear = resolver.phases().phase("gear").unwrap();
+/// resolver.register_transform_in_phase(hp_id.clone(), gear, Box::new(AdditiveTransform::new(50.0)));
+/// resolver.register_transform(hp_id.clone(), Box::new(ClampTransform::new(0.0, 120.0)));
+///
+/// // The cap runs after the gear phase
+/// let resolved = resolver.resolve(&hp_id, &StatContext::new()).unwrap();
+/// assert_eq!(resolved.value, 120.0);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PhaseRegistry {
+    phases: Vec<(String, TransformPhase)>,
+}
+
+impl PhaseRegistry {
+    /// Create an empty registry (the default phase order).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a registry from `(name, phase)` pairs, in application order.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(PhaseRegistry)` - The registry
+    /// * `Err(StatError::InvalidPhase)` - If a name or phase is declared twice
+    pub fn from_phases<I, S>(phases: I) -> Result<Self, StatError>
+    where
+        I: IntoIterator<Item = (S, TransformPhase)>,
+        S: Into<String>,
+    {
+        let mut registry = Self::new();
+        for (name, phase) in phases {
+            registry.define(name, phase)?;
+        }
+        Ok(registry)
+    }
+
+    /// Declare a phase after every phase declared so far.
+    ///
+    /// Phases are identified by `value()`, so `Custom(1)` and `Custom(3)`
+    /// are the same phase.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The phase's name, unique within the registry
+    /// * `phase` - The phase transforms are registered in
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - The phase was declared
+    /// * `Err(StatError::InvalidPhase)` - If the name or phase is already declared
+    pub fn define(
+        &mut self,
+        name: impl Into<String>,
+        phase: TransformPhase,
+    ) -> Result<(), StatError> {
+        let name = name.into();
+        if self.phase(&name).is_some() {
+            return Err(StatError::InvalidPhase(format!(
+                "phase `{}` is declared twice",
+                name
+            )));
+        }
+        if let Some(existing) = self.name(phase) {
+            return Err(StatError::InvalidPhase(format!(
+                "`{}` and `{}` are both {:?}",
+                existing, name, phase
+            )));
+        }
+        self.phases.push((name, phase));
+        Ok(())
+    }
+
+    /// Look up a phase by name.
+    pub fn phase(&self, name: &str) -> Option<TransformPhase> {
+        self.phases
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, phase)| *phase)
+    }
+
+    /// Get the name a phase was declared with.
+    pub fn name(&self, phase: TransformPhase) -> Option<&str> {
+        self.phases
+            .iter()
+            .find(|(_, p)| p.value() == phase.value())
+            .map(|(name, _)| name.as_str())
+    }
+
+    /// Iterate over the declared phases in application order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, TransformPhase)> {
+        self.phases
+            .iter()
+            .map(|(name, phase)| (name.as_str(), *phase))
+    }
+
+    /// Get the position of a phase in application order.
+    ///
+    /// Lower ranks are applied first. An undeclared phase ranks just before
+    /// the first declared phase with a higher `value()` (after every
+    /// declared phase if there is none), ordered by `value()` among the
+    /// undeclared phases that share that slot.
+    pub fn rank(&self, phase: TransformPhase) -> usize {
+        // Each declared phase owns a slot of 257 ranks: 256 for the
+        // undeclared phases placed before it, then its own.
+        const SLOT: usize = u8::MAX as usize + 2;
+        let value = phase.value();
+        if let Some(index) = self.phases.iter().position(|(_, p)| p.value() == value) {
+            return index * SLOT + SLOT - 1;
+        }
+        let slot = self
+            .phases
+            .iter()
+            .position(|(_, p)| p.value() > value)
+            .unwrap_or(self.phases.len());
+        slot * SLOT + value as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_order() {
+        let registry = PhaseRegistry::new();
+        assert!(registry.rank(TransformPhase::Additive) < registry.rank(TransformPhase::Final));
+        assert!(registry.rank(TransformPhase::Final) < registry.rank(TransformPhase::Custom(3)));
+    }
+
+    #[test]
+    fn test_declared_order() {
+        let registry = PhaseRegistry::from_phases([
+            ("gear", TransformPhase::Custom(3)),
+            ("cap", TransformPhase::Final),
+        ])
+        .unwrap();
+
+        assert!(registry.rank(TransformPhase::Custom(3)) < registry.rank(TransformPhase::Final));
+        // Undeclared built-in phases keep their place before the cap
+        assert!(registry.rank(TransformPhase::Additive) < registry.rank(TransformPhase::Final));
+        assert!(
+            registry.rank(TransformPhase::Additive) < registry.rank(TransformPhase::Multiplicative)
+        );
+        // Undeclared custom phases still follow the cap
+        assert!(registry.rank(TransformPhase::Custom(4)) > registry.rank(TransformPhase::Final));
+        assert!(
+            registry.rank(TransformPhase::Custom(5)) > registry.rank(TransformPhase::Custom(4))
+        );
+
+        assert_eq!(registry.phase("cap"), Some(TransformPhase::Final));
+        assert_eq!(registry.name(TransformPhase::Custom(3)), Some("gear"));
+        assert_eq!(registry.phase("aura"), None);
+    }
+
+    #[test]
+    fn test_duplicates_rejected() {
+        let mut registry = PhaseRegistry::new();
+        registry.define("gear", TransformPhase::Custom(3)).unwrap();
+        assert!(registry.define("gear", TransformPhase::Custom(4)).is_err());
+        // Custom(1) is clamped to the same phase value as Custom(3)
+        assert!(registry.define("buff", TransformPhase::Custom(1)).is_err());
+    }
+}
@@ -3,9 +3,11 @@
 //! Contains the `ResolvedStat` type, which represents a fully resolved
 //! stat value with complete breakdown information for debugging.
 
-use crate::numeric::StatValue;
+use crate::numeric::{StatNumeric, StatValue};
 use crate::stat_id::StatId;
+use crate::transform::{StackRule, TransformPhase};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 /// A resolved stat value with full breakdown information.
 ///
@@ -29,27 +31,148 @@ use serde::{Deserialize, Serialize};
 /// assert_eq!(resolved.transforms.len(), 1);
 /// ```
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-pub struct ResolvedStat {
+#[serde(bound = "")]
+pub struct ResolvedStat<N: StatNumeric = StatValue> {
     /// The stat identifier.
     pub stat_id: StatId,
 
     /// The final resolved value.
-    pub value: StatValue,
+    pub value: N,
 
     /// Breakdown of all contributing sources.
     ///
     /// Each entry is `(source_description, value)`.
     /// Sources are listed in the order they were registered.
-    pub sources: Vec<(String, StatValue)>,
+    pub sources: Vec<(String, N)>,
+
+    /// Tags of each contributing source (see `StatSource::tags`).
+    ///
+    /// Entry `i` belongs to `sources[i]`; sources without tags have an
+    /// empty map.
+    #[serde(default)]
+    pub source_tags: Vec<BTreeMap<String, String>>,
 
     /// Breakdown of all applied transforms.
     ///
     /// Each entry is `(transform_description, value_after_transform)`.
     /// Transforms are listed in the order they were applied.
-    pub transforms: Vec<(String, StatValue)>,
+    pub transforms: Vec<(String, N)>,
+
+    /// Typed breakdown of all applied transforms.
+    ///
+    /// One entry per phase, in application order. Unlike `transforms`, it
+    /// contains no formatted text besides the transforms' own descriptions,
+    /// so clients can render (and localize) tooltips themselves.
+    #[serde(default)]
+    pub breakdown: Vec<PhaseBreakdown<N>>,
+}
+
+/// Breakdown of one transform phase of a resolved stat.
+///
+/// # Examples
+///
+/// ```rust
+/// use zzstat::*;
+/// use zzstat::source::ConstantSource;
+/// use zzstat::transform::{AdditiveTransform, MultiplicativeTransform, StackRule, TransformPhase};
+///
+/// let mut resolver = StatResolver::new();
+/// let atk_id = StatId::from_str("ATK");
+/// resolver.register_source(atk_id.clone(), Box::new(ConstantSource::new(100.0)));
+/// resolver.register_transform(atk_id.clone(), Box::new(AdditiveTransform::new(20.0)));
+/// resolver.register_transform(atk_id.clone(), Box::new(MultiplicativeTransform::new(1.5)));
+///
+/// let resolved = resolver.resolve(&atk_id, &StatContext::new()).unwrap();
+///
+/// let additive = &resolved.breakdown[0];
+/// assert_eq!(additive.phase, TransformPhase::Additive);
+/// assert_eq!(additive.value_before, 100.0);
+/// assert_eq!(additive.value_after, 120.0);
+///
+/// let multiplicative = &resolved.breakdown[1];
+/// assert_eq!(multiplicative.stacks[0].rule, StackRule::Multiplicative);
+/// assert_eq!(multiplicative.stacks[0].transforms[0].delta, 60.0);
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(bound = "")]
+pub struct PhaseBreakdown<N: StatNumeric = StatValue> {
+    /// The phase.
+    pub phase: TransformPhase,
+
+    /// The value entering the phase.
+    pub value_before: N,
+
+    /// The value leaving the phase.
+    pub value_after: N,
+
+    /// One entry per stack rule group, in application order.
+    pub stacks: Vec<StackBreakdown<N>>,
+}
+
+/// Breakdown of the transforms combined under one stack rule within a phase.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(bound = "")]
+pub struct StackBreakdown<N: StatNumeric = StatValue> {
+    /// The stack rule used to combine the transforms.
+    pub rule: StackRule,
+
+    /// The value before the stack was applied.
+    pub value_before: N,
+
+    /// The value after the stack was applied.
+    pub value_after: N,
+
+    /// The contributing transforms, in registration order.
+    ///
+    /// For `Override` only the winning transform is listed.
+    pub transforms: Vec<TransformBreakdown<N>>,
+
+    /// For `DiminishingReturns`, the raw sum and the value actually added.
+    #[serde(default)]
+    pub diminishing: Option<DiminishingBreakdown<N>>,
+}
+
+/// Raw and diminished contribution of a `DiminishingReturns` stack.
+///
+/// The deltas of the stack's transforms are scaled so they add up to
+/// `diminished`, keeping each transform's share of `raw`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(bound = "")]
+pub struct DiminishingBreakdown<N: StatNumeric = StatValue> {
+    /// The sum of the transforms' contributions before diminishing.
+    pub raw: N,
+
+    /// The value actually added after diminishing.
+    pub diminished: N,
+}
+
+/// Contribution of a single transform to a resolved stat.
+///
+/// `value_before` and `value_after` describe the transform applied on its
+/// own at its position in the stack, so the deltas of a stack add up to
+/// the stack's total change. Clamp transforms that are not the most
+/// restrictive bound have a zero delta; diminishing stacks split their
+/// change evenly between the stacked transforms.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(bound = "")]
+pub struct TransformBreakdown<N: StatNumeric = StatValue> {
+    /// The transform's description (see `StatTransform::description`).
+    pub description: String,
+
+    /// Where the transform came from (see `StatTransform::origin`).
+    pub origin: Option<String>,
+
+    /// The change attributed to this transform.
+    pub delta: N,
+
+    /// The value before this transform.
+    pub value_before: N,
+
+    /// The value after this transform.
+    pub value_after: N,
 }
 
-impl ResolvedStat {
+impl<N: StatNumeric> ResolvedStat<N> {
     /// Create a new `ResolvedStat` with the given stat ID and value.
     ///
     /// The sources and transforms vectors start empty and can be
@@ -64,12 +187,14 @@ impl ResolvedStat {
     /// let resolved = ResolvedStat::new(StatId::from_str("HP"), 100.0);
     /// assert_eq!(resolved.value, 100.0);
     /// ```
-    pub fn new(stat_id: StatId, value: StatValue) -> Self {
+    pub fn new(stat_id: StatId, value: N) -> Self {
         Self {
             stat_id,
             value,
             sources: Vec::new(),
+            source_tags: Vec::new(),
             transforms: Vec::new(),
+            breakdown: Vec::new(),
         }
     }
 
@@ -93,8 +218,86 @@ impl ResolvedStat {
     /// resolved.add_source("Base HP", 100.0);
     /// resolved.add_source("Item Bonus", 50.0);
     /// ```
-    pub fn add_source(&mut self, description: impl Into<String>, value: StatValue) {
+    pub fn add_source(&mut self, description: impl Into<String>, value: N) {
+        self.add_tagged_source(description, value, BTreeMap::new());
+    }
+
+    /// Add a tagged source contribution to the breakdown.
+    ///
+    /// # Arguments
+    ///
+    /// * `description` - Human-readable description of the source
+    /// * `value` - The value contributed by this source
+    /// * `tags` - Tags attributing the source (origin item ID, category, ...)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use zzstat::ResolvedStat;
+    /// use zzstat::StatId;
+    /// use std::collections::BTreeMap;
+    ///
+    /// let mut resolved = ResolvedStat::new(StatId::from_str("HP"), 150.0);
+    /// resolved.add_source("Base HP", 100.0);
+    /// resolved.add_tagged_source(
+    ///     "Plate Armor",
+    ///     50.0,
+    ///     BTreeMap::from([("slot".to_string(), "chest".to_string())]),
+    /// );
+    /// assert_eq!(resolved.source_tags[1]["slot"], "chest");
+    /// ```
+    pub fn add_tagged_source(
+        &mut self,
+        description: impl Into<String>,
+        value: N,
+        tags: BTreeMap<String, String>,
+    ) {
         self.sources.push((description.into(), value));
+        self.source_tags.push(tags);
+    }
+
+    /// Get the sources carrying a specific tag value.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The tag name
+    /// * `value` - The tag value to match
+    ///
+    /// # Returns
+    ///
+    /// An iterator over `(source_description, value)` entries of matching
+    /// sources, in registration order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use zzstat::ResolvedStat;
+    /// use zzstat::StatId;
+    /// use std::collections::BTreeMap;
+    ///
+    /// let guild = BTreeMap::from([("category".to_string(), "guild".to_string())]);
+    ///
+    /// let mut resolved = ResolvedStat::new(StatId::from_str("ATK"), 115.0);
+    /// resolved.add_source("Base", 100.0);
+    /// resolved.add_tagged_source("Guild perk", 10.0, guild.clone());
+    /// resolved.add_tagged_source("Guild banner", 5.0, guild);
+    ///
+    /// let total: f64 = resolved
+    ///     .sources_with_tag("category", "guild")
+    ///     .map(|(_, value)| *value)
+    ///     .sum();
+    /// assert_eq!(total, 15.0);
+    /// ```
+    pub fn sources_with_tag<'a>(
+        &'a self,
+        key: &'a str,
+        value: &'a str,
+    ) -> impl Iterator<Item = &'a (String, N)> + 'a {
+        self.sources
+            .iter()
+            .zip(&self.source_tags)
+            .filter(move |(_, tags)| tags.get(key).is_some_and(|v| v == value))
+            .map(|(source, _)| source)
     }
 
     /// Add a transform application to the breakdown.
@@ -116,7 +319,7 @@ impl ResolvedStat {
     /// let mut resolved = ResolvedStat::new(StatId::from_str("ATK"), 150.0);
     /// resolved.add_transform("Multiplier 1.5x", 150.0);
     /// ```
-    pub fn add_transform(&mut self, description: impl Into<String>, value: StatValue) {
+    pub fn add_transform(&mut self, description: impl Into<String>, value: N) {
         self.transforms.push((description.into(), value));
     }
 }
@@ -124,7 +327,6 @@ impl ResolvedStat {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::numeric::StatNumeric;
 
     #[test]
     fn test_resolved_stat_creation() {
@@ -159,6 +361,32 @@ mod tests {
         assert_eq!(stat.sources[2].0, "Item2");
     }
 
+    #[test]
+    fn test_resolved_stat_source_tags() {
+        let mut stat = ResolvedStat::new(StatId::from_str("HP"), StatValue::from_f64(170.0));
+        stat.add_source("Base", StatValue::from_f64(100.0));
+        stat.add_tagged_source(
+            "Helmet",
+            StatValue::from_f64(30.0),
+            BTreeMap::from([("slot".to_string(), "head".to_string())]),
+        );
+        stat.add_tagged_source(
+            "Armor",
+            StatValue::from_f64(40.0),
+            BTreeMap::from([("slot".to_string(), "chest".to_string())]),
+        );
+
+        assert_eq!(stat.sources.len(), stat.source_tags.len());
+        assert!(stat.source_tags[0].is_empty());
+
+        let head: Vec<_> = stat.sources_with_tag("slot", "head").collect();
+        assert_eq!(
+            head,
+            vec![&("Helmet".to_string(), StatValue::from_f64(30.0))]
+        );
+        assert_eq!(stat.sources_with_tag("slot", "feet").count(), 0);
+    }
+
     #[test]
     fn test_resolved_stat_multiple_transforms() {
         let mut stat = ResolvedStat::new(StatId::from_str("ATK"), StatValue::from_f64(300.0));
@@ -4,38 +4,177 @@
 //! for stat resolution. It manages sources, transforms, dependency
 //! graphs, and caching.
 
-use crate::context::StatContext;
+use crate::context::{ContextFingerprint, StatContext};
 use crate::error::StatError;
 use crate::graph::StatGraph;
-use crate::numeric::{StatNumeric, StatValue};
-use crate::resolved::ResolvedStat;
+use crate::numeric::{max_value, min_value, FixedPoint, StatNumeric, StatValue};
+use crate::phase::PhaseRegistry;
+use crate::resolved::{
+    DiminishingBreakdown, PhaseBreakdown, ResolvedStat, StackBreakdown, TransformBreakdown,
+};
 use crate::source::StatSource;
 use crate::stat_id::StatId;
-use crate::transform::{StackRule, StatTransform, TransformEntry, TransformPhase};
-use std::collections::HashMap;
+use crate::transform::{
+    Contribution, DiminishingMode, StackRule, StatTransform, TransformEntry, TransformPhase,
+};
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 
-/// Base data shared across resolver forks.
+/// Process-wide counter for registration handles.
 ///
-/// Contains the sources and transforms that are shared via copy-on-write.
-struct BaseData {
+/// Handles are unique across all resolvers, so a handle issued by a fork
+/// can never alias an entry registered in its parent (or a sibling fork).
+static NEXT_HANDLE_ID: AtomicU64 = AtomicU64::new(1);
+
+fn next_handle_id() -> u64 {
+    NEXT_HANDLE_ID.fetch_add(1, Ordering::Relaxed)
+}
+
+/// Handle to a source registered with [`StatResolver::register_source`].
+///
+/// Pass it to [`StatResolver::remove_source`] to take the source back out
+/// (e.g. when an item is unequipped).
+///
+/// # Examples
+///
+/// ```rust
+/// use zzstat::*;
+/// use zzstat::source::ConstantSource;
+///
+/// let mut resolver = StatResolver::new();
+/// let hp_id = StatId::from_str("HP");
+///
+/// let handle = resolver.register_source(hp_id.clone(), Box::new(ConstantSource::new(100.0)));
+/// assert_eq!(handle.stat(), &hp_id);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SourceHandle {
+    stat: StatId,
+    id: u64,
+}
+
+impl SourceHandle {
+    /// Get the stat this source was registered for.
+    pub fn stat(&self) -> &StatId {
+        &self.stat
+    }
+}
+
+/// Handle to a transform registered with one of the
+/// `StatResolver::register_transform*` methods.
+///
+/// Pass it to [`StatResolver::remove_transform`] to take the transform back
+/// out (e.g. when a buff expires).
+///
+/// # Examples
+///
+/// ```rust
+/// use zzstat::*;
+/// use zzstat::transform::AdditiveTransform;
+///
+/// let mut resolver = StatResolver::new();
+/// let atk_id = StatId::from_str("ATK");
+///
+/// let handle = resolver.register_transform(atk_id.clone(), Box::new(AdditiveTransform::new(10.0)));
+/// assert_eq!(handle.stat(), &atk_id);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TransformHandle {
+    stat: StatId,
+    id: u64,
+}
+
+impl TransformHandle {
+    /// Get the stat this transform was registered for.
+    pub fn stat(&self) -> &StatId {
+        &self.stat
+    }
+}
+
+/// A registered item together with the id of its handle.
+///
+/// Items are reference-counted so layers can be copied cheaply when a
+/// fork's changes are committed into another resolver.
+#[derive(Clone)]
+struct Slot<T> {
+    id: u64,
+    item: T,
+}
+
+/// The sources registered for one stat in one layer.
+type SourceSlots<N> = Vec<Slot<Arc<dyn StatSource<N>>>>;
+
+/// One layer of registered sources and transforms.
+///
+/// A resolver is a stack of layers. The bottom layer holds the base data;
+/// every fork stacks a fresh overlay layer on top of its parent's chain.
+/// Layers are shared between resolvers via `Arc` and are only mutated while
+/// uniquely owned (copy-on-write).
+#[derive(Clone)]
+struct Layer<N: StatNumeric> {
     /// Multiple sources per stat (additive).
-    sources: HashMap<StatId, Vec<Box<dyn StatSource>>>,
+    sources: HashMap<StatId, SourceSlots<N>>,
 
     /// Transform chain per stat.
-    transforms: HashMap<StatId, Vec<TransformEntry>>,
+    transforms: HashMap<StatId, Vec<Slot<Arc<TransformEntry<N>>>>>,
+
+    /// Handle ids of sources from lower layers removed in this layer.
+    removed_sources: HashSet<u64>,
+
+    /// Handle ids of transforms from lower layers removed in this layer.
+    removed_transforms: HashSet<u64>,
 }
 
-/// Overlay data for copy-on-write modifications.
-///
-/// When a resolver is forked, modifications are stored in the overlay.
-/// Reading checks overlay first, then falls back to base data.
-struct OverlayData {
-    /// Overlay sources (shadows base sources when present).
-    sources: HashMap<StatId, Vec<Box<dyn StatSource>>>,
-
-    /// Overlay transforms (shadows base transforms when present).
-    transforms: HashMap<StatId, Vec<TransformEntry>>,
+impl<N: StatNumeric> Layer<N> {
+    fn new() -> Self {
+        Self {
+            sources: HashMap::new(),
+            transforms: HashMap::new(),
+            removed_sources: HashSet::new(),
+            removed_transforms: HashSet::new(),
+        }
+    }
+
+    fn has_source(&self, handle: &SourceHandle) -> bool {
+        self.sources
+            .get(&handle.stat)
+            .is_some_and(|slots| slots.iter().any(|slot| slot.id == handle.id))
+    }
+
+    /// Merge the layer directly above this one into it.
+    ///
+    /// Entries keep their relative order (this layer's entries first), and
+    /// removals either drop entries from this layer or stay as masks for
+    /// the layers below.
+    fn absorb(&mut self, upper: Layer<N>) {
+        for id in upper.removed_sources {
+            if !Self::remove_id(&mut self.sources, id) {
+                self.removed_sources.insert(id);
+            }
+        }
+        for id in upper.removed_transforms {
+            if !Self::remove_id(&mut self.transforms, id) {
+                self.removed_transforms.insert(id);
+            }
+        }
+        for (stat_id, slots) in upper.sources {
+            self.sources.entry(stat_id).or_default().extend(slots);
+        }
+        for (stat_id, slots) in upper.transforms {
+            self.transforms.entry(stat_id).or_default().extend(slots);
+        }
+    }
+
+    fn remove_id<T>(map: &mut HashMap<StatId, Vec<Slot<T>>>, id: u64) -> bool {
+        for slots in map.values_mut() {
+            if let Some(pos) = slots.iter().position(|slot| slot.id == id) {
+                slots.remove(pos);
+                return true;
+            }
+        }
+        false
+    }
 }
 
 /// Scope for stat resolution.
@@ -60,6 +199,10 @@ enum ResolveScope {
 /// 4. Resolves stats in topological order
 /// 5. Caches results until invalidated
 ///
+/// Cached stats whose sources or transforms declare context keys (see
+/// `StatTransform::context_keys`) are re-resolved automatically when a
+/// later call passes a `StatContext` with different values for those keys.
+///
 /// Supports copy-on-write forking for efficient resolver variations.
 ///
 /// # Examples
@@ -73,8 +216,8 @@ enum ResolveScope {
 /// let hp_id = StatId::from_str("HP");
 ///
 /// // Register sources
-/// resolver.register_source(hp_id.clone(), Box::new(ConstantSource(100.0)));
-/// resolver.register_source(hp_id.clone(), Box::new(ConstantSource(50.0)));
+/// resolver.register_source(hp_id.clone(), Box::new(ConstantSource::new(100.0)));
+/// resolver.register_source(hp_id.clone(), Box::new(ConstantSource::new(50.0)));
 ///
 /// // Register transform
 /// resolver.register_transform(hp_id.clone(), Box::new(MultiplicativeTransform::new(1.5)));
@@ -84,15 +227,38 @@ enum ResolveScope {
 /// let resolved = resolver.resolve(&hp_id, &context).unwrap();
 /// assert_eq!(resolved.value.to_f64(), 225.0); // (100 + 50) * 1.5
 /// ```
-pub struct StatResolver {
-    /// Shared base data (sources and transforms).
-    base: Arc<BaseData>,
+pub struct StatResolver<N: StatNumeric = StatValue> {
+    /// Layer chain, bottom (base data) first.
+    ///
+    /// The top layer receives modifications while it is uniquely owned;
+    /// once shared with a fork, a new layer is pushed instead.
+    layers: Vec<Arc<Layer<N>>>,
+
+    /// Dependency graph, kept in sync with registered transforms.
+    ///
+    /// Shared with forks until one of them changes it (copy-on-write).
+    graph: Arc<StatGraph>,
 
-    /// Copy-on-write overlay for modifications.
-    overlay: OverlayData,
+    /// Topological order of `graph`, computed on first use and kept until
+    /// the graph changes.
+    resolution_order: Option<Arc<Vec<StatId>>>,
 
     /// Cache of resolved stats (per-instance, not shared).
-    cache: HashMap<StatId, ResolvedStat>,
+    cache: HashMap<StatId, ResolvedStat<N>>,
+
+    /// Context values each cached stat was computed from.
+    ///
+    /// Only present for stats whose sources or transforms declare context keys.
+    context_fingerprints: HashMap<StatId, ContextFingerprint>,
+
+    /// Order transform phases are applied in (shared with forks).
+    phases: Arc<PhaseRegistry>,
+
+    /// Scale source values and phase results are rounded to, if pinned.
+    canonical_scale: Option<u8>,
+
+    /// Whether batch resolves spread dependency levels over threads.
+    parallel: bool,
 }
 
 impl StatResolver {
@@ -106,24 +272,157 @@ impl StatResolver {
     /// let resolver = StatResolver::new();
     /// ```
     pub fn new() -> Self {
-        Self {
-            base: Arc::new(BaseData {
-                sources: HashMap::new(),
-                transforms: HashMap::new(),
-            }),
-            overlay: OverlayData {
-                sources: HashMap::new(),
-                transforms: HashMap::new(),
-            },
-            cache: HashMap::new(),
+        Self::default()
+    }
+
+    /// Create a new empty resolver that applies phases in the order of
+    /// `phases`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use zzstat::{PhaseRegistry, StatResolver, TransformPhase};
+    ///
+    /// let phases = PhaseRegistry::from_phases([
+    ///     ("gear", TransformPhase::Custom(3)),
+    ///     ("cap", TransformPhase::Final),
+    /// ])
+    /// .unwrap();
+    /// let resolver = StatResolver::with_phases(phases);
+    /// ```
+    pub fn with_phases(phases: PhaseRegistry) -> Self {
+        let mut resolver = Self::new();
+        resolver.phases = Arc::new(phases);
+        resolver
+    }
+}
+
+impl<N: StatNumeric> StatResolver<N> {
+    /// Get the phase order this resolver applies.
+    pub fn phases(&self) -> &PhaseRegistry {
+        &self.phases
+    }
+
+    /// Replace the phase order.
+    ///
+    /// Every cached stat is invalidated, since reordering phases can
+    /// change any value.
+    pub fn set_phases(&mut self, phases: PhaseRegistry) {
+        self.phases = Arc::new(phases);
+        self.invalidate_all();
+    }
+
+    /// Get the pinned canonical scale, if any.
+    pub fn canonical_scale(&self) -> Option<u8> {
+        self.canonical_scale
+    }
+
+    /// Pin the scale every resolved value is rounded to, or unpin it with
+    /// `None`.
+    ///
+    /// Source values and the value after each phase are rounded (half
+    /// away from zero) to `scale` decimal places with
+    /// `StatNumeric::with_scale`, so a source or transform that works at a
+    /// finer scale can't change how later phases round. Backends without
+    /// a fixed scale, such as `f64`, are unaffected. Forks inherit the
+    /// setting, and every cached stat is invalidated.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - The scale was set
+    /// * `Err(StatError::InvalidNumber)` - If `scale` is greater than
+    ///   `FixedPoint::MAX_SCALE`; the resolver is left unchanged
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use zzstat::*;
+    ///
+    /// let mut resolver: StatResolver<FixedPoint> = StatResolver::default();
+    /// resolver.set_canonical_scale(Some(2)).unwrap();
+    /// let hp_id = StatId::from_str("HP");
+    /// resolver.register_source(hp_id.clone(), Box::new(ConstantSource::new(FixedPoint::new(1234567, 6))));
+    ///
+    /// let hp = resolver.resolve(&hp_id, &StatContext::new()).unwrap();
+    /// assert_eq!(hp.value, FixedPoint::new(123, 2));
+    /// assert_eq!(hp.value.scale(), 2);
+    ///
+    /// assert!(resolver.set_canonical_scale(Some(19)).is_err());
+    /// ```
+    pub fn set_canonical_scale(&mut self, scale: Option<u8>) -> Result<(), StatError> {
+        if let Some(scale) = scale {
+            if scale > FixedPoint::MAX_SCALE {
+                return Err(StatError::InvalidNumber(format!(
+                    "canonical scale {} exceeds the maximum of {}",
+                    scale,
+                    FixedPoint::MAX_SCALE
+                )));
+            }
+        }
+        self.canonical_scale = scale;
+        self.invalidate_all();
+        Ok(())
+    }
+
+    /// Check whether parallel batch resolution is enabled.
+    pub fn is_parallel(&self) -> bool {
+        self.parallel
+    }
+
+    /// Enable or disable parallel batch resolution.
+    ///
+    /// When enabled, `resolve_all` and `resolve_batch` group the stats to
+    /// resolve into dependency levels (see `StatGraph::levels`) and resolve
+    /// the stats of each level concurrently on scoped threads. Results are
+    /// identical to the sequential path: every stat is computed from the
+    /// same inputs and cached in a fixed order, and if any stat fails the
+    /// rest are resolved sequentially, so the same error is returned.
+    /// `resolve` always runs sequentially. Off by default; forks inherit
+    /// the setting.
+    ///
+    /// Worth it for entities with many independent derived stats; for small
+    /// stat sheets, spawning threads costs more than it saves.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use zzstat::*;
+    ///
+    /// let mut resolver = StatResolver::new();
+    /// resolver.set_parallel(true);
+    /// for name in ["HP", "MP", "ATK", "DEF"] {
+    ///     resolver.register_source(StatId::from_str(name), Box::new(ConstantSource::new(10.0)));
+    /// }
+    ///
+    /// let results = resolver.resolve_all(&StatContext::new()).unwrap();
+    /// assert_eq!(results.len(), 4);
+    /// ```
+    pub fn set_parallel(&mut self, parallel: bool) {
+        self.parallel = parallel;
+    }
+
+    /// Round a value to the canonical scale, if one is pinned.
+    fn canonicalize(&self, value: N) -> N {
+        match self.canonical_scale {
+            Some(scale) => value.with_scale(scale),
+            None => value,
         }
     }
 
-    /// Fork this resolver, creating a new resolver that shares base data.
+    /// Fork this resolver, creating a new resolver that shares its data.
+    ///
+    /// The fork stacks an empty overlay on top of this resolver's entire
+    /// layer chain, so forking a fork keeps everything the parent fork
+    /// added. Forking only clones `Arc`s (one per layer, plus the shared
+    /// dependency graph), and the fork starts with an empty cache.
     ///
-    /// The forked resolver starts with an empty overlay and cache.
     /// Modifications to the fork only affect the fork (copy-on-write).
-    /// The original resolver is unaffected.
+    /// The original resolver is unaffected, and modifications made to the
+    /// original after forking are not visible to the fork.
+    ///
+    /// Stats resolve deterministically at any depth: sources and transforms
+    /// are visited layer by layer from the base up, in registration order
+    /// within each layer.
     ///
     /// # Examples
     ///
@@ -134,13 +433,13 @@ impl StatResolver {
     ///
     /// let mut base = StatResolver::new();
     /// let hp_id = StatId::from_str("HP");
-    /// base.register_source(hp_id.clone(), Box::new(ConstantSource(100.0)));
+    /// base.register_source(hp_id.clone(), Box::new(ConstantSource::new(100.0)));
     ///
     /// // Fork the resolver
     /// let mut fork = base.fork();
     ///
     /// // Modify the fork (doesn't affect base)
-    /// fork.register_source(hp_id.clone(), Box::new(ConstantSource(50.0)));
+    /// fork.register_source(hp_id.clone(), Box::new(ConstantSource::new(50.0)));
     ///
     /// let context = StatContext::new();
     /// let base_resolved = base.resolve(&hp_id, &context).unwrap();
@@ -150,29 +449,138 @@ impl StatResolver {
     /// assert_eq!(fork_resolved.value.to_f64(), 150.0); // 100 + 50
     /// ```
     pub fn fork(&self) -> Self {
+        let mut layers = self.layers.clone();
+        layers.push(Arc::new(Layer::new()));
         Self {
-            base: Arc::clone(&self.base),
-            overlay: OverlayData {
-                sources: HashMap::new(),
-                transforms: HashMap::new(),
-            },
+            layers,
+            graph: Arc::clone(&self.graph),
+            resolution_order: self.resolution_order.clone(),
             cache: HashMap::new(),
+            context_fingerprints: HashMap::new(),
+            phases: Arc::clone(&self.phases),
+            canonical_scale: self.canonical_scale,
+            parallel: self.parallel,
+        }
+    }
+
+    /// Commit this fork's changes into another resolver.
+    ///
+    /// Every source and transform the fork added is moved into `parent`,
+    /// and every entry the fork removed is removed from `parent` as well.
+    /// Handles issued by the fork stay valid for the parent, so a committed
+    /// item can later be unequipped with `parent.remove_source(&handle)`.
+    ///
+    /// Only the layers the fork does not share with `parent` are committed.
+    /// Committing a nested fork into its grandparent therefore also carries
+    /// over what the intermediate fork added. Entries the parent has removed
+    /// in the meantime are ignored.
+    ///
+    /// Only the affected stats (and their dependents) are invalidated in
+    /// the parent; the rest of its cache is kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `parent` - The resolver to apply this fork's changes to
+    ///
+    /// # Returns
+    ///
+    /// The set of stats whose sources or transforms changed in `parent`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use zzstat::*;
+    /// use zzstat::source::ConstantSource;
+    ///
+    /// let mut base = StatResolver::new();
+    /// let hp_id = StatId::from_str("HP");
+    /// base.register_source(hp_id.clone(), Box::new(ConstantSource::new(100.0)));
+    ///
+    /// // Preview a gear change in a fork...
+    /// let mut preview = base.fork();
+    /// let armor = preview.register_source(hp_id.clone(), Box::new(ConstantSource::new(50.0)));
+    ///
+    /// // ...and keep it once the player confirms
+    /// let changed = preview.commit_into(&mut base);
+    /// assert!(changed.contains(&hp_id));
+    ///
+    /// let context = StatContext::new();
+    /// assert_eq!(base.resolve(&hp_id, &context).unwrap().value, 150.0);
+    ///
+    /// // The fork's handle now refers to the committed source
+    /// assert!(base.remove_source(&armor));
+    /// ```
+    pub fn commit_into(self, parent: &mut StatResolver<N>) -> HashSet<StatId> {
+        let shared = self
+            .layers
+            .iter()
+            .zip(&parent.layers)
+            .take_while(|(own, theirs)| Arc::ptr_eq(own, theirs))
+            .count();
+
+        let mut affected = HashSet::new();
+        for layer in self.layers.into_iter().skip(shared) {
+            let layer = Arc::try_unwrap(layer).unwrap_or_else(|shared| (*shared).clone());
+
+            // Removals refer to entries from lower layers, which have
+            // already been committed (or were inherited from `parent`)
+            for id in layer.removed_sources {
+                if let Some(handle) = parent.find_source_handle(id) {
+                    if parent.remove_source_entry(&handle) {
+                        affected.insert(handle.stat);
+                    }
+                }
+            }
+            for id in layer.removed_transforms {
+                if let Some(handle) = parent.find_transform_handle(id) {
+                    if parent.remove_transform_entry(&handle) {
+                        affected.insert(handle.stat);
+                    }
+                }
+            }
+
+            for (stat_id, slots) in layer.sources {
+                if !slots.is_empty() {
+                    parent.get_mut_sources(stat_id.clone()).extend(slots);
+                    parent.link_stat(&stat_id);
+                    affected.insert(stat_id);
+                }
+            }
+            for (stat_id, slots) in layer.transforms {
+                if !slots.is_empty() {
+                    for slot in &slots {
+                        parent.link_transform(&stat_id, &slot.item);
+                    }
+                    parent.get_mut_transforms(stat_id.clone()).extend(slots);
+                    affected.insert(stat_id);
+                }
+            }
         }
+
+        for stat_id in &affected {
+            parent.invalidate_with_dependents(stat_id);
+        }
+        affected
     }
 
     /// Register a source for a stat.
     ///
     /// Multiple sources for the same stat are summed (additive).
-    /// Registering a source automatically invalidates the cache for that stat.
+    /// Registering a source automatically invalidates the cache for that stat
+    /// and every stat that depends on it.
     ///
-    /// Uses copy-on-write semantics: if this resolver is a fork, the source
-    /// is added to the overlay. Otherwise, it's added to the base data.
+    /// Uses copy-on-write semantics: the source is added to this resolver's
+    /// top layer, which is never shared with a parent or a fork.
     ///
     /// # Arguments
     ///
     /// * `stat_id` - The stat to register a source for
     /// * `source` - The source to register
     ///
+    /// # Returns
+    ///
+    /// A [`SourceHandle`] that can be passed to [`remove_source`](Self::remove_source).
+    ///
     /// # Examples
     ///
     /// ```rust
@@ -182,23 +590,33 @@ impl StatResolver {
     /// let mut resolver = StatResolver::new();
     /// let hp_id = StatId::from_str("HP");
     ///
-    /// resolver.register_source(hp_id.clone(), Box::new(ConstantSource(100.0)));
-    /// resolver.register_source(hp_id.clone(), Box::new(ConstantSource(50.0)));
+    /// resolver.register_source(hp_id.clone(), Box::new(ConstantSource::new(100.0)));
+    /// resolver.register_source(hp_id.clone(), Box::new(ConstantSource::new(50.0)));
     /// // HP will be 150.0 (100 + 50)
     /// ```
-    pub fn register_source(&mut self, stat_id: StatId, source: Box<dyn StatSource>) {
-        let stat_id_clone = stat_id.clone();
+    pub fn register_source(
+        &mut self,
+        stat_id: StatId,
+        source: Box<dyn StatSource<N>>,
+    ) -> SourceHandle {
+        let id = next_handle_id();
         // Use copy-on-write helper to get the appropriate sources vector
-        self.get_mut_sources(stat_id).push(source);
-        // Invalidate cache for this stat
-        self.cache.remove(&stat_id_clone);
+        self.get_mut_sources(stat_id.clone()).push(Slot {
+            id,
+            item: Arc::from(source),
+        });
+        self.link_stat(&stat_id);
+        // Invalidate cache for this stat and its dependents
+        self.invalidate_with_dependents(&stat_id);
+        SourceHandle { stat: stat_id, id }
     }
 
     /// Register a transform for a stat.
     ///
     /// Transforms are applied in registration order within each phase.
     /// The stack rule is inferred from the transform's phase.
-    /// Registering a transform automatically invalidates the cache for that stat.
+    /// Registering a transform automatically invalidates the cache for that stat
+    /// and every stat that depends on it.
     ///
     /// # Arguments
     ///
@@ -215,11 +633,15 @@ impl StatResolver {
     /// let mut resolver = StatResolver::new();
     /// let atk_id = StatId::from_str("ATK");
     ///
-    /// resolver.register_source(atk_id.clone(), Box::new(ConstantSource(100.0)));
+    /// resolver.register_source(atk_id.clone(), Box::new(ConstantSource::new(100.0)));
     /// resolver.register_transform(atk_id.clone(), Box::new(MultiplicativeTransform::new(1.5)));
     /// // ATK will be 150.0 (100 * 1.5)
     /// ```
-    pub fn register_transform(&mut self, stat_id: StatId, transform: Box<dyn StatTransform>) {
+    pub fn register_transform(
+        &mut self,
+        stat_id: StatId,
+        transform: Box<dyn StatTransform<N>>,
+    ) -> TransformHandle {
         let phase = transform.phase();
         let rule = crate::transform::infer_stack_rule(transform.as_ref());
         let entry = TransformEntry {
@@ -227,12 +649,14 @@ impl StatResolver {
             rule,
             transform,
         };
-        self.register_transform_entry(stat_id, entry);
+        self.register_transform_entry(stat_id, entry)
     }
 
     /// Register a transform with explicit phase and inferred stack rule.
     ///
-    /// The stack rule is inferred from the transform's type and phase.
+    /// The stack rule is inferred from `phase` (see
+    /// `infer_stack_rule_in_phase`): a transform registered in the
+    /// `Additive` phase stacks additively whatever phase it declares.
     /// This is a convenience method for registering transforms in a specific phase.
     ///
     /// # Arguments
@@ -251,7 +675,7 @@ impl StatResolver {
     /// let mut resolver = StatResolver::new();
     /// let atk_id = StatId::from_str("ATK");
     ///
-    /// resolver.register_source(atk_id.clone(), Box::new(ConstantSource(100.0)));
+    /// resolver.register_source(atk_id.clone(), Box::new(ConstantSource::new(100.0)));
     /// resolver.register_transform_in_phase(
     ///     atk_id.clone(),
     ///     TransformPhase::Additive,
@@ -262,15 +686,15 @@ impl StatResolver {
         &mut self,
         stat_id: StatId,
         phase: TransformPhase,
-        transform: Box<dyn StatTransform>,
-    ) {
-        let rule = crate::transform::infer_stack_rule(transform.as_ref());
+        transform: Box<dyn StatTransform<N>>,
+    ) -> TransformHandle {
+        let rule = crate::transform::infer_stack_rule_in_phase(transform.as_ref(), phase);
         let entry = TransformEntry {
             phase,
             rule,
             transform,
         };
-        self.register_transform_entry(stat_id, entry);
+        self.register_transform_entry(stat_id, entry)
     }
 
     /// Register a transform with explicit phase and stack rule.
@@ -295,7 +719,7 @@ impl StatResolver {
     /// let mut resolver = StatResolver::new();
     /// let atk_id = StatId::from_str("ATK");
     ///
-    /// resolver.register_source(atk_id.clone(), Box::new(ConstantSource(100.0)));
+    /// resolver.register_source(atk_id.clone(), Box::new(ConstantSource::new(100.0)));
     /// resolver.register_transform_with_rule(
     ///     atk_id.clone(),
     ///     TransformPhase::Additive,
@@ -308,90 +732,146 @@ impl StatResolver {
         stat_id: StatId,
         phase: TransformPhase,
         rule: StackRule,
-        transform: Box<dyn StatTransform>,
-    ) {
+        transform: Box<dyn StatTransform<N>>,
+    ) -> TransformHandle {
         let entry = TransformEntry {
             phase,
             rule,
             transform,
         };
-        self.register_transform_entry(stat_id, entry);
+        self.register_transform_entry(stat_id, entry)
     }
 
-    /// Internal method to register a transform entry.
-    ///
-    /// Uses copy-on-write semantics: if this resolver is a fork, the transform
-    /// is added to the overlay. Otherwise, it's added to the base data.
-    fn register_transform_entry(&mut self, stat_id: StatId, entry: TransformEntry) {
-        let stat_id_clone = stat_id.clone();
-        // Use copy-on-write helper to get the appropriate transforms vector
-        self.get_mut_transforms(stat_id).push(entry);
-        // Invalidate cache for this stat and potentially dependent stats
-        self.cache.remove(&stat_id_clone);
-    }
-
-    /// Resolve a single stat.
-    ///
-    /// This will resolve the requested stat and all of its dependencies
-    /// in the correct order. Results are cached until invalidated.
+    /// Register a transform, rejecting it if it would create a cycle.
     ///
-    /// This method shares its core implementation with `resolve_all()` and
-    /// `resolve_batch()` via the internal `resolve_internal()` method.
+    /// Like `register_transform`, but checks the transform's `depends_on()`
+    /// against the current dependency graph first. Use this when loading
+    /// content, so a bad definition is reported where it was registered
+    /// instead of on the next `resolve` call.
     ///
     /// # Arguments
     ///
-    /// * `stat_id` - The stat to resolve
-    /// * `context` - The stat context for conditional calculations
+    /// * `stat_id` - The stat to register a transform for
+    /// * `transform` - The transform to register
     ///
     /// # Returns
     ///
-    /// * `Ok(ResolvedStat)` - The resolved stat with full breakdown
-    /// * `Err(StatError)` - If resolution fails (cycle, missing dependency, etc.)
+    /// * `Ok(TransformHandle)` - The transform was registered
+    /// * `Err(StatError::Cycle)` - The transform would close a cycle; the
+    ///   path lists each stat followed by the stat it depends on, starting
+    ///   and ending with `stat_id`. The resolver is left unchanged.
     ///
     /// # Examples
     ///
     /// ```rust
     /// use zzstat::*;
-    /// use zzstat::source::ConstantSource;
+    /// use zzstat::transform::ScalingTransform;
     ///
     /// let mut resolver = StatResolver::new();
-    /// let hp_id = StatId::from_str("HP");
+    /// let atk_id = StatId::from_str("ATK");
+    /// let str_id = StatId::from_str("STR");
     ///
-    /// resolver.register_source(hp_id.clone(), Box::new(ConstantSource(100.0)));
+    /// // ATK depends on STR
+    /// resolver
+    ///     .try_register_transform(atk_id.clone(), Box::new(ScalingTransform::new(str_id.clone(), 2.0)))
+    ///     .unwrap();
     ///
-    /// let context = StatContext::new();
-    /// let resolved = resolver.resolve(&hp_id, &context).unwrap();
-    /// assert_eq!(resolved.value, 100.0);
+    /// // STR depending on ATK would close the loop
+    /// let err = resolver
+    ///     .try_register_transform(str_id.clone(), Box::new(ScalingTransform::new(atk_id.clone(), 0.1)))
+    ///     .unwrap_err();
+    /// assert_eq!(err, StatError::Cycle { path: vec![str_id.clone(), atk_id, str_id] });
     /// ```
-    pub fn resolve(
+    pub fn try_register_transform(
         &mut self,
-        stat_id: &StatId,
-        context: &StatContext,
-    ) -> Result<ResolvedStat, StatError> {
-        let results = self.resolve_internal(ResolveScope::Single(stat_id.clone()), context)?;
-        results
-            .into_iter()
-            .next()
-            .map(|(_, resolved)| resolved)
-            .ok_or_else(|| StatError::MissingSource(stat_id.clone()))
+        stat_id: StatId,
+        transform: Box<dyn StatTransform<N>>,
+    ) -> Result<TransformHandle, StatError> {
+        let phase = transform.phase();
+        let rule = crate::transform::infer_stack_rule(transform.as_ref());
+        self.try_register_transform_with_rule(stat_id, phase, rule, transform)
     }
 
-    /// Resolve all registered stats.
+    /// Register a transform with an explicit phase and stack rule, rejecting
+    /// it if it would create a cycle.
     ///
-    /// Resolves all stats that have been registered (have sources or transforms).
-    /// Stats are resolved in dependency order, and results are cached.
+    /// See `try_register_transform` for how cycles are reported.
     ///
-    /// This method shares its core implementation with `resolve()` and
-    /// `resolve_batch()` via the internal `resolve_internal()` method.
+    /// # Arguments
+    ///
+    /// * `stat_id` - The stat to register a transform for
+    /// * `phase` - The phase this transform belongs to
+    /// * `rule` - The stack rule for combining this transform with others
+    /// * `transform` - The transform to register
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(TransformHandle)` - The transform was registered
+    /// * `Err(StatError::Cycle)` - The transform would close a cycle; the
+    ///   resolver is left unchanged
+    /// * `Err(StatError::InvalidCurve)` - The rule's diminishing returns
+    ///   curve is invalid (see `StackRule::validate`)
+    pub fn try_register_transform_with_rule(
+        &mut self,
+        stat_id: StatId,
+        phase: TransformPhase,
+        rule: StackRule,
+        transform: Box<dyn StatTransform<N>>,
+    ) -> Result<TransformHandle, StatError> {
+        rule.validate()?;
+        for dep in transform.depends_on() {
+            // The new edge makes stat_id depend on dep; that closes a cycle
+            // if dep already depends on stat_id
+            let path = if dep == stat_id {
+                Some(vec![dep])
+            } else {
+                self.graph.dependency_path(&dep, &stat_id)
+            };
+            if let Some(path) = path {
+                let mut cycle = vec![stat_id];
+                cycle.extend(path);
+                return Err(StatError::Cycle { path: cycle });
+            }
+        }
+        Ok(self.register_transform_with_rule(stat_id, phase, rule, transform))
+    }
+
+    /// Internal method to register a transform entry.
+    ///
+    /// Uses copy-on-write semantics: the transform is added to this resolver's
+    /// top layer, which is never shared with a parent or a fork.
+    fn register_transform_entry(
+        &mut self,
+        stat_id: StatId,
+        entry: TransformEntry<N>,
+    ) -> TransformHandle {
+        let id = next_handle_id();
+        self.link_transform(&stat_id, &entry);
+        // Use copy-on-write helper to get the appropriate transforms vector
+        self.get_mut_transforms(stat_id.clone()).push(Slot {
+            id,
+            item: Arc::new(entry),
+        });
+        // Invalidate cache for this stat and its dependents
+        self.invalidate_with_dependents(&stat_id);
+        TransformHandle { stat: stat_id, id }
+    }
+
+    /// Remove a previously registered source.
+    ///
+    /// Works for sources registered in this resolver as well as sources
+    /// inherited from a parent. Removing an inherited source from a fork only
+    /// hides it in that fork; the parent and sibling forks are unaffected.
+    /// Removing a source invalidates the cache for its stat and its dependents.
     ///
     /// # Arguments
     ///
-    /// * `context` - The stat context for conditional calculations
+    /// * `handle` - The handle returned by `register_source`
     ///
     /// # Returns
     ///
-    /// * `Ok(HashMap<StatId, ResolvedStat>)` - Map of all resolved stats
-    /// * `Err(StatError)` - If resolution fails (cycle, missing dependency, etc.)
+    /// `true` if the source was removed, `false` if it was not registered
+    /// (or has already been removed).
     ///
     /// # Examples
     ///
@@ -400,24 +880,260 @@ impl StatResolver {
     /// use zzstat::source::ConstantSource;
     ///
     /// let mut resolver = StatResolver::new();
-    /// resolver.register_source(StatId::from_str("HP"), Box::new(ConstantSource(100.0)));
-    /// resolver.register_source(StatId::from_str("MP"), Box::new(ConstantSource(50.0)));
+    /// let hp_id = StatId::from_str("HP");
+    ///
+    /// resolver.register_source(hp_id.clone(), Box::new(ConstantSource::new(100.0)));
+    /// let item = resolver.register_source(hp_id.clone(), Box::new(ConstantSource::new(50.0)));
     ///
     /// let context = StatContext::new();
-    /// let results = resolver.resolve_all(&context).unwrap();
-    /// assert_eq!(results.len(), 2);
+    /// assert_eq!(resolver.resolve(&hp_id, &context).unwrap().value, 150.0);
+    ///
+    /// // Unequip the item
+    /// assert!(resolver.remove_source(&item));
+    /// assert_eq!(resolver.resolve(&hp_id, &context).unwrap().value, 100.0);
     /// ```
-    pub fn resolve_all(
-        &mut self,
-        context: &StatContext,
-    ) -> Result<HashMap<StatId, ResolvedStat>, StatError> {
-        self.resolve_internal(ResolveScope::All, context)
+    pub fn remove_source(&mut self, handle: &SourceHandle) -> bool {
+        let removed = self.remove_source_entry(handle);
+        if removed {
+            self.invalidate_with_dependents(&handle.stat);
+        }
+        removed
     }
 
-    /// Resolve multiple target stats and their dependencies in a single batch.
+    /// Remove a previously registered transform.
     ///
-    /// More efficient than calling `resolve()` multiple times, as it only resolves
-    /// the dependency subgraph needed for the specified targets.
+    /// Works for transforms registered in this resolver as well as transforms
+    /// inherited from a parent. Removing an inherited transform from a fork
+    /// only hides it in that fork; the parent and sibling forks are unaffected.
+    /// Removing a transform invalidates the cache for its stat and its
+    /// dependents.
+    ///
+    /// # Arguments
+    ///
+    /// * `handle` - The handle returned by one of the `register_transform*` methods
+    ///
+    /// # Returns
+    ///
+    /// `true` if the transform was removed, `false` if it was not registered
+    /// (or has already been removed).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use zzstat::*;
+    /// use zzstat::source::ConstantSource;
+    /// use zzstat::transform::MultiplicativeTransform;
+    ///
+    /// let mut resolver = StatResolver::new();
+    /// let atk_id = StatId::from_str("ATK");
+    ///
+    /// resolver.register_source(atk_id.clone(), Box::new(ConstantSource::new(100.0)));
+    /// let buff = resolver.register_transform(
+    ///     atk_id.clone(),
+    ///     Box::new(MultiplicativeTransform::new(1.5)),
+    /// );
+    ///
+    /// let context = StatContext::new();
+    /// assert_eq!(resolver.resolve(&atk_id, &context).unwrap().value, 150.0);
+    ///
+    /// // Buff expires
+    /// assert!(resolver.remove_transform(&buff));
+    /// assert_eq!(resolver.resolve(&atk_id, &context).unwrap().value, 100.0);
+    /// ```
+    pub fn remove_transform(&mut self, handle: &TransformHandle) -> bool {
+        let removed = self.remove_transform_entry(handle);
+        if removed {
+            self.invalidate_with_dependents(&handle.stat);
+        }
+        removed
+    }
+
+    /// Remove a source without touching the cache.
+    fn remove_source_entry(&mut self, handle: &SourceHandle) -> bool {
+        let visible = self.layers.iter().any(|layer| layer.has_source(handle))
+            && !self.is_source_removed(handle.id);
+        if !visible {
+            return false;
+        }
+        let top = self.top_layer_mut();
+        if !Self::remove_slot(&mut top.sources, &handle.stat, handle.id) {
+            // Inherited from a shared layer: hide it instead
+            top.removed_sources.insert(handle.id);
+        }
+        true
+    }
+
+    /// Remove a transform without touching the cache.
+    fn remove_transform_entry(&mut self, handle: &TransformHandle) -> bool {
+        if self.is_transform_removed(handle.id) {
+            return false;
+        }
+        let Some(entry) = self.layers.iter().find_map(|layer| {
+            layer.transforms.get(&handle.stat).and_then(|slots| {
+                slots
+                    .iter()
+                    .find(|slot| slot.id == handle.id)
+                    .map(|slot| Arc::clone(&slot.item))
+            })
+        }) else {
+            return false;
+        };
+        let top = self.top_layer_mut();
+        if !Self::remove_slot(&mut top.transforms, &handle.stat, handle.id) {
+            // Inherited from a shared layer: hide it instead
+            top.removed_transforms.insert(handle.id);
+        }
+        self.unlink_transform(&handle.stat, &entry);
+        true
+    }
+
+    /// Add a stat to the dependency graph.
+    fn link_stat(&mut self, stat_id: &StatId) {
+        if !self.graph.contains_node(stat_id) {
+            Arc::make_mut(&mut self.graph).add_node(stat_id.clone());
+            self.resolution_order = None;
+        }
+    }
+
+    /// Add a transform's dependencies to the dependency graph.
+    fn link_transform(&mut self, stat_id: &StatId, entry: &TransformEntry<N>) {
+        let graph = Arc::make_mut(&mut self.graph);
+        graph.add_node(stat_id.clone());
+        for dep in entry.transform.depends_on() {
+            // dep must be resolved before stat_id
+            graph.add_edge(stat_id.clone(), dep);
+        }
+        self.resolution_order = None;
+    }
+
+    /// Remove a transform's dependencies from the dependency graph.
+    ///
+    /// Dependencies that are left without edges, sources, or transforms
+    /// are dropped from the graph entirely.
+    fn unlink_transform(&mut self, stat_id: &StatId, entry: &TransformEntry<N>) {
+        let deps = entry.transform.depends_on();
+        if deps.is_empty() {
+            return;
+        }
+        for dep in &deps {
+            Arc::make_mut(&mut self.graph).remove_edge(stat_id, dep);
+        }
+        for dep in &deps {
+            let registered = self
+                .layers
+                .iter()
+                .any(|layer| layer.sources.contains_key(dep) || layer.transforms.contains_key(dep));
+            if !registered && !self.graph.has_edges(dep) {
+                Arc::make_mut(&mut self.graph).remove_node(dep);
+            }
+        }
+        self.resolution_order = None;
+    }
+
+    /// Remove the slot with the given id from a per-stat map.
+    fn remove_slot<T>(map: &mut HashMap<StatId, Vec<Slot<T>>>, stat_id: &StatId, id: u64) -> bool {
+        let Some(slots) = map.get_mut(stat_id) else {
+            return false;
+        };
+        match slots.iter().position(|slot| slot.id == id) {
+            Some(pos) => {
+                slots.remove(pos);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Resolve a single stat.
+    ///
+    /// This will resolve the requested stat and all of its dependencies
+    /// in the correct order. Results are cached until invalidated.
+    /// Stats the requested stat doesn't (transitively) depend on are not
+    /// visited, so resolving one stat stays cheap on large stat sheets.
+    ///
+    /// This method shares its core implementation with `resolve_all()` and
+    /// `resolve_batch()` via the internal `resolve_internal()` method.
+    ///
+    /// # Arguments
+    ///
+    /// * `stat_id` - The stat to resolve
+    /// * `context` - The stat context for conditional calculations
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ResolvedStat)` - The resolved stat with full breakdown
+    /// * `Err(StatError)` - If resolution fails (cycle, missing dependency, etc.)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use zzstat::*;
+    /// use zzstat::source::ConstantSource;
+    ///
+    /// let mut resolver = StatResolver::new();
+    /// let hp_id = StatId::from_str("HP");
+    ///
+    /// resolver.register_source(hp_id.clone(), Box::new(ConstantSource::new(100.0)));
+    ///
+    /// let context = StatContext::new();
+    /// let resolved = resolver.resolve(&hp_id, &context).unwrap();
+    /// assert_eq!(resolved.value, 100.0);
+    /// ```
+    pub fn resolve(
+        &mut self,
+        stat_id: &StatId,
+        context: &StatContext,
+    ) -> Result<ResolvedStat<N>, StatError> {
+        let results = self.resolve_internal(ResolveScope::Single(stat_id.clone()), context)?;
+        results
+            .into_iter()
+            .next()
+            .map(|(_, resolved)| resolved)
+            .ok_or_else(|| StatError::MissingSource(stat_id.clone()))
+    }
+
+    /// Resolve all registered stats.
+    ///
+    /// Resolves all stats that have been registered (have sources or transforms).
+    /// Stats are resolved in dependency order, and results are cached.
+    ///
+    /// This method shares its core implementation with `resolve()` and
+    /// `resolve_batch()` via the internal `resolve_internal()` method.
+    ///
+    /// # Arguments
+    ///
+    /// * `context` - The stat context for conditional calculations
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(HashMap<StatId, ResolvedStat<N>>)` - Map of all resolved stats
+    /// * `Err(StatError)` - If resolution fails (cycle, missing dependency, etc.)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use zzstat::*;
+    /// use zzstat::source::ConstantSource;
+    ///
+    /// let mut resolver = StatResolver::new();
+    /// resolver.register_source(StatId::from_str("HP"), Box::new(ConstantSource::new(100.0)));
+    /// resolver.register_source(StatId::from_str("MP"), Box::new(ConstantSource::new(50.0)));
+    ///
+    /// let context = StatContext::new();
+    /// let results = resolver.resolve_all(&context).unwrap();
+    /// assert_eq!(results.len(), 2);
+    /// ```
+    pub fn resolve_all(
+        &mut self,
+        context: &StatContext,
+    ) -> Result<HashMap<StatId, ResolvedStat<N>>, StatError> {
+        self.resolve_internal(ResolveScope::All, context)
+    }
+
+    /// Resolve multiple target stats and their dependencies in a single batch.
+    ///
+    /// More efficient than calling `resolve()` multiple times, as it only resolves
+    /// the dependency subgraph needed for the specified targets.
     ///
     /// This method shares its core implementation with `resolve()` and
     /// `resolve_all()` via the internal `resolve_internal()` method.
@@ -429,7 +1145,7 @@ impl StatResolver {
     ///
     /// # Returns
     ///
-    /// * `Ok(HashMap<StatId, ResolvedStat>)` - Map of resolved stats (targets and dependencies)
+    /// * `Ok(HashMap<StatId, ResolvedStat<N>>)` - Map of resolved stats (targets and dependencies)
     /// * `Err(StatError)` - If resolution fails (cycle, missing dependency, etc.)
     ///
     /// # Examples
@@ -443,9 +1159,9 @@ impl StatResolver {
     /// let atk_id = StatId::from_str("ATK");
     /// let hp_id = StatId::from_str("HP");
     ///
-    /// resolver.register_source(str_id.clone(), Box::new(ConstantSource(10.0)));
-    /// resolver.register_source(atk_id.clone(), Box::new(ConstantSource(50.0)));
-    /// resolver.register_source(hp_id.clone(), Box::new(ConstantSource(100.0)));
+    /// resolver.register_source(str_id.clone(), Box::new(ConstantSource::new(10.0)));
+    /// resolver.register_source(atk_id.clone(), Box::new(ConstantSource::new(50.0)));
+    /// resolver.register_source(hp_id.clone(), Box::new(ConstantSource::new(100.0)));
     ///
     /// let context = StatContext::new();
     /// let results = resolver.resolve_batch(&[atk_id.clone(), hp_id.clone()], &context)?;
@@ -458,14 +1174,16 @@ impl StatResolver {
         &mut self,
         targets: &[StatId],
         context: &StatContext,
-    ) -> Result<HashMap<StatId, ResolvedStat>, StatError> {
+    ) -> Result<HashMap<StatId, ResolvedStat<N>>, StatError> {
         self.resolve_internal(ResolveScope::Batch(targets.to_vec()), context)
     }
 
     /// Invalidate the cache for a specific stat.
     ///
     /// The next time this stat is resolved, it will be recalculated
-    /// instead of using the cached value.
+    /// instead of using the cached value. Every stat that (transitively)
+    /// depends on it is invalidated as well, so no cached value computed
+    /// from the old value survives.
     ///
     /// # Arguments
     ///
@@ -480,16 +1198,136 @@ impl StatResolver {
     /// let mut resolver = StatResolver::new();
     /// let hp_id = StatId::from_str("HP");
     ///
-    /// resolver.register_source(hp_id.clone(), Box::new(ConstantSource(100.0)));
+    /// resolver.register_source(hp_id.clone(), Box::new(ConstantSource::new(100.0)));
     /// let context = StatContext::new();
     /// let _ = resolver.resolve(&hp_id, &context).unwrap();
     ///
     /// // Invalidate and add new source
     /// resolver.invalidate(&hp_id);
-    /// resolver.register_source(hp_id.clone(), Box::new(ConstantSource(50.0)));
+    /// resolver.register_source(hp_id.clone(), Box::new(ConstantSource::new(50.0)));
     /// ```
     pub fn invalidate(&mut self, stat_id: &StatId) {
-        self.cache.remove(stat_id);
+        self.invalidate_with_dependents(stat_id);
+    }
+
+    /// Get every stat that depends on a stat, directly or transitively.
+    ///
+    /// These are the stats whose values can change when `stat_id` changes.
+    /// The stat itself is not included.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use zzstat::*;
+    /// use zzstat::transform::ScalingTransform;
+    ///
+    /// let mut resolver = StatResolver::new();
+    /// let str_id = StatId::from_str("STR");
+    /// let atk_id = StatId::from_str("ATK");
+    /// let dps_id = StatId::from_str("DPS");
+    ///
+    /// resolver.register_transform(atk_id.clone(), Box::new(ScalingTransform::new(str_id.clone(), 2.0)));
+    /// resolver.register_transform(dps_id.clone(), Box::new(ScalingTransform::new(atk_id.clone(), 1.0)));
+    ///
+    /// let dependents = resolver.dependents_of(&str_id);
+    /// assert!(dependents.contains(&atk_id) && dependents.contains(&dps_id));
+    /// ```
+    pub fn dependents_of(&self, stat_id: &StatId) -> HashSet<StatId> {
+        self.graph.dependents_of(stat_id)
+    }
+
+    /// Invalidate a stat and all stats that transitively depend on it.
+    ///
+    /// Walks the reverse edges of the dependency graph starting at `stat_id`
+    /// and evicts every cached stat it reaches.
+    ///
+    /// # Arguments
+    ///
+    /// * `stat_id` - The stat whose value changed
+    ///
+    /// # Returns
+    ///
+    /// The set of stats that were evicted from the cache (including
+    /// `stat_id` itself if it was cached). Stats that were not cached are
+    /// not included, so this is exactly the set of values game code may
+    /// need to re-sync to clients.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use zzstat::*;
+    /// use zzstat::source::ConstantSource;
+    /// use zzstat::transform::ScalingTransform;
+    ///
+    /// let mut resolver = StatResolver::new();
+    /// let str_id = StatId::from_str("STR");
+    /// let atk_id = StatId::from_str("ATK");
+    ///
+    /// resolver.register_source(str_id.clone(), Box::new(ConstantSource::new(10.0)));
+    /// resolver.register_transform(atk_id.clone(), Box::new(ScalingTransform::new(str_id.clone(), 2.0)));
+    ///
+    /// let context = StatContext::new();
+    /// resolver.resolve(&atk_id, &context).unwrap();
+    ///
+    /// let evicted = resolver.invalidate_with_dependents(&str_id);
+    /// assert!(evicted.contains(&str_id));
+    /// assert!(evicted.contains(&atk_id));
+    /// ```
+    pub fn invalidate_with_dependents(&mut self, stat_id: &StatId) -> HashSet<StatId> {
+        let mut evicted = HashSet::new();
+        // Walk the dependents even when this stat isn't cached: it may have
+        // been evicted on its own while stats computed from it stayed cached.
+        if self.evict(stat_id) {
+            evicted.insert(stat_id.clone());
+        }
+
+        for dependent in self.graph.dependents_of(stat_id) {
+            if self.evict(&dependent) {
+                evicted.insert(dependent);
+            }
+        }
+        evicted
+    }
+
+    /// Remove a single stat from the cache.
+    ///
+    /// Returns `true` if the stat was cached.
+    fn evict(&mut self, stat_id: &StatId) -> bool {
+        self.context_fingerprints.remove(stat_id);
+        self.cache.remove(stat_id).is_some()
+    }
+
+    /// Invalidate cached stats that were computed under a different context.
+    ///
+    /// Compares the recorded values of each stat's declared context keys
+    /// against `context` and cascades invalidation from every mismatch.
+    fn invalidate_stale_context(&mut self, context: &StatContext) {
+        let mut stale: Vec<StatId> = self
+            .context_fingerprints
+            .iter()
+            .filter(|(_, fingerprint)| !fingerprint.matches(context))
+            .map(|(stat_id, _)| stat_id.clone())
+            .collect();
+        // Sort for a deterministic invalidation order
+        stale.sort();
+        for stat_id in stale {
+            self.invalidate_with_dependents(&stat_id);
+        }
+    }
+
+    /// Collect the context keys read by a stat's sources and transforms.
+    fn context_keys_for(&self, stat_id: &StatId) -> Vec<String> {
+        let mut keys: Vec<String> = self
+            .get_sources(stat_id)
+            .flat_map(|source| source.context_keys())
+            .chain(
+                self.get_transforms(stat_id)
+                    .flat_map(|entry| entry.transform.context_keys()),
+            )
+            .collect();
+        keys.sort();
+        keys.dedup();
+        keys
     }
 
     /// Invalidate the entire cache.
@@ -503,7 +1341,7 @@ impl StatResolver {
     /// use zzstat::source::ConstantSource;
     ///
     /// let mut resolver = StatResolver::new();
-    /// resolver.register_source(StatId::from_str("HP"), Box::new(ConstantSource(100.0)));
+    /// resolver.register_source(StatId::from_str("HP"), Box::new(ConstantSource::new(100.0)));
     ///
     /// let context = StatContext::new();
     /// let _ = resolver.resolve_all(&context).unwrap();
@@ -513,11 +1351,12 @@ impl StatResolver {
     /// ```
     pub fn invalidate_all(&mut self) {
         self.cache.clear();
+        self.context_fingerprints.clear();
     }
 
     /// Get the breakdown for a stat (if it's been resolved).
     ///
-    /// Returns the cached `ResolvedStat` if it exists, or `None` if
+    /// Returns the cached `ResolvedStat<N>` if it exists, or `None` if
     /// the stat hasn't been resolved yet.
     ///
     /// # Arguments
@@ -538,7 +1377,7 @@ impl StatResolver {
     /// let mut resolver = StatResolver::new();
     /// let hp_id = StatId::from_str("HP");
     ///
-    /// resolver.register_source(hp_id.clone(), Box::new(ConstantSource(100.0)));
+    /// resolver.register_source(hp_id.clone(), Box::new(ConstantSource::new(100.0)));
     /// let context = StatContext::new();
     ///
     /// // Not resolved yet
@@ -551,88 +1390,134 @@ impl StatResolver {
     /// let breakdown = resolver.get_breakdown(&hp_id).unwrap();
     /// assert_eq!(breakdown.value, 100.0);
     /// ```
-    pub fn get_breakdown(&self, stat_id: &StatId) -> Option<&ResolvedStat> {
+    pub fn get_breakdown(&self, stat_id: &StatId) -> Option<&ResolvedStat<N>> {
         self.cache.get(stat_id)
     }
 
-    /// Get sources for a stat (checking overlay first, then base).
+    /// Get sources for a stat, layer by layer from the base up.
     ///
-    /// Overlay completely shadows base - if overlay has sources for this stat,
-    /// only overlay sources are returned. Otherwise, base sources are returned.
-    #[allow(dead_code)]
-    fn get_sources(&self, stat_id: &StatId) -> Option<&Vec<Box<dyn StatSource>>> {
-        self.overlay
-            .sources
-            .get(stat_id)
-            .or_else(|| self.base.sources.get(stat_id))
+    /// Sources from upper layers are added to those below; sources removed
+    /// in any layer are skipped.
+    fn get_sources<'a>(
+        &'a self,
+        stat_id: &StatId,
+    ) -> impl Iterator<Item = &'a dyn StatSource<N>> + 'a {
+        let stat_id = stat_id.clone();
+        self.layers
+            .iter()
+            .flat_map(move |layer| layer.sources.get(&stat_id).into_iter().flatten())
+            .filter(move |slot| !self.is_source_removed(slot.id))
+            .map(|slot| &*slot.item)
     }
 
-    /// Get transforms for a stat (checking overlay first, then base).
+    /// Get transforms for a stat, layer by layer from the base up.
     ///
-    /// Overlay completely shadows base - if overlay has transforms for this stat,
-    /// only overlay transforms are returned. Otherwise, base transforms are returned.
-    fn get_transforms(&self, stat_id: &StatId) -> Option<&Vec<TransformEntry>> {
-        self.overlay
-            .transforms
-            .get(stat_id)
-            .or_else(|| self.base.transforms.get(stat_id))
+    /// Transforms from upper layers are added to those below; transforms
+    /// removed in any layer are skipped.
+    fn get_transforms<'a>(
+        &'a self,
+        stat_id: &StatId,
+    ) -> impl Iterator<Item = &'a TransformEntry<N>> + 'a {
+        let stat_id = stat_id.clone();
+        self.layers
+            .iter()
+            .flat_map(move |layer| layer.transforms.get(&stat_id).into_iter().flatten())
+            .filter(move |slot| !self.is_transform_removed(slot.id))
+            .map(|slot| &*slot.item)
     }
 
-    /// Get all stat IDs that have sources or transforms.
-    fn get_all_stat_ids(&self) -> std::collections::HashSet<StatId> {
-        let mut ids = std::collections::HashSet::new();
-        ids.extend(self.base.sources.keys().cloned());
-        ids.extend(self.base.transforms.keys().cloned());
-        ids.extend(self.overlay.sources.keys().cloned());
-        ids.extend(self.overlay.transforms.keys().cloned());
-        ids
+    /// Find the handle of a source by its id.
+    fn find_source_handle(&self, id: u64) -> Option<SourceHandle> {
+        self.layers.iter().find_map(|layer| {
+            layer.sources.iter().find_map(|(stat_id, slots)| {
+                slots
+                    .iter()
+                    .any(|slot| slot.id == id)
+                    .then(|| SourceHandle {
+                        stat: stat_id.clone(),
+                        id,
+                    })
+            })
+        })
     }
 
-    /// Check if this resolver is a fork (has shared base data).
-    ///
-    /// Forks use copy-on-write semantics, storing modifications in the overlay.
-    fn is_fork(&self) -> bool {
-        Arc::strong_count(&self.base) > 1
+    /// Find the handle of a transform by its id.
+    fn find_transform_handle(&self, id: u64) -> Option<TransformHandle> {
+        self.layers.iter().find_map(|layer| {
+            layer.transforms.iter().find_map(|(stat_id, slots)| {
+                slots
+                    .iter()
+                    .any(|slot| slot.id == id)
+                    .then(|| TransformHandle {
+                        stat: stat_id.clone(),
+                        id,
+                    })
+            })
+        })
+    }
+
+    /// Check whether a source has been removed in any layer.
+    fn is_source_removed(&self, id: u64) -> bool {
+        self.layers
+            .iter()
+            .any(|layer| layer.removed_sources.contains(&id))
     }
 
-    /// Get mutable access to sources, using overlay if this is a fork.
+    /// Check whether a transform has been removed in any layer.
+    fn is_transform_removed(&self, id: u64) -> bool {
+        self.layers
+            .iter()
+            .any(|layer| layer.removed_transforms.contains(&id))
+    }
+
+    /// Get mutable access to the top layer.
     ///
-    /// Returns a mutable reference to the sources vector for the given stat,
-    /// either from the overlay (if fork) or base (if original).
-    fn get_mut_sources(&mut self, stat_id: StatId) -> &mut Vec<Box<dyn StatSource>> {
-        if self.is_fork() {
-            // This is a fork, use overlay
-            self.overlay.sources.entry(stat_id).or_default()
-        } else {
-            // This is the original, try to get mutable access to base
-            if let Some(base) = Arc::get_mut(&mut self.base) {
-                base.sources.entry(stat_id).or_default()
-            } else {
-                // Fallback: use overlay if we can't get mutable access
-                self.overlay.sources.entry(stat_id).or_default()
-            }
+    /// If the top layer is shared with a parent or a fork, a fresh layer is
+    /// pushed first so shared data is never modified. Adjacent layers that
+    /// are no longer shared (e.g. after a fork was dropped) are merged back
+    /// together to keep the chain short.
+    fn top_layer_mut(&mut self) -> &mut Layer<N> {
+        self.compact_layers();
+        if self.layers.last_mut().and_then(Arc::get_mut).is_none() {
+            self.layers.push(Arc::new(Layer::new()));
         }
+        let top = self
+            .layers
+            .last_mut()
+            .expect("resolver has at least one layer");
+        Arc::get_mut(top).expect("top layer is uniquely owned")
     }
 
-    /// Get mutable access to transforms, using overlay if this is a fork.
-    ///
-    /// Returns a mutable reference to the transforms vector for the given stat,
-    /// either from the overlay (if fork) or base (if original).
-    fn get_mut_transforms(&mut self, stat_id: StatId) -> &mut Vec<TransformEntry> {
-        if self.is_fork() {
-            // This is a fork, use overlay
-            self.overlay.transforms.entry(stat_id).or_default()
-        } else {
-            // This is the original, try to get mutable access to base
-            if let Some(base) = Arc::get_mut(&mut self.base) {
-                base.transforms.entry(stat_id).or_default()
-            } else {
-                // Fallback: use overlay if we can't get mutable access
-                self.overlay.transforms.entry(stat_id).or_default()
+    /// Merge the top two layers while both are uniquely owned.
+    fn compact_layers(&mut self) {
+        while self.layers.len() > 1 {
+            let len = self.layers.len();
+            let both_unique = self.layers[len - 2..]
+                .iter()
+                .all(|layer| Arc::strong_count(layer) == 1 && Arc::weak_count(layer) == 0);
+            if !both_unique {
+                break;
             }
+            let upper = self.layers.pop().expect("checked length above");
+            let upper = Arc::try_unwrap(upper)
+                .unwrap_or_else(|_| unreachable!("layer was checked to be unique"));
+            let lower = self.layers.last_mut().expect("checked length above");
+            Arc::get_mut(lower)
+                .expect("layer was checked to be unique")
+                .absorb(upper);
         }
     }
 
+    /// Get mutable access to sources in the top layer.
+    fn get_mut_sources(&mut self, stat_id: StatId) -> &mut SourceSlots<N> {
+        self.top_layer_mut().sources.entry(stat_id).or_default()
+    }
+
+    /// Get mutable access to transforms in the top layer.
+    fn get_mut_transforms(&mut self, stat_id: StatId) -> &mut Vec<Slot<Arc<TransformEntry<N>>>> {
+        self.top_layer_mut().transforms.entry(stat_id).or_default()
+    }
+
     /// Internal method to resolve stats based on scope.
     ///
     /// This is the unified implementation for all resolve methods.
@@ -641,7 +1526,12 @@ impl StatResolver {
         &mut self,
         scope: ResolveScope,
         context: &StatContext,
-    ) -> Result<HashMap<StatId, ResolvedStat>, StatError> {
+    ) -> Result<HashMap<StatId, ResolvedStat<N>>, StatError> {
+        // Drop cached stats that were computed under a different context
+        if !self.context_fingerprints.is_empty() {
+            self.invalidate_stale_context(context);
+        }
+
         // Extract needed information from scope before moving
         let (single_stat_id, _batch_targets, is_all) = match &scope {
             ResolveScope::Single(stat_id) => {
@@ -662,34 +1552,27 @@ impl StatResolver {
             }
         };
 
-        // Determine which graph to use and which stats to resolve
-        let (graph, resolution_order) = match scope {
-            ResolveScope::Single(_) => {
-                // Build full graph and get topological sort
-                let full_graph = self.build_graph()?;
-                let order = full_graph.topological_sort()?;
-                (full_graph, order)
-            }
-            ResolveScope::All => {
-                // Build full graph and get topological sort
-                let full_graph = self.build_graph()?;
-                let order = full_graph.topological_sort()?;
-                (full_graph, order)
-            }
-            ResolveScope::Batch(ref targets) => {
-                // Build full graph and extract subgraph for targets
-                let full_graph = self.build_graph()?;
-                let subgraph = full_graph.subgraph_for_targets(targets);
-                let order = subgraph.topological_sort()?;
-                (full_graph, order)
+        // Determine which stats to resolve
+        let resolution_order = match scope {
+            ResolveScope::All => self.resolution_order()?,
+            // Only visit the targets' transitive dependencies
+            ResolveScope::Single(ref stat_id) => {
+                Arc::new(self.graph.dependency_order(std::slice::from_ref(stat_id))?)
             }
+            ResolveScope::Batch(ref targets) => Arc::new(self.graph.dependency_order(targets)?),
         };
 
+        // Resolve level by level in parallel mode; on failure, finish
+        // sequentially so the error matches the sequential path
+        if self.parallel && single_stat_id.is_none() {
+            let _ = self.resolve_levels_parallel(&resolution_order, context);
+        }
+
         // Resolve all stats in resolution order
-        for stat_id in &resolution_order {
+        for stat_id in resolution_order.iter() {
             if !self.cache.contains_key(stat_id) {
-                let resolved = self.resolve_stat_internal(stat_id, context, &graph)?;
-                self.cache.insert(stat_id.clone(), resolved);
+                let resolved = self.resolve_stat_internal(stat_id, context)?;
+                self.cache_resolved(stat_id, resolved, context);
             }
         }
 
@@ -705,7 +1588,7 @@ impl StatResolver {
             results = self.cache.clone();
         } else {
             // Return only stats from the resolution order (subgraph)
-            for stat_id in &resolution_order {
+            for stat_id in resolution_order.iter() {
                 if let Some(resolved) = self.cache.get(stat_id) {
                     results.insert(stat_id.clone(), resolved.clone());
                 }
@@ -715,28 +1598,101 @@ impl StatResolver {
         Ok(results)
     }
 
-    /// Build the dependency graph from all registered transforms.
-    fn build_graph(&self) -> Result<StatGraph, StatError> {
-        let mut graph = StatGraph::new();
-
-        // Add all stats that have sources or transforms
-        for stat_id in self.get_all_stat_ids() {
-            graph.add_node(stat_id);
-        }
+    /// Resolve the uncached stats of `order` one dependency level at a
+    /// time, spreading each level over scoped worker threads.
+    ///
+    /// Stops at the first level with a failing stat, after caching that
+    /// level's successful results.
+    fn resolve_levels_parallel(
+        &mut self,
+        order: &[StatId],
+        context: &StatContext,
+    ) -> Result<(), StatError> {
+        let workers = std::thread::available_parallelism().map_or(1, |n| n.get());
+        for level in self.graph.levels(order) {
+            let pending: Vec<&StatId> = level
+                .iter()
+                .filter(|stat_id| !self.cache.contains_key(*stat_id))
+                .collect();
+            if pending.is_empty() {
+                continue;
+            }
 
-        // Add edges from transform dependencies (check overlay first, then base)
-        for stat_id in self.get_all_stat_ids() {
-            if let Some(transforms) = self.get_transforms(&stat_id) {
-                for entry in transforms {
-                    for dep in entry.transform.depends_on() {
-                        // dep must be resolved before stat_id
-                        graph.add_edge(stat_id.clone(), dep);
+            let this = &*self;
+            let results: Vec<Result<ResolvedStat<N>, StatError>> =
+                if workers < 2 || pending.len() < 2 {
+                    pending
+                        .iter()
+                        .map(|stat_id| this.resolve_stat_internal(stat_id, context))
+                        .collect()
+                } else {
+                    let chunk_size = pending.len().div_ceil(workers);
+                    std::thread::scope(|scope| {
+                        let handles: Vec<_> = pending
+                            .chunks(chunk_size)
+                            .map(|chunk| {
+                                scope.spawn(move || {
+                                    chunk
+                                        .iter()
+                                        .map(|stat_id| this.resolve_stat_internal(stat_id, context))
+                                        .collect::<Vec<_>>()
+                                })
+                            })
+                            .collect();
+                        // Join in chunk order, so results line up with `pending`
+                        handles
+                            .into_iter()
+                            .flat_map(|handle| {
+                                handle
+                                    .join()
+                                    .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+                            })
+                            .collect()
+                    })
+                };
+
+            let mut failed = None;
+            for (stat_id, result) in pending.into_iter().zip(results) {
+                match result {
+                    Ok(resolved) => self.cache_resolved(stat_id, resolved, context),
+                    Err(err) => {
+                        failed.get_or_insert(err);
                     }
                 }
             }
+            if let Some(err) = failed {
+                return Err(err);
+            }
+        }
+        Ok(())
+    }
+
+    /// Cache a resolved stat, with the context it was computed under.
+    fn cache_resolved(
+        &mut self,
+        stat_id: &StatId,
+        resolved: ResolvedStat<N>,
+        context: &StatContext,
+    ) {
+        self.cache.insert(stat_id.clone(), resolved);
+        let keys = self.context_keys_for(stat_id);
+        if !keys.is_empty() {
+            self.context_fingerprints
+                .insert(stat_id.clone(), context.fingerprint(&keys));
         }
+    }
 
-        Ok(graph)
+    /// Get the topological order of the full dependency graph.
+    ///
+    /// The order is computed once and reused until a registration or
+    /// removal changes the graph.
+    fn resolution_order(&mut self) -> Result<Arc<Vec<StatId>>, StatError> {
+        if let Some(order) = &self.resolution_order {
+            return Ok(Arc::clone(order));
+        }
+        let order = Arc::new(self.graph.topological_sort()?);
+        self.resolution_order = Some(Arc::clone(&order));
+        Ok(order)
     }
 
     /// Internal method to resolve a single stat.
@@ -744,75 +1700,66 @@ impl StatResolver {
         &self,
         stat_id: &StatId,
         context: &StatContext,
-        _graph: &StatGraph,
-    ) -> Result<ResolvedStat, StatError> {
-        let mut resolved = ResolvedStat::new(stat_id.clone(), StatValue::zero());
+    ) -> Result<ResolvedStat<N>, StatError> {
+        let mut resolved = ResolvedStat::new(stat_id.clone(), N::zero());
 
         // Step 1: Collect all source values (additive)
-        // Combine overlay and base sources (overlay adds to base, doesn't shadow)
-        let mut base_value = StatValue::zero();
+        // Combine sources from every layer (upper layers add, don't shadow)
+        let mut base_value = N::zero();
         let mut source_count = 0;
 
-        // Collect base sources
-        if let Some(base_sources) = self.base.sources.get(stat_id) {
-            for source in base_sources.iter() {
-                let value = source.get_value(stat_id, context);
-                base_value += value;
-                source_count += 1;
-                resolved.add_source(format!("Source #{}", source_count), value);
-            }
-        }
-
-        // Collect overlay sources (additive to base)
-        if let Some(overlay_sources) = self.overlay.sources.get(stat_id) {
-            for source in overlay_sources.iter() {
-                let value = source.get_value(stat_id, context);
-                base_value += value;
-                source_count += 1;
-                resolved.add_source(format!("Source #{}", source_count), value);
-            }
+        for source in self.get_sources(stat_id) {
+            let value = self.canonicalize(source.get_value(stat_id, context));
+            base_value += value;
+            source_count += 1;
+            let description = source
+                .description()
+                .unwrap_or_else(|| format!("Source #{}", source_count));
+            resolved.add_tagged_source(description, value, source.tags());
         }
 
         // If no sources at all, create a default source entry
         if source_count == 0 {
-            resolved.add_source("Default", StatValue::zero());
+            resolved.add_source("Default", N::zero());
         }
 
         // Step 2: Apply transforms grouped by phase, then by stack rule
-        // Combine overlay and base transforms (overlay adds to base, doesn't shadow)
-        let mut current_value = base_value;
+        // Combine transforms from every layer (upper layers add, don't shadow)
+        let mut current_value = self.canonicalize(base_value);
 
-        // Collect all transforms (base first, then overlay)
-        let mut all_transforms = Vec::new();
-        if let Some(base_transforms) = self.base.transforms.get(stat_id) {
-            all_transforms.extend(base_transforms.iter());
-        }
-        if let Some(overlay_transforms) = self.overlay.transforms.get(stat_id) {
-            all_transforms.extend(overlay_transforms.iter());
-        }
+        // Collect all transforms (base layer first)
+        let all_transforms: Vec<&TransformEntry<N>> = self.get_transforms(stat_id).collect();
 
         if !all_transforms.is_empty() {
-            // Group transforms by phase
-            let mut transforms_by_phase: std::collections::BTreeMap<u8, Vec<&TransformEntry>> =
-                std::collections::BTreeMap::new();
+            // Group transforms by phase, keyed by position in the phase order
+            let mut transforms_by_phase: std::collections::BTreeMap<
+                usize,
+                Vec<&TransformEntry<N>>,
+            > = std::collections::BTreeMap::new();
 
             for entry in all_transforms {
-                let phase_value = entry.phase.value();
-                transforms_by_phase
-                    .entry(phase_value)
-                    .or_default()
-                    .push(entry);
+                let rank = self.phases.rank(entry.phase);
+                transforms_by_phase.entry(rank).or_default().push(entry);
             }
 
             // Apply transforms in phase order, with stack rules applied within each phase
-            for (_phase_value, phase_entries) in transforms_by_phase {
-                current_value = self.apply_transforms_with_stack_rules(
+            for (_rank, phase_entries) in transforms_by_phase {
+                let phase = phase_entries[0].phase;
+                let phase_start = current_value;
+                let (value, stacks) = self.apply_transforms_with_stack_rules(
                     current_value,
                     phase_entries,
                     stat_id,
                     context,
                     &mut resolved,
                 )?;
+                current_value = self.canonicalize(value);
+                resolved.breakdown.push(PhaseBreakdown {
+                    phase,
+                    value_before: phase_start,
+                    value_after: current_value,
+                    stacks,
+                });
             }
         }
 
@@ -823,27 +1770,35 @@ impl StatResolver {
     /// Apply transforms in a phase with stack rule semantics.
     ///
     /// Groups transforms by stack rule priority and applies them in order:
-    /// Override → Additive → Multiplicative → Diminishing → Min → Max → MinMax
+    /// Override → Additive → Multiplicative → Diminishing → DiminishingReturns → Min → Max → MinMax
     ///
     /// Stack rule semantics:
-    /// - Additive: base + sum(all additive deltas) where delta = transform.apply(0)
-    /// - Multiplicative: base × product(all multipliers) where multiplier = transform.apply(1.0)
+    /// - Additive: base + sum(all additive deltas)
+    /// - Multiplicative: base × product(all multipliers)
     /// - Override: last transform wins (deterministic order)
     /// - Diminishing: value × (1 - exp(-k × stacks)) where stacks = number of transforms
+    /// - DiminishingReturns: base + curve(sum(all additive deltas)), per curve
     /// - Min: clamp to maximum of all min bounds (most restrictive)
     /// - Max: clamp to minimum of all max bounds (most restrictive)
     /// - MinMax: collect all min/max bounds, compute effective_min = max(all mins),
     ///   effective_max = min(all maxes), then clamp(value, effective_min, effective_max)
+    ///
+    /// Deltas and multipliers come from `StatTransform::contribution`,
+    /// falling back to applying the transform to 0 or 1.0 when it doesn't
+    /// report one (see `additive_delta` and `multiplicative_factor`).
+    ///
+    /// Returns the value after the phase together with one breakdown entry
+    /// per stack rule group that changed (or could have changed) the value.
     fn apply_transforms_with_stack_rules(
         &self,
-        base_value: StatValue,
-        entries: Vec<&TransformEntry>,
+        base_value: N,
+        entries: Vec<&TransformEntry<N>>,
         stat_id: &StatId,
         context: &StatContext,
-        resolved: &mut ResolvedStat,
-    ) -> Result<StatValue, StatError> {
+        resolved: &mut ResolvedStat<N>,
+    ) -> Result<(N, Vec<StackBreakdown<N>>), StatError> {
         // Group entries by stack rule (sorted by priority)
-        let mut by_rule: std::collections::BTreeMap<u8, Vec<&TransformEntry>> =
+        let mut by_rule: std::collections::BTreeMap<u8, Vec<&TransformEntry<N>>> =
             std::collections::BTreeMap::new();
 
         for entry in entries {
@@ -852,6 +1807,7 @@ impl StatResolver {
         }
 
         let mut current_value = base_value;
+        let mut stacks = Vec::new();
 
         // Apply stack rules in priority order
         for (_priority, rule_entries) in by_rule {
@@ -860,9 +1816,11 @@ impl StatResolver {
             }
 
             // All entries in this group have the same stack rule
-            let stack_rule = rule_entries[0].rule;
+            let stack_rule = rule_entries[0].rule.clone();
+            let stack_start = current_value;
+            let mut contributions = Vec::new();
 
-            match stack_rule {
+            match &stack_rule {
                 StackRule::Override => {
                     // Last transform wins (deterministic order - use last entry)
                     if let Some(last_entry) = rule_entries.last() {
@@ -873,19 +1831,21 @@ impl StatResolver {
                                 .transform
                                 .apply(current_value, &dependencies, context)?;
                         resolved.add_transform(last_entry.transform.description(), new_value);
+                        contributions.push(Self::contribution(
+                            last_entry,
+                            current_value,
+                            new_value,
+                        ));
                         current_value = new_value;
                     }
                 }
                 StackRule::Additive => {
                     // Additive stacking: base + sum(all additive deltas)
-                    // Extract delta by applying each transform to zero
-                    let mut sum_delta = StatValue::zero();
+                    let mut sum_delta = N::zero();
                     for entry in &rule_entries {
-                        let dependencies =
-                            self.collect_dependencies(entry.transform.depends_on(), stat_id)?;
-                        // Apply to zero to extract the additive delta
-                        let zero = StatValue::zero();
-                        let delta = entry.transform.apply(zero, &dependencies, context)?;
+                        let delta = self.additive_delta(entry, stack_start, stat_id, context)?;
+                        let before = current_value + sum_delta;
+                        contributions.push(Self::contribution(entry, before, before + delta));
                         sum_delta += delta;
                     }
                     // Apply the sum of deltas to the current value
@@ -897,18 +1857,37 @@ impl StatResolver {
                 }
                 StackRule::Multiplicative => {
                     // Multiplicative stacking: base × product(all multipliers)
-                    // Extract multiplier by applying each transform to 1.0
-                    let mut product_multiplier = StatValue::from_f64(1.0);
+                    // Ratios and deltas are applied to the running value
+                    // step by step, so no rounded factor is multiplied in.
+                    let mut product_multiplier = N::from_int(1);
+                    let mut running = current_value;
+                    let mut stepwise = false;
                     for entry in &rule_entries {
-                        let dependencies =
-                            self.collect_dependencies(entry.transform.depends_on(), stat_id)?;
-                        // Apply to 1.0 to extract the multiplier
-                        let one = StatValue::from_f64(1.0);
-                        let multiplier = entry.transform.apply(one, &dependencies, context)?;
-                        product_multiplier *= multiplier;
+                        let after =
+                            match self.multiplicative_step(entry, stack_start, stat_id, context)? {
+                                MultiplicativeStep::Factor(multiplier) => {
+                                    product_multiplier *= multiplier;
+                                    running * multiplier
+                                }
+                                MultiplicativeStep::Ratio { after, before } => {
+                                    stepwise = true;
+                                    product_multiplier *= after / before;
+                                    running.mul_div(after, before)
+                                }
+                                MultiplicativeStep::Delta(delta) => {
+                                    stepwise = true;
+                                    running + delta
+                                }
+                            };
+                        contributions.push(Self::contribution(entry, running, after));
+                        running = after;
                     }
                     // Apply the product of multipliers to the current value
-                    current_value *= product_multiplier;
+                    if stepwise {
+                        current_value = running;
+                    } else {
+                        current_value *= product_multiplier;
+                    }
                     resolved.add_transform(
                         format!("×{:.4} (multiplicative stack)", product_multiplier.to_f64()),
                         current_value,
@@ -917,115 +1896,225 @@ impl StatResolver {
                 StackRule::Diminishing { k } => {
                     // Diminishing returns: value × (1 - exp(-k × stacks))
                     // Count the number of stacks (transforms)
-                    let stacks = rule_entries.len() as f64;
-                    let k_f64 = k.to_f64();
-                    let multiplier = 1.0 - (-k_f64 * stacks).exp();
-                    current_value *= StatValue::from_f64(multiplier);
+                    let stacks = N::from_int(rule_entries.len() as i64);
+                    let multiplier = N::from_int(1) - (N::from_decimal(*k) * stacks).exp_neg();
+                    current_value *= multiplier;
                     resolved.add_transform(
                         format!(
-                            "×{:.4} (diminishing k={:.2}, stacks={:.0})",
-                            multiplier, k_f64, stacks
+                            "×{:.4} (diminishing k={:.2}, stacks={})",
+                            multiplier.to_f64(),
+                            k.to_f64(),
+                            rule_entries.len()
                         ),
                         current_value,
                     );
-                }
-                StackRule::Min => {
-                    // Min clamping: clamp to maximum of all min bounds (most restrictive)
-                    let mut min_bound = None;
+                    // Every stack counts the same, so each gets an equal share
+                    let share = (current_value - stack_start) / stacks;
+                    let mut running = stack_start;
                     for entry in &rule_entries {
-                        let bound = self.extract_min_bound(entry, stat_id, context)?;
-                        if let Some(bound_value) = bound {
-                            // Take the maximum of all min bounds (most restrictive)
-                            min_bound = Some(
-                                min_bound.map_or(bound_value, |m: StatValue| m.max(bound_value)),
-                            );
-                        }
-                    }
-                    if let Some(min) = min_bound {
-                        current_value = current_value.max(min);
-                        resolved.add_transform(format!("min({:.2})", min.to_f64()), current_value);
+                        contributions.push(Self::contribution(entry, running, running + share));
+                        running += share;
                     }
                 }
-                StackRule::Max => {
-                    // Max clamping: clamp to minimum of all max bounds (most restrictive)
-                    let mut max_bound = None;
+                StackRule::DiminishingReturns(_) => {
+                    // Diminish the summed additive deltas, separately per mode
+                    let mut modes: Vec<(&DiminishingMode, Vec<&TransformEntry<N>>)> = Vec::new();
                     for entry in &rule_entries {
-                        let bound = self.extract_max_bound(entry, stat_id, context)?;
-                        if let Some(bound_value) = bound {
-                            // Take the minimum of all max bounds (most restrictive)
-                            max_bound = Some(
-                                max_bound.map_or(bound_value, |m: StatValue| m.min(bound_value)),
-                            );
+                        let StackRule::DiminishingReturns(mode) = &entry.rule else {
+                            continue;
+                        };
+                        match modes.iter_mut().find(|(m, _)| *m == mode) {
+                            Some((_, group)) => group.push(entry),
+                            None => modes.push((mode, vec![entry])),
                         }
                     }
-                    if let Some(max) = max_bound {
-                        current_value = current_value.min(max);
-                        resolved.add_transform(format!("max({:.2})", max.to_f64()), current_value);
-                    }
-                }
-                StackRule::MinMax => {
-                    // MinMax clamping: collect all min/max bounds and apply most restrictive
-                    // effective_min = max(all mins), effective_max = min(all maxes)
-                    let mut min_bounds = Vec::new();
-                    let mut max_bounds = Vec::new();
 
-                    for entry in &rule_entries {
-                        // Extract bounds using helper methods
-                        if let Some(min) = self.extract_min_bound(entry, stat_id, context)? {
-                            min_bounds.push(min);
+                    for (mode, group) in modes {
+                        let group_start = current_value;
+                        let mut deltas = Vec::with_capacity(group.len());
+                        let mut raw = N::zero();
+                        for entry in &group {
+                            let delta =
+                                self.additive_delta(entry, group_start, stat_id, context)?;
+                            deltas.push(delta);
+                            raw += delta;
                         }
-                        if let Some(max) = self.extract_max_bound(entry, stat_id, context)? {
-                            max_bounds.push(max);
+                        let diminished = mode.apply(raw);
+                        current_value += diminished;
+                        resolved.add_transform(
+                            format!(
+                                "+{:.2} (diminished from {:.2})",
+                                diminished.to_f64(),
+                                raw.to_f64()
+                            ),
+                            current_value,
+                        );
+
+                        // Each transform keeps its share of the raw sum
+                        let mut running = group_start;
+                        let mut group_contributions = Vec::with_capacity(group.len());
+                        for (entry, delta) in group.iter().zip(deltas) {
+                            let share = if raw == N::zero() {
+                                N::zero()
+                            } else {
+                                delta * diminished / raw
+                            };
+                            group_contributions.push(Self::contribution(
+                                entry,
+                                running,
+                                running + share,
+                            ));
+                            running += share;
                         }
-                    }
-
-                    // Compute effective bounds (most restrictive)
-                    let effective_min =
-                        min_bounds.iter().fold(None, |acc: Option<StatValue>, &m| {
-                            Some(acc.map_or(m, |a: StatValue| a.max(m)))
+                        stacks.push(StackBreakdown {
+                            rule: StackRule::DiminishingReturns(mode.clone()),
+                            value_before: group_start,
+                            value_after: current_value,
+                            transforms: group_contributions,
+                            diminishing: Some(DiminishingBreakdown { raw, diminished }),
                         });
-                    let effective_max =
-                        max_bounds.iter().fold(None, |acc: Option<StatValue>, &m| {
-                            Some(acc.map_or(m, |a: StatValue| a.min(m)))
-                        });
-
-                    // Apply clamping
-                    if let Some(min) = effective_min {
-                        current_value = current_value.max(min);
-                    }
-                    if let Some(max) = effective_max {
-                        current_value = current_value.min(max);
                     }
+                }
+                StackRule::Min | StackRule::Max | StackRule::MinMax => {
+                    let clamp = self.apply_clamp_stack(
+                        &stack_rule,
+                        &rule_entries,
+                        current_value,
+                        stat_id,
+                        context,
+                        resolved,
+                    )?;
+                    current_value = clamp.value;
+                    contributions = clamp.contributions;
 
                     // Update resolved stat description
-                    match (effective_min, effective_max) {
-                        (Some(min), Some(max)) => {
-                            resolved.add_transform(
-                                format!("clamp({:.2}, {:.2})", min.to_f64(), max.to_f64()),
-                                current_value,
-                            );
-                        }
-                        (Some(min), None) => {
-                            resolved.add_transform(
-                                format!("clamp_min({:.2})", min.to_f64()),
-                                current_value,
-                            );
+                    let description = match (&stack_rule, clamp.min, clamp.max) {
+                        (StackRule::Min, Some(min), _) => format!("min({:.2})", min.to_f64()),
+                        (StackRule::Max, _, Some(max)) => format!("max({:.2})", max.to_f64()),
+                        (StackRule::MinMax, Some(min), Some(max)) => {
+                            format!("clamp({:.2}, {:.2})", min.to_f64(), max.to_f64())
                         }
-                        (None, Some(max)) => {
-                            resolved.add_transform(
-                                format!("clamp_max({:.2})", max.to_f64()),
-                                current_value,
-                            );
+                        (StackRule::MinMax, Some(min), None) => {
+                            format!("clamp_min({:.2})", min.to_f64())
                         }
-                        (None, None) => {
-                            // No bounds, no-op
+                        (StackRule::MinMax, None, Some(max)) => {
+                            format!("clamp_max({:.2})", max.to_f64())
                         }
+                        // No bounds, no-op
+                        _ => String::new(),
+                    };
+                    if !description.is_empty() {
+                        resolved.add_transform(description, current_value);
                     }
                 }
             }
+
+            if !contributions.is_empty() {
+                stacks.push(StackBreakdown {
+                    rule: stack_rule,
+                    value_before: stack_start,
+                    value_after: current_value,
+                    transforms: contributions,
+                    diminishing: None,
+                });
+            }
         }
 
-        Ok(current_value)
+        Ok((current_value, stacks))
+    }
+
+    /// Get the amount a transform adds to `input` under an additive rule.
+    ///
+    /// Reads `StatTransform::contribution`; transforms without one are
+    /// applied to zero instead.
+    fn additive_delta(
+        &self,
+        entry: &TransformEntry<N>,
+        input: N,
+        stat_id: &StatId,
+        context: &StatContext,
+    ) -> Result<N, StatError> {
+        let dependencies = self.collect_dependencies(entry.transform.depends_on(), stat_id)?;
+        match entry.transform.contribution(&dependencies, context)? {
+            Some(Contribution::Delta(delta)) => Ok(delta),
+            Some(contribution) => Ok(contribution.apply_to(input) - input),
+            None => entry.transform.apply(N::zero(), &dependencies, context),
+        }
+    }
+
+    /// Get the step a transform contributes to `input` under a
+    /// multiplicative rule.
+    ///
+    /// Reads `StatTransform::contribution`. Non-factor contributions become
+    /// the ratio of their result on `input` to `input`, or a delta when
+    /// `input` is zero and no ratio can be formed. Only transforms without
+    /// a contribution are probed by applying them to 1.0.
+    fn multiplicative_step(
+        &self,
+        entry: &TransformEntry<N>,
+        input: N,
+        stat_id: &StatId,
+        context: &StatContext,
+    ) -> Result<MultiplicativeStep<N>, StatError> {
+        let dependencies = self.collect_dependencies(entry.transform.depends_on(), stat_id)?;
+        match entry.transform.contribution(&dependencies, context)? {
+            Some(Contribution::Factor(factor)) => Ok(MultiplicativeStep::Factor(factor)),
+            Some(contribution) if input != N::zero() => Ok(MultiplicativeStep::Ratio {
+                after: contribution.apply_to(input),
+                before: input,
+            }),
+            Some(contribution) => Ok(MultiplicativeStep::Delta(
+                contribution.apply_to(input) - input,
+            )),
+            None => entry
+                .transform
+                .apply(N::from_int(1), &dependencies, context)
+                .map(MultiplicativeStep::Factor),
+        }
+    }
+
+    /// Build the breakdown entry for a single transform.
+    fn contribution(
+        entry: &TransformEntry<N>,
+        value_before: N,
+        value_after: N,
+    ) -> TransformBreakdown<N> {
+        TransformBreakdown {
+            description: entry.transform.description(),
+            origin: entry.transform.origin(),
+            delta: value_after - value_before,
+            value_before,
+            value_after,
+        }
+    }
+
+    /// Build breakdown entries for a group of clamp transforms.
+    ///
+    /// The change is attributed to the transform providing the effective
+    /// (most restrictive) bound; every other transform has a zero delta.
+    fn clamp_contributions(
+        bounds: Vec<(&TransformEntry<N>, Option<N>, Option<N>)>,
+        value_before: N,
+        effective_min: Option<N>,
+        effective_max: Option<N>,
+    ) -> Vec<TransformBreakdown<N>> {
+        let mut running = value_before;
+        let mut min_applied = false;
+        let mut max_applied = false;
+        let mut contributions = Vec::new();
+        for (entry, min, max) in bounds {
+            let before = running;
+            if let Some(min) = min.filter(|&m| !min_applied && Some(m) == effective_min) {
+                running = max_value(running, min);
+                min_applied = true;
+            }
+            if let Some(max) = max.filter(|&m| !max_applied && Some(m) == effective_max) {
+                running = min_value(running, max);
+                max_applied = true;
+            }
+            contributions.push(Self::contribution(entry, before, running));
+        }
+        contributions
     }
 
     /// Collect dependency values for a transform.
@@ -1033,7 +2122,7 @@ impl StatResolver {
         &self,
         dep_ids: Vec<StatId>,
         _stat_id: &StatId,
-    ) -> Result<HashMap<StatId, StatValue>, StatError> {
+    ) -> Result<HashMap<StatId, N>, StatError> {
         let mut dependencies = HashMap::new();
         for dep_id in dep_ids {
             let dep_value = self
@@ -1046,71 +2135,128 @@ impl StatResolver {
         Ok(dependencies)
     }
 
-    /// Extract minimum bound from a transform entry.
+    /// Apply a `Min`, `Max` or `MinMax` stack.
     ///
-    /// Applies the transform to a very negative value to infer the minimum bound.
-    /// For ClampTransform, this will return the min parameter.
-    fn extract_min_bound(
-        &self,
-        entry: &TransformEntry,
-        stat_id: &StatId,
-        context: &StatContext,
-    ) -> Result<Option<StatValue>, StatError> {
-        let dependencies = self.collect_dependencies(entry.transform.depends_on(), stat_id)?;
-        let very_negative = StatValue::from_f64(-1e10);
-        let bound_result = entry
-            .transform
-            .apply(very_negative, &dependencies, context)?;
-
-        // If the result differs from input, it's a bound
-        if bound_result > very_negative {
-            Ok(Some(bound_result))
-        } else {
-            Ok(None)
-        }
-    }
-
-    /// Extract maximum bound from a transform entry.
+    /// Bounds are read with `StatTransform::contribution`: the most restrictive
+    /// lower bound (max of mins) and upper bound (min of maxes) win. `Min`
+    /// only uses lower bounds and `Max` only upper bounds. Entries that
+    /// aren't clamps are applied first, in registration order, so the
+    /// bounds always hold.
     ///
-    /// Applies the transform to a very large value to infer the maximum bound.
-    /// For ClampTransform, this will return the max parameter.
-    fn extract_max_bound(
+    /// Returns the new value, the effective bounds and the contributions.
+    fn apply_clamp_stack(
         &self,
-        entry: &TransformEntry,
+        rule: &StackRule,
+        entries: &[&TransformEntry<N>],
+        value: N,
         stat_id: &StatId,
         context: &StatContext,
-    ) -> Result<Option<StatValue>, StatError> {
-        let dependencies = self.collect_dependencies(entry.transform.depends_on(), stat_id)?;
-        let very_large = StatValue::from_f64(1e10);
-        let bound_result = entry.transform.apply(very_large, &dependencies, context)?;
+        resolved: &mut ResolvedStat<N>,
+    ) -> Result<ClampStack<N>, StatError> {
+        let mut current_value = value;
+        let mut contributions = Vec::new();
+        let mut bounds = Vec::new();
+        let mut effective_min: Option<N> = None;
+        let mut effective_max: Option<N> = None;
 
-        // If the result differs from input, it's a bound
-        if bound_result < very_large {
-            Ok(Some(bound_result))
-        } else {
-            Ok(None)
+        for entry in entries {
+            let dependencies = self.collect_dependencies(entry.transform.depends_on(), stat_id)?;
+            let Some(Contribution::Bound(entry_bounds)) =
+                entry.transform.contribution(&dependencies, context)?
+            else {
+                let before = current_value;
+                current_value = entry
+                    .transform
+                    .apply(current_value, &dependencies, context)?;
+                resolved.add_transform(entry.transform.description(), current_value);
+                contributions.push(Self::contribution(entry, before, current_value));
+                continue;
+            };
+            let min = entry_bounds.min.filter(|_| *rule != StackRule::Max);
+            let max = entry_bounds.max.filter(|_| *rule != StackRule::Min);
+            if let Some(min) = min {
+                effective_min = Some(effective_min.map_or(min, |m| max_value(m, min)));
+            }
+            if let Some(max) = max {
+                effective_max = Some(effective_max.map_or(max, |m| min_value(m, max)));
+            }
+            bounds.push((*entry, min, max));
+        }
+
+        if effective_min.is_some() || effective_max.is_some() {
+            let clamp_start = current_value;
+            if let Some(min) = effective_min {
+                current_value = max_value(current_value, min);
+            }
+            if let Some(max) = effective_max {
+                current_value = min_value(current_value, max);
+            }
+            contributions.extend(Self::clamp_contributions(
+                bounds,
+                clamp_start,
+                effective_min,
+                effective_max,
+            ));
         }
+
+        Ok(ClampStack {
+            value: current_value,
+            min: effective_min,
+            max: effective_max,
+            contributions,
+        })
     }
 }
 
-impl Default for StatResolver {
+/// Result of applying a `Min`, `Max` or `MinMax` stack.
+struct ClampStack<N: StatNumeric> {
+    value: N,
+    min: Option<N>,
+    max: Option<N>,
+    contributions: Vec<TransformBreakdown<N>>,
+}
+
+/// What a transform contributes under a `Multiplicative` stack rule.
+enum MultiplicativeStep<N> {
+    /// Multiply the running value by a factor.
+    Factor(N),
+    /// Multiply the running value by `after / before` in one
+    /// `StatNumeric::mul_div`, so a fixed-point backend neither rounds the
+    /// ratio first nor overflows on the intermediate product.
+    Ratio { after: N, before: N },
+    /// Add a delta to the running value (non-factor contributions on a
+    /// zero input, where no factor can be derived).
+    Delta(N),
+}
+
+impl<N: StatNumeric> Default for StatResolver<N> {
     fn default() -> Self {
-        Self::new()
+        Self {
+            layers: vec![Arc::new(Layer::new())],
+            graph: Arc::new(StatGraph::new()),
+            resolution_order: None,
+            cache: HashMap::new(),
+            context_fingerprints: HashMap::new(),
+            phases: Arc::new(PhaseRegistry::new()),
+            canonical_scale: None,
+            parallel: false,
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::numeric::Decimal;
     use crate::source::ConstantSource;
-    use crate::transform::{MultiplicativeTransform, ScalingTransform};
+    use crate::transform::{AdditiveTransform, MultiplicativeTransform, ScalingTransform};
 
     #[test]
     fn test_resolve_simple_source() {
         let mut resolver = StatResolver::new();
         let hp_id = StatId::from_str("HP");
 
-        resolver.register_source(hp_id.clone(), Box::new(ConstantSource(100.0)));
+        resolver.register_source(hp_id.clone(), Box::new(ConstantSource::new(100.0)));
 
         let context = StatContext::new();
         let resolved = resolver.resolve(&hp_id, &context).unwrap();
@@ -1124,8 +2270,8 @@ mod tests {
         let mut resolver = StatResolver::new();
         let hp_id = StatId::from_str("HP");
 
-        resolver.register_source(hp_id.clone(), Box::new(ConstantSource(100.0)));
-        resolver.register_source(hp_id.clone(), Box::new(ConstantSource(50.0)));
+        resolver.register_source(hp_id.clone(), Box::new(ConstantSource::new(100.0)));
+        resolver.register_source(hp_id.clone(), Box::new(ConstantSource::new(50.0)));
 
         let context = StatContext::new();
         let resolved = resolver.resolve(&hp_id, &context).unwrap();
@@ -1139,7 +2285,7 @@ mod tests {
         let mut resolver = StatResolver::new();
         let atk_id = StatId::from_str("ATK");
 
-        resolver.register_source(atk_id.clone(), Box::new(ConstantSource(100.0)));
+        resolver.register_source(atk_id.clone(), Box::new(ConstantSource::new(100.0)));
         resolver.register_transform(atk_id.clone(), Box::new(MultiplicativeTransform::new(1.5)));
 
         let context = StatContext::new();
@@ -1155,8 +2301,8 @@ mod tests {
         let str_id = StatId::from_str("STR");
         let atk_id = StatId::from_str("ATK");
 
-        resolver.register_source(str_id.clone(), Box::new(ConstantSource(10.0)));
-        resolver.register_source(atk_id.clone(), Box::new(ConstantSource(50.0)));
+        resolver.register_source(str_id.clone(), Box::new(ConstantSource::new(10.0)));
+        resolver.register_source(atk_id.clone(), Box::new(ConstantSource::new(50.0)));
         resolver.register_transform(
             atk_id.clone(),
             Box::new(ScalingTransform::new(str_id.clone(), 2.0)),
@@ -1186,7 +2332,7 @@ mod tests {
         let mut resolver = StatResolver::new();
         let hp_id = StatId::from_str("HP");
 
-        resolver.register_source(hp_id.clone(), Box::new(ConstantSource(100.0)));
+        resolver.register_source(hp_id.clone(), Box::new(ConstantSource::new(100.0)));
 
         let context = StatContext::new();
         let resolved1 = resolver.resolve(&hp_id, &context).unwrap();
@@ -1198,7 +2344,7 @@ mod tests {
 
         // Invalidate and add new source
         resolver.invalidate(&hp_id);
-        resolver.register_source(hp_id.clone(), Box::new(ConstantSource(50.0)));
+        resolver.register_source(hp_id.clone(), Box::new(ConstantSource::new(50.0)));
 
         let resolved3 = resolver.resolve(&hp_id, &context).unwrap();
         assert_eq!(resolved3.value, StatValue::from_f64(150.0)); // 100 + 50
@@ -1211,8 +2357,8 @@ mod tests {
         let b_id = StatId::from_str("B");
 
         // Create a cycle: A depends on B, B depends on A
-        resolver.register_source(a_id.clone(), Box::new(ConstantSource(1.0)));
-        resolver.register_source(b_id.clone(), Box::new(ConstantSource(1.0)));
+        resolver.register_source(a_id.clone(), Box::new(ConstantSource::new(1.0)));
+        resolver.register_source(b_id.clone(), Box::new(ConstantSource::new(1.0)));
 
         resolver.register_transform(
             a_id.clone(),
@@ -1233,4 +2379,1049 @@ mod tests {
             panic!("Expected Cycle error");
         }
     }
+
+    #[test]
+    fn test_try_register_transform_rejects_cycle() {
+        let mut resolver = StatResolver::new();
+        let a = StatId::from_str("A");
+        let b = StatId::from_str("B");
+        let c = StatId::from_str("C");
+        resolver.register_source(a.clone(), Box::new(ConstantSource::new(1.0)));
+
+        // A depends on B, B depends on C
+        resolver
+            .try_register_transform(a.clone(), Box::new(ScalingTransform::new(b.clone(), 1.0)))
+            .unwrap();
+        resolver
+            .try_register_transform(b.clone(), Box::new(ScalingTransform::new(c.clone(), 1.0)))
+            .unwrap();
+
+        let context = StatContext::new();
+        resolver.resolve(&a, &context).unwrap();
+
+        // C depending on A closes the loop
+        let result = resolver
+            .try_register_transform(c.clone(), Box::new(ScalingTransform::new(a.clone(), 1.0)));
+        assert_eq!(
+            result.unwrap_err(),
+            StatError::Cycle {
+                path: vec![c.clone(), a.clone(), b.clone(), c.clone()]
+            }
+        );
+
+        // Self-dependency
+        let result = resolver
+            .try_register_transform(a.clone(), Box::new(ScalingTransform::new(a.clone(), 1.0)));
+        assert_eq!(
+            result.unwrap_err(),
+            StatError::Cycle {
+                path: vec![a.clone(), a.clone()]
+            }
+        );
+
+        // The resolver is unchanged: nothing was invalidated or registered
+        assert!(resolver.get_breakdown(&a).is_some());
+        assert!(!resolver.graph.dependents_of(&c).contains(&c));
+        assert!(resolver.resolve(&c, &context).is_ok());
+    }
+
+    #[test]
+    fn test_breakdown_attributes_clamp_to_binding_bound() {
+        use crate::transform::ClampTransform;
+
+        let mut resolver = StatResolver::new();
+        let crit_id = StatId::from_str("CRIT");
+        resolver.register_source(crit_id.clone(), Box::new(ConstantSource::new(120.0)));
+        resolver.register_transform(crit_id.clone(), Box::new(ClampTransform::new(0.0, 100.0)));
+        resolver.register_transform(crit_id.clone(), Box::new(ClampTransform::new(0.0, 75.0)));
+
+        let context = StatContext::new();
+        let resolved = resolver.resolve(&crit_id, &context).unwrap();
+        assert_eq!(resolved.value, StatValue::from_f64(75.0));
+
+        let phase = &resolved.breakdown[0];
+        assert_eq!(phase.phase, TransformPhase::Final);
+        assert_eq!(phase.value_before, StatValue::from_f64(120.0));
+        assert_eq!(phase.value_after, StatValue::from_f64(75.0));
+
+        let stack = &phase.stacks[0];
+        assert_eq!(stack.rule, StackRule::MinMax);
+        assert_eq!(stack.transforms.len(), 2);
+        assert_eq!(stack.transforms[0].delta, StatValue::zero());
+        assert_eq!(stack.transforms[1].delta, StatValue::from_f64(-45.0));
+    }
+
+    #[test]
+    fn test_breakdown_deltas_sum_to_stack_change() {
+        use crate::transform::AdditiveTransform;
+
+        let mut resolver = StatResolver::new();
+        let hp_id = StatId::from_str("HP");
+        resolver.register_source(hp_id.clone(), Box::new(ConstantSource::new(100.0)));
+        resolver.register_transform(hp_id.clone(), Box::new(AdditiveTransform::new(10.0)));
+        resolver.register_transform(hp_id.clone(), Box::new(AdditiveTransform::new(15.0)));
+        resolver.register_transform(hp_id.clone(), Box::new(MultiplicativeTransform::new(2.0)));
+        resolver.register_transform(hp_id.clone(), Box::new(MultiplicativeTransform::new(1.5)));
+
+        let context = StatContext::new();
+        let resolved = resolver.resolve(&hp_id, &context).unwrap();
+        assert_eq!(resolved.value, StatValue::from_f64(375.0));
+        assert_eq!(resolved.breakdown.len(), 2);
+
+        for phase in &resolved.breakdown {
+            for stack in &phase.stacks {
+                let total = stack
+                    .transforms
+                    .iter()
+                    .fold(StatValue::zero(), |acc, t| acc + t.delta);
+                assert_eq!(total, stack.value_after - stack.value_before);
+            }
+        }
+
+        let multiplicative = &resolved.breakdown[1].stacks[0].transforms;
+        assert_eq!(multiplicative[0].value_before, StatValue::from_f64(125.0));
+        assert_eq!(multiplicative[0].value_after, StatValue::from_f64(250.0));
+        assert_eq!(multiplicative[1].delta, StatValue::from_f64(125.0));
+    }
+
+    #[test]
+    fn test_diminishing_returns_use_contribution_size() {
+        use crate::transform::{AdditiveTransform, DiminishingMode};
+
+        let rule = StackRule::DiminishingReturns(DiminishingMode::Hyperbolic {
+            cap: 1.0.into(),
+            k: 1.0.into(),
+        });
+        let resolve_with = |percent: f64| {
+            let mut resolver = StatResolver::new();
+            let dodge_id = StatId::from_str("DODGE");
+            for _ in 0..5 {
+                resolver.register_transform_with_rule(
+                    dodge_id.clone(),
+                    TransformPhase::Additive,
+                    rule.clone(),
+                    Box::new(AdditiveTransform::new(percent)),
+                );
+            }
+            resolver.resolve(&dodge_id, &StatContext::new()).unwrap()
+        };
+
+        // Curves with a non-positive k are rejected
+        let mut resolver = StatResolver::new();
+        let result = resolver.try_register_transform_with_rule(
+            StatId::from_str("DODGE"),
+            TransformPhase::Additive,
+            StackRule::DiminishingReturns(DiminishingMode::Hyperbolic {
+                cap: 1.0.into(),
+                k: Decimal::ZERO,
+            }),
+            Box::new(AdditiveTransform::new(0.01)),
+        );
+        assert!(matches!(result, Err(StatError::InvalidCurve(_))));
+
+        // Five +1% sources: 0.05 / 1.05; five +50% sources: 2.5 / 3.5
+        let small = resolve_with(0.01);
+        let large = resolve_with(0.5);
+        let tolerance = if cfg!(feature = "fixed-point") {
+            1e-3
+        } else {
+            1e-12
+        };
+        assert!((small.value.to_f64() - 0.05 / 1.05).abs() < tolerance);
+        assert!((large.value.to_f64() - 2.5 / 3.5).abs() < tolerance);
+
+        let stack = &large.breakdown[0].stacks[0];
+        let diminishing = stack.diminishing.unwrap();
+        assert_eq!(diminishing.raw, StatValue::from_f64(2.5));
+        assert_eq!(diminishing.diminished, large.value);
+        let total = stack
+            .transforms
+            .iter()
+            .fold(StatValue::zero(), |acc, t| acc + t.delta);
+        assert!((total - diminishing.diminished).to_f64().abs() < tolerance);
+    }
+
+    #[test]
+    fn test_diminishing_returns_modes_are_separate() {
+        use crate::transform::{AdditiveTransform, DiminishingMode, PiecewiseCurve};
+
+        let mut resolver = StatResolver::new();
+        let haste_id = StatId::from_str("HASTE");
+        let curve = PiecewiseCurve::new(&[(10.0, 0.5)]).unwrap();
+        let hyperbolic = DiminishingMode::Hyperbolic {
+            cap: 10.0.into(),
+            k: 10.0.into(),
+        };
+        for (mode, value) in [
+            (DiminishingMode::Piecewise(curve.clone()), 8.0),
+            (hyperbolic, 10.0),
+            (DiminishingMode::Piecewise(curve), 12.0),
+        ] {
+            resolver.register_transform_with_rule(
+                haste_id.clone(),
+                TransformPhase::Additive,
+                StackRule::DiminishingReturns(mode),
+                Box::new(AdditiveTransform::new(value)),
+            );
+        }
+
+        let resolved = resolver.resolve(&haste_id, &StatContext::new()).unwrap();
+        // Piecewise: 10 + (20 - 10) * 0.5 = 15; hyperbolic: 10 * 10 / 20 = 5
+        assert_eq!(resolved.value, StatValue::from_f64(20.0));
+        let stacks = &resolved.breakdown[0].stacks;
+        assert_eq!(stacks.len(), 2);
+        assert_eq!(stacks[0].transforms.len(), 2);
+        assert_eq!(
+            stacks[1].diminishing.unwrap().raw,
+            StatValue::from_f64(10.0)
+        );
+    }
+
+    #[test]
+    fn test_stack_rules_read_contributions() {
+        use crate::transform::{ConditionalTransform, ScalingTransform};
+
+        let mut resolver = StatResolver::new();
+        let str_id = StatId::from_str("STR");
+        let atk_id = StatId::from_str("ATK");
+        resolver.register_source(str_id.clone(), Box::new(ConstantSource::new(10.0)));
+        resolver.register_source(atk_id.clone(), Box::new(ConstantSource::new(100.0)));
+        // A factor under the additive rule adds a share of the stack's input
+        resolver.register_transform_with_rule(
+            atk_id.clone(),
+            TransformPhase::Additive,
+            StackRule::Additive,
+            Box::new(MultiplicativeTransform::new(1.5)),
+        );
+        // A delta under the multiplicative rule becomes the matching factor
+        // (probing with 1.0 would have read 1 + 20 = ×21)
+        resolver.register_transform_with_rule(
+            atk_id.clone(),
+            TransformPhase::Multiplicative,
+            StackRule::Multiplicative,
+            Box::new(
+                ConditionalTransform::new(
+                    |ctx| ctx.get::<bool>("enraged").unwrap_or(false),
+                    Box::new(ScalingTransform::new(str_id.clone(), 2.0)),
+                    "enraged",
+                )
+                .with_context_keys(["enraged"]),
+            ),
+        );
+
+        let mut context = StatContext::new();
+        let resolved = resolver.resolve(&atk_id, &context).unwrap();
+        assert_eq!(resolved.value, StatValue::from_f64(150.0));
+
+        context.set("enraged", true);
+        let resolved = resolver.resolve(&atk_id, &context).unwrap();
+        assert_eq!(resolved.value, StatValue::from_f64(170.0));
+    }
+
+    #[test]
+    fn test_stack_rules_probe_without_contribution() {
+        struct Halve;
+        impl StatTransform for Halve {
+            fn depends_on(&self) -> Vec<StatId> {
+                Vec::new()
+            }
+            fn apply(
+                &self,
+                input: StatValue,
+                _dependencies: &HashMap<StatId, StatValue>,
+                _context: &StatContext,
+            ) -> Result<StatValue, StatError> {
+                Ok(input / StatValue::from_f64(2.0))
+            }
+            fn description(&self) -> String {
+                "halve".to_string()
+            }
+        }
+
+        let mut resolver = StatResolver::new();
+        let hp_id = StatId::from_str("HP");
+        resolver.register_source(hp_id.clone(), Box::new(ConstantSource::new(100.0)));
+        resolver.register_transform(hp_id.clone(), Box::new(Halve));
+        resolver.register_transform(hp_id.clone(), Box::new(MultiplicativeTransform::new(3.0)));
+
+        let context = StatContext::new();
+        let resolved = resolver.resolve(&hp_id, &context).unwrap();
+        // Probed with 1.0: ×0.5, then ×3
+        assert_eq!(resolved.value, StatValue::from_f64(150.0));
+    }
+
+    #[test]
+    fn test_conditional_scaling_on_zero_base() {
+        use crate::transform::ConditionalTransform;
+
+        let mut resolver = StatResolver::new();
+        let str_id = StatId::from_str("STR");
+        let atk_id = StatId::from_str("ATK");
+        resolver.register_source(str_id.clone(), Box::new(ConstantSource::new(10.0)));
+        let scaling = ConditionalTransform::new(
+            |_| true,
+            Box::new(ScalingTransform::new(str_id.clone(), 2.0)),
+            "STR scaling",
+        );
+        assert_eq!(
+            StatTransform::<StatValue>::phase(&scaling),
+            TransformPhase::Additive
+        );
+        resolver.register_transform_in_phase(
+            atk_id.clone(),
+            TransformPhase::Additive,
+            Box::new(scaling),
+        );
+
+        let context = StatContext::new();
+        let resolved = resolver.resolve(&atk_id, &context).unwrap();
+        assert_eq!(resolved.value, StatValue::from_f64(20.0));
+
+        // In the multiplicative phase the delta is still applied, not probed
+        let mut resolver = StatResolver::new();
+        resolver.register_source(str_id.clone(), Box::new(ConstantSource::new(10.0)));
+        resolver.register_transform_in_phase(
+            atk_id.clone(),
+            TransformPhase::Multiplicative,
+            Box::new(ConditionalTransform::new(
+                |_| true,
+                Box::new(ScalingTransform::new(str_id.clone(), 2.0)),
+                "STR scaling",
+            )),
+        );
+        let resolved = resolver.resolve(&atk_id, &context).unwrap();
+        assert_eq!(resolved.value, StatValue::from_f64(20.0));
+    }
+
+    #[test]
+    fn test_multiplicative_ratio_on_large_fixed_point_values() {
+        let fp = |s: &str| s.parse::<FixedPoint>().unwrap();
+        let mut resolver: StatResolver<FixedPoint> = StatResolver::default();
+        let hp_id = StatId::from_str("HP");
+        resolver.register_source(
+            hp_id.clone(),
+            Box::new(ConstantSource::new(fp("200000000"))),
+        );
+        // A delta in the multiplicative phase is applied as a ratio
+        resolver.register_transform_in_phase(
+            hp_id.clone(),
+            TransformPhase::Multiplicative,
+            Box::new(AdditiveTransform::from_value(fp("100000000.5"))),
+        );
+        resolver.register_transform_in_phase(
+            hp_id.clone(),
+            TransformPhase::Multiplicative,
+            Box::new(MultiplicativeTransform::from_value(fp("2"))),
+        );
+
+        let resolved = resolver.resolve(&hp_id, &StatContext::new()).unwrap();
+        assert_eq!(resolved.value, fp("600000001"));
+    }
+
+    #[test]
+    fn test_phase_order() {
+        use crate::transform::{AdditiveTransform, ClampTransform};
+
+        let mut resolver = StatResolver::new();
+        let hp_id = StatId::from_str("HP");
+        resolver.register_source(hp_id.clone(), Box::new(ConstantSource::new(100.0)));
+        resolver.register_transform(hp_id.clone(), Box::new(ClampTransform::new(0.0, 120.0)));
+        resolver.register_transform_in_phase(
+            hp_id.clone(),
+            TransformPhase::Custom(3),
+            Box::new(AdditiveTransform::new(50.0)),
+        );
+
+        let context = StatContext::new();
+        // By default the custom phase runs after the clamp
+        let resolved = resolver.resolve(&hp_id, &context).unwrap();
+        assert_eq!(resolved.value, StatValue::from_f64(150.0));
+
+        let fork = resolver.fork();
+        resolver.set_phases(
+            PhaseRegistry::from_phases([
+                ("gear", TransformPhase::Custom(3)),
+                ("cap", TransformPhase::Final),
+            ])
+            .unwrap(),
+        );
+        let resolved = resolver.resolve(&hp_id, &context).unwrap();
+        assert_eq!(resolved.value, StatValue::from_f64(120.0));
+        assert_eq!(resolved.breakdown[0].phase, TransformPhase::Custom(3));
+        assert_eq!(resolved.breakdown[1].phase, TransformPhase::Final);
+
+        // Forks keep the order they were created with
+        let mut fork = fork;
+        assert_eq!(fork.phases(), &PhaseRegistry::new());
+        let resolved = fork.resolve(&hp_id, &context).unwrap();
+        assert_eq!(resolved.value, StatValue::from_f64(150.0));
+        assert_eq!(
+            resolver.fork().phases().phase("cap"),
+            Some(TransformPhase::Final)
+        );
+    }
+
+    #[test]
+    fn test_undeclared_phase_keeps_default_place() {
+        use crate::transform::{AdditiveTransform, ClampTransform};
+
+        let phases = PhaseRegistry::from_phases([
+            ("gear", TransformPhase::Custom(3)),
+            ("cap", TransformPhase::Final),
+        ])
+        .unwrap();
+        let mut resolver = StatResolver::with_phases(phases);
+        let hp_id = StatId::from_str("HP");
+        resolver.register_source(hp_id.clone(), Box::new(ConstantSource::new(100.0)));
+        resolver.register_transform(hp_id.clone(), Box::new(AdditiveTransform::new(50.0)));
+        resolver.register_transform(hp_id.clone(), Box::new(ClampTransform::new(0.0, 120.0)));
+
+        let context = StatContext::new();
+        // The undeclared additive phase still runs before the cap
+        let resolved = resolver.resolve(&hp_id, &context).unwrap();
+        assert_eq!(resolved.value, StatValue::from_f64(120.0));
+        assert_eq!(resolved.breakdown[0].phase, TransformPhase::Additive);
+    }
+
+    #[test]
+    fn test_clamp_bounds_beyond_1e10() {
+        use crate::transform::ClampTransform;
+
+        let mut resolver = StatResolver::new();
+        let gold_id = StatId::from_str("GOLD");
+        let debt_id = StatId::from_str("DEBT");
+        resolver.register_source(gold_id.clone(), Box::new(ConstantSource::new(5e10)));
+        resolver.register_source(debt_id.clone(), Box::new(ConstantSource::new(-5e10)));
+        resolver.register_transform(gold_id.clone(), Box::new(ClampTransform::new(0.0, 2e10)));
+        resolver.register_transform_with_rule(
+            debt_id.clone(),
+            TransformPhase::Final,
+            StackRule::Min,
+            Box::new(ClampTransform::with_min(StatValue::from_f64(-3e10))),
+        );
+
+        let context = StatContext::new();
+        let gold = resolver.resolve(&gold_id, &context).unwrap();
+        assert_eq!(gold.value, StatValue::from_f64(2e10));
+        let debt = resolver.resolve(&debt_id, &context).unwrap();
+        assert_eq!(debt.value, StatValue::from_f64(-3e10));
+    }
+
+    #[test]
+    fn test_mixed_scales_and_canonical_scale() {
+        use crate::numeric::FixedPoint;
+        use crate::transform::{ClampTransform, MultiplicativeTransform};
+
+        let mut resolver: StatResolver<FixedPoint> = StatResolver::default();
+        let crit_id = StatId::from_str("CRIT");
+        // 0.6 at scale 6, capped at 0.5 given at scale 2 and 0.75 at scale 4
+        resolver.register_source(
+            crit_id.clone(),
+            Box::new(ConstantSource::new(FixedPoint::new(600_000, 6))),
+        );
+        for max in [FixedPoint::new(7500, 4), FixedPoint::new(50, 2)] {
+            resolver.register_transform_with_rule(
+                crit_id.clone(),
+                TransformPhase::Final,
+                StackRule::Max,
+                Box::new(ClampTransform::with_max(max)),
+            );
+        }
+
+        let context = StatContext::new();
+        let crit = resolver.resolve(&crit_id, &context).unwrap();
+        assert_eq!(crit.value, FixedPoint::new(5, 1));
+
+        resolver.register_transform(
+            crit_id.clone(),
+            Box::new(MultiplicativeTransform::from_value(FixedPoint::new(
+                1_333_333, 6,
+            ))),
+        );
+        resolver.set_canonical_scale(Some(4)).unwrap();
+        let crit = resolver.resolve(&crit_id, &context).unwrap();
+        // 0.6 × 1.333333 = 0.7999998, rounded to 0.8 before the cap phase
+        assert_eq!(crit.breakdown[0].value_after, FixedPoint::new(8, 1));
+        assert_eq!(crit.breakdown[0].value_after.scale(), 4);
+        assert_eq!(crit.value, FixedPoint::new(5, 1));
+        assert_eq!(resolver.fork().canonical_scale(), Some(4));
+
+        assert!(matches!(
+            resolver.set_canonical_scale(Some(19)),
+            Err(StatError::InvalidNumber(_))
+        ));
+        assert_eq!(resolver.canonical_scale(), Some(4));
+    }
+
+    #[test]
+    fn test_non_clamp_transform_under_clamp_rule_is_applied() {
+        use crate::transform::ClampTransform;
+
+        let mut resolver = StatResolver::new();
+        let hp_id = StatId::from_str("HP");
+        resolver.register_source(hp_id.clone(), Box::new(ConstantSource::new(100.0)));
+        resolver.register_transform(hp_id.clone(), Box::new(ClampTransform::new(0.0, 150.0)));
+        // Not a clamp, but explicitly grouped under the clamp rule
+        resolver.register_transform_with_rule(
+            hp_id.clone(),
+            TransformPhase::Final,
+            StackRule::MinMax,
+            Box::new(MultiplicativeTransform::new(2.0)),
+        );
+
+        let context = StatContext::new();
+        let resolved = resolver.resolve(&hp_id, &context).unwrap();
+        // Doubled first, then the cap still holds
+        assert_eq!(resolved.value, StatValue::from_f64(150.0));
+        let stack = &resolved.breakdown[0].stacks[0];
+        assert_eq!(stack.transforms.len(), 2);
+        assert_eq!(stack.transforms[0].delta, StatValue::from_f64(100.0));
+        assert_eq!(stack.transforms[1].delta, StatValue::from_f64(-50.0));
+    }
+
+    #[test]
+    fn test_conditional_clamp_bounds() {
+        use crate::transform::{ClampTransform, ConditionalTransform};
+
+        let mut resolver = StatResolver::new();
+        let speed_id = StatId::from_str("SPEED");
+        resolver.register_source(speed_id.clone(), Box::new(ConstantSource::new(300.0)));
+        resolver.register_transform_with_rule(
+            speed_id.clone(),
+            TransformPhase::Final,
+            StackRule::MinMax,
+            Box::new(
+                ConditionalTransform::new(
+                    |ctx| ctx.get::<bool>("rooted").unwrap_or(false),
+                    Box::new(ClampTransform::with_max(StatValue::from_f64(0.0))),
+                    "rooted",
+                )
+                .with_context_keys(["rooted"]),
+            ),
+        );
+
+        let mut context = StatContext::new();
+        let resolved = resolver.resolve(&speed_id, &context).unwrap();
+        assert_eq!(resolved.value, StatValue::from_f64(300.0));
+
+        context.set("rooted", true);
+        let resolved = resolver.resolve(&speed_id, &context).unwrap();
+        assert_eq!(resolved.value, StatValue::from_f64(0.0));
+    }
+
+    #[test]
+    fn test_remove_source() {
+        let mut resolver = StatResolver::new();
+        let hp_id = StatId::from_str("HP");
+
+        resolver.register_source(hp_id.clone(), Box::new(ConstantSource::new(100.0)));
+        let item = resolver.register_source(hp_id.clone(), Box::new(ConstantSource::new(50.0)));
+
+        let context = StatContext::new();
+        let resolved = resolver.resolve(&hp_id, &context).unwrap();
+        assert_eq!(resolved.value, StatValue::from_f64(150.0));
+
+        assert!(resolver.remove_source(&item));
+        let resolved = resolver.resolve(&hp_id, &context).unwrap();
+        assert_eq!(resolved.value, StatValue::from_f64(100.0));
+
+        // Removing twice is a no-op
+        assert!(!resolver.remove_source(&item));
+    }
+
+    #[test]
+    fn test_remove_transform() {
+        let mut resolver = StatResolver::new();
+        let atk_id = StatId::from_str("ATK");
+
+        resolver.register_source(atk_id.clone(), Box::new(ConstantSource::new(100.0)));
+        let buff = resolver
+            .register_transform(atk_id.clone(), Box::new(MultiplicativeTransform::new(1.5)));
+
+        let context = StatContext::new();
+        let resolved = resolver.resolve(&atk_id, &context).unwrap();
+        assert_eq!(resolved.value, StatValue::from_f64(150.0));
+
+        assert!(resolver.remove_transform(&buff));
+        let resolved = resolver.resolve(&atk_id, &context).unwrap();
+        assert_eq!(resolved.value, StatValue::from_f64(100.0));
+        assert!(!resolver.remove_transform(&buff));
+    }
+
+    #[test]
+    fn test_remove_base_entries_from_fork() {
+        let mut base = StatResolver::new();
+        let atk_id = StatId::from_str("ATK");
+
+        let source = base.register_source(atk_id.clone(), Box::new(ConstantSource::new(100.0)));
+        let buff =
+            base.register_transform(atk_id.clone(), Box::new(MultiplicativeTransform::new(2.0)));
+
+        let mut fork = base.fork();
+        fork.register_source(atk_id.clone(), Box::new(ConstantSource::new(10.0)));
+        assert!(fork.remove_transform(&buff));
+        assert!(fork.remove_source(&source));
+        assert!(!fork.remove_source(&source));
+
+        let context = StatContext::new();
+        let fork_resolved = fork.resolve(&atk_id, &context).unwrap();
+        assert_eq!(fork_resolved.value, StatValue::from_f64(10.0));
+
+        // Base is unaffected
+        let base_resolved = base.resolve(&atk_id, &context).unwrap();
+        assert_eq!(base_resolved.value, StatValue::from_f64(200.0));
+    }
+
+    #[test]
+    fn test_nested_fork_inherits_parent_overlay() {
+        let mut base = StatResolver::new();
+        let atk_id = StatId::from_str("ATK");
+        base.register_source(atk_id.clone(), Box::new(ConstantSource::new(100.0)));
+
+        let mut gear = base.fork();
+        let sword = gear.register_source(atk_id.clone(), Box::new(ConstantSource::new(20.0)));
+
+        let mut buffed = gear.fork();
+        buffed.register_transform(atk_id.clone(), Box::new(MultiplicativeTransform::new(2.0)));
+
+        let context = StatContext::new();
+        let resolved = buffed.resolve(&atk_id, &context).unwrap();
+        assert_eq!(resolved.value, StatValue::from_f64(240.0));
+
+        // Removing an entry inherited from the parent fork only hides it here
+        assert!(buffed.remove_source(&sword));
+        let resolved = buffed.resolve(&atk_id, &context).unwrap();
+        assert_eq!(resolved.value, StatValue::from_f64(200.0));
+
+        let gear_resolved = gear.resolve(&atk_id, &context).unwrap();
+        assert_eq!(gear_resolved.value, StatValue::from_f64(120.0));
+        let base_resolved = base.resolve(&atk_id, &context).unwrap();
+        assert_eq!(base_resolved.value, StatValue::from_f64(100.0));
+    }
+
+    #[test]
+    fn test_parent_changes_after_fork_are_not_inherited() {
+        let mut base = StatResolver::new();
+        let hp_id = StatId::from_str("HP");
+        let shield = base.register_source(hp_id.clone(), Box::new(ConstantSource::new(100.0)));
+
+        let mut fork = base.fork();
+        base.register_source(hp_id.clone(), Box::new(ConstantSource::new(50.0)));
+        assert!(base.remove_source(&shield));
+
+        let context = StatContext::new();
+        let fork_resolved = fork.resolve(&hp_id, &context).unwrap();
+        assert_eq!(fork_resolved.value, StatValue::from_f64(100.0));
+        let base_resolved = base.resolve(&hp_id, &context).unwrap();
+        assert_eq!(base_resolved.value, StatValue::from_f64(50.0));
+    }
+
+    #[test]
+    fn test_layers_merge_after_fork_is_dropped() {
+        let mut resolver = StatResolver::new();
+        let hp_id = StatId::from_str("HP");
+        resolver.register_source(hp_id.clone(), Box::new(ConstantSource::new(100.0)));
+
+        let fork = resolver.fork();
+        let potion = resolver.register_source(hp_id.clone(), Box::new(ConstantSource::new(50.0)));
+        assert_eq!(resolver.layers.len(), 2);
+        drop(fork);
+
+        assert!(resolver.remove_source(&potion));
+        assert_eq!(resolver.layers.len(), 1);
+
+        let context = StatContext::new();
+        let resolved = resolver.resolve(&hp_id, &context).unwrap();
+        assert_eq!(resolved.value, StatValue::from_f64(100.0));
+    }
+
+    #[test]
+    fn test_commit_into_applies_additions_and_removals() {
+        let mut base = StatResolver::new();
+        let atk_id = StatId::from_str("ATK");
+        let old_sword = base.register_source(atk_id.clone(), Box::new(ConstantSource::new(10.0)));
+        base.register_source(atk_id.clone(), Box::new(ConstantSource::new(100.0)));
+
+        let mut preview = base.fork();
+        assert!(preview.remove_source(&old_sword));
+        let new_sword =
+            preview.register_source(atk_id.clone(), Box::new(ConstantSource::new(30.0)));
+        preview.register_transform(atk_id.clone(), Box::new(MultiplicativeTransform::new(2.0)));
+
+        let changed = preview.commit_into(&mut base);
+        assert_eq!(changed, HashSet::from([atk_id.clone()]));
+
+        let context = StatContext::new();
+        let resolved = base.resolve(&atk_id, &context).unwrap();
+        assert_eq!(resolved.value, StatValue::from_f64(260.0));
+
+        assert!(!base.remove_source(&old_sword));
+        assert!(base.remove_source(&new_sword));
+        let resolved = base.resolve(&atk_id, &context).unwrap();
+        assert_eq!(resolved.value, StatValue::from_f64(200.0));
+    }
+
+    #[test]
+    fn test_commit_into_only_invalidates_affected_stats() {
+        let mut base = StatResolver::new();
+        let str_id = StatId::from_str("STR");
+        let atk_id = StatId::from_str("ATK");
+        let hp_id = StatId::from_str("HP");
+
+        base.register_source(str_id.clone(), Box::new(ConstantSource::new(10.0)));
+        base.register_source(atk_id.clone(), Box::new(ConstantSource::new(100.0)));
+        base.register_transform(
+            atk_id.clone(),
+            Box::new(ScalingTransform::new(str_id.clone(), 2.0)),
+        );
+        base.register_source(hp_id.clone(), Box::new(ConstantSource::new(500.0)));
+
+        let context = StatContext::new();
+        base.resolve_batch(&[atk_id.clone(), hp_id.clone()], &context)
+            .unwrap();
+
+        let mut fork = base.fork();
+        fork.register_source(str_id.clone(), Box::new(ConstantSource::new(5.0)));
+        fork.commit_into(&mut base);
+
+        assert!(base.get_breakdown(&str_id).is_none());
+        assert!(base.get_breakdown(&atk_id).is_none());
+        assert!(base.get_breakdown(&hp_id).is_some());
+
+        let resolved = base.resolve(&atk_id, &context).unwrap();
+        assert_eq!(resolved.value, StatValue::from_f64(130.0));
+    }
+
+    #[test]
+    fn test_commit_nested_fork_into_grandparent() {
+        let mut base = StatResolver::new();
+        let hp_id = StatId::from_str("HP");
+        base.register_source(hp_id.clone(), Box::new(ConstantSource::new(100.0)));
+
+        let mut gear = base.fork();
+        let armor = gear.register_source(hp_id.clone(), Box::new(ConstantSource::new(50.0)));
+        let mut buffed = gear.fork();
+        assert!(buffed.remove_source(&armor));
+        buffed.register_source(hp_id.clone(), Box::new(ConstantSource::new(25.0)));
+        drop(gear);
+
+        buffed.commit_into(&mut base);
+
+        let context = StatContext::new();
+        let resolved = base.resolve(&hp_id, &context).unwrap();
+        assert_eq!(resolved.value, StatValue::from_f64(125.0));
+    }
+
+    /// A wide stat sheet: 40 base stats, 40 derived from pairs of them,
+    /// and 10 derived from the derived ones.
+    fn wide_resolver() -> StatResolver {
+        use crate::expression::ExpressionTransform;
+
+        let mut resolver = StatResolver::new();
+        let id = |prefix: &str, i: usize| StatId::from_str(&format!("{}{}", prefix, i));
+        for i in 0..40 {
+            resolver.register_source(id("BASE", i), Box::new(ConstantSource::new(i as f64 + 1.0)));
+            let formula = format!("BASE{} * 1.5 + sqrt(BASE{})", i, (i + 7) % 40);
+            resolver.register_transform(
+                id("MID", i),
+                Box::new(ExpressionTransform::parse(&formula).unwrap()),
+            );
+        }
+        for i in 0..10 {
+            let formula = format!("max(MID{}, MID{}) / (BASE{} + 2)", i, i + 10, i + 20);
+            resolver.register_transform(
+                id("TOP", i),
+                Box::new(ExpressionTransform::parse(&formula).unwrap()),
+            );
+        }
+        resolver
+    }
+
+    #[test]
+    fn test_parallel_resolution_matches_sequential() {
+        let context = StatContext::new();
+        let mut sequential = wide_resolver();
+        let mut parallel = wide_resolver();
+        parallel.set_parallel(true);
+        assert!(parallel.fork().is_parallel());
+
+        let expected = sequential.resolve_all(&context).unwrap();
+        let actual = parallel.resolve_all(&context).unwrap();
+        assert_eq!(actual.len(), 90);
+        assert_eq!(actual, expected);
+
+        let targets = [StatId::from_str("TOP3"), StatId::from_str("MID30")];
+        let mut sequential = wide_resolver();
+        let mut parallel = wide_resolver();
+        parallel.set_parallel(true);
+        let expected = sequential.resolve_batch(&targets, &context).unwrap();
+        assert_eq!(
+            parallel.resolve_batch(&targets, &context).unwrap(),
+            expected
+        );
+        assert_eq!(parallel.cache.len(), expected.len());
+    }
+
+    #[test]
+    fn test_parallel_resolution_reports_sequential_error() {
+        use crate::expression::ExpressionTransform;
+
+        let context = StatContext::new();
+        let build = |parallel: bool| {
+            let mut resolver = wide_resolver();
+            resolver.set_parallel(parallel);
+            // One stat fails above the TOP level, another on the first level
+            for (stat, formula) in [("AAA_TOP", "TOP0 / (TOP0 - TOP0)"), ("ZZZ", "1 / 0")] {
+                resolver.register_transform(
+                    StatId::from_str(stat),
+                    Box::new(ExpressionTransform::parse(formula).unwrap()),
+                );
+            }
+            resolver
+        };
+
+        let expected = build(false).resolve_all(&context).unwrap_err();
+        let mut parallel = build(true);
+        assert_eq!(parallel.resolve_all(&context).unwrap_err(), expected);
+    }
+
+    #[test]
+    fn test_resolution_order_is_cached_until_graph_changes() {
+        let mut resolver = StatResolver::new();
+        let str_id = StatId::from_str("STR");
+        let atk_id = StatId::from_str("ATK");
+        let context = StatContext::new();
+
+        resolver.register_source(str_id.clone(), Box::new(ConstantSource::new(10.0)));
+        resolver.resolve_all(&context).unwrap();
+        assert!(resolver.resolution_order.is_some());
+
+        // Another source for a known stat doesn't change the graph
+        resolver.register_source(str_id.clone(), Box::new(ConstantSource::new(5.0)));
+        assert!(resolver.resolution_order.is_some());
+
+        let scaling = resolver.register_transform(
+            atk_id.clone(),
+            Box::new(ScalingTransform::new(str_id.clone(), 2.0)),
+        );
+        assert!(resolver.resolution_order.is_none());
+
+        let all = resolver.resolve_all(&context).unwrap();
+        assert_eq!(all[&atk_id].value, StatValue::from_f64(30.0));
+        let order = resolver.resolution_order.clone().unwrap();
+        let str_pos = order.iter().position(|s| s == &str_id).unwrap();
+        let atk_pos = order.iter().position(|s| s == &atk_id).unwrap();
+        assert!(str_pos < atk_pos);
+
+        assert!(resolver.remove_transform(&scaling));
+        assert!(resolver.resolution_order.is_none());
+        assert!(!resolver.graph.has_edges(&atk_id));
+    }
+
+    #[test]
+    fn test_removing_transform_breaks_cycle() {
+        let mut resolver = StatResolver::new();
+        let a = StatId::from_str("A");
+        let b = StatId::from_str("B");
+        let context = StatContext::new();
+
+        resolver.register_transform(a.clone(), Box::new(ScalingTransform::new(b.clone(), 1.0)));
+        let back_edge =
+            resolver.register_transform(b.clone(), Box::new(ScalingTransform::new(a.clone(), 1.0)));
+        assert!(resolver.resolve(&a, &context).is_err());
+
+        assert!(resolver.remove_transform(&back_edge));
+        assert!(resolver.resolve(&a, &context).is_ok());
+    }
+
+    #[test]
+    fn test_unregistered_dependency_leaves_graph_with_transform() {
+        let mut resolver = StatResolver::new();
+        let atk_id = StatId::from_str("ATK");
+        let str_id = StatId::from_str("STR");
+
+        let scaling = resolver.register_transform(
+            atk_id.clone(),
+            Box::new(ScalingTransform::new(str_id.clone(), 2.0)),
+        );
+        assert!(resolver.graph.contains_node(&str_id));
+
+        assert!(resolver.remove_transform(&scaling));
+        assert!(!resolver.graph.contains_node(&str_id));
+        assert!(resolver.graph.contains_node(&atk_id));
+    }
+
+    #[test]
+    fn test_single_resolve_only_visits_dependencies() {
+        let mut resolver = StatResolver::new();
+        let str_id = StatId::from_str("STR");
+        let atk_id = StatId::from_str("ATK");
+        let hp_id = StatId::from_str("HP");
+        let a = StatId::from_str("A");
+        let b = StatId::from_str("B");
+
+        resolver.register_source(str_id.clone(), Box::new(ConstantSource::new(10.0)));
+        resolver.register_source(atk_id.clone(), Box::new(ConstantSource::new(100.0)));
+        resolver.register_transform(
+            atk_id.clone(),
+            Box::new(ScalingTransform::new(str_id.clone(), 2.0)),
+        );
+        resolver.register_source(hp_id.clone(), Box::new(ConstantSource::new(500.0)));
+
+        // An unrelated cycle doesn't affect ATK
+        resolver.register_transform(a.clone(), Box::new(ScalingTransform::new(b.clone(), 1.0)));
+        resolver.register_transform(b.clone(), Box::new(ScalingTransform::new(a.clone(), 1.0)));
+
+        let context = StatContext::new();
+        let resolved = resolver.resolve(&atk_id, &context).unwrap();
+        assert_eq!(resolved.value, StatValue::from_f64(120.0));
+
+        assert!(resolver.get_breakdown(&str_id).is_some());
+        assert!(resolver.get_breakdown(&hp_id).is_none());
+        assert!(resolver.get_breakdown(&a).is_none());
+        assert!(matches!(
+            resolver.resolve(&a, &context),
+            Err(StatError::Cycle { .. })
+        ));
+    }
+
+    #[test]
+    fn test_invalidate_cascades_to_dependents() {
+        let mut resolver = StatResolver::new();
+        let str_id = StatId::from_str("STR");
+        let atk_id = StatId::from_str("ATK");
+        let dps_id = StatId::from_str("DPS");
+        let hp_id = StatId::from_str("HP");
+
+        resolver.register_source(str_id.clone(), Box::new(ConstantSource::new(10.0)));
+        resolver.register_source(hp_id.clone(), Box::new(ConstantSource::new(100.0)));
+        resolver.register_transform(
+            atk_id.clone(),
+            Box::new(ScalingTransform::new(str_id.clone(), 2.0)),
+        );
+        resolver.register_transform(
+            dps_id.clone(),
+            Box::new(ScalingTransform::new(atk_id.clone(), 1.0)),
+        );
+
+        let context = StatContext::new();
+        resolver.resolve_all(&context).unwrap();
+
+        let evicted = resolver.invalidate_with_dependents(&str_id);
+        assert_eq!(evicted.len(), 3);
+        assert!(evicted.contains(&str_id));
+        assert!(evicted.contains(&atk_id));
+        assert!(evicted.contains(&dps_id));
+        assert!(resolver.get_breakdown(&hp_id).is_some());
+
+        // Nothing left to evict
+        assert!(resolver.invalidate_with_dependents(&str_id).is_empty());
+    }
+
+    #[test]
+    fn test_invalidate_reaches_dependents_of_uncached_stat() {
+        let mut resolver = StatResolver::new();
+        let str_id = StatId::from_str("STR");
+        let atk_id = StatId::from_str("ATK");
+
+        resolver.register_source(str_id.clone(), Box::new(ConstantSource::new(10.0)));
+        resolver.register_transform(
+            atk_id.clone(),
+            Box::new(ScalingTransform::new(str_id.clone(), 2.0)),
+        );
+        resolver.resolve_all(&StatContext::new()).unwrap();
+
+        // STR dropped from the cache on its own; ATK is still cached
+        resolver.evict(&str_id);
+        assert!(resolver.get_breakdown(&atk_id).is_some());
+
+        let evicted = resolver.invalidate_with_dependents(&str_id);
+        assert_eq!(evicted, HashSet::from([atk_id.clone()]));
+        assert!(resolver.get_breakdown(&atk_id).is_none());
+    }
+
+    #[test]
+    fn test_register_source_refreshes_dependents() {
+        let mut resolver = StatResolver::new();
+        let str_id = StatId::from_str("STR");
+        let atk_id = StatId::from_str("ATK");
+
+        resolver.register_source(str_id.clone(), Box::new(ConstantSource::new(10.0)));
+        resolver.register_transform(
+            atk_id.clone(),
+            Box::new(ScalingTransform::new(str_id.clone(), 2.0)),
+        );
+
+        let context = StatContext::new();
+        let resolved = resolver.resolve(&atk_id, &context).unwrap();
+        assert_eq!(resolved.value, StatValue::from_f64(20.0));
+
+        // STR changes; cached ATK must not stay stale
+        let buff = resolver.register_source(str_id.clone(), Box::new(ConstantSource::new(5.0)));
+        let resolved = resolver.resolve(&atk_id, &context).unwrap();
+        assert_eq!(resolved.value, StatValue::from_f64(30.0));
+
+        resolver.remove_source(&buff);
+        let resolved = resolver.resolve(&atk_id, &context).unwrap();
+        assert_eq!(resolved.value, StatValue::from_f64(20.0));
+    }
+
+    #[test]
+    fn test_context_change_re_resolves_dependents() {
+        use crate::transform::ConditionalTransform;
+
+        struct DifficultySource;
+
+        impl StatSource for DifficultySource {
+            fn get_value(&self, _stat_id: &StatId, context: &StatContext) -> StatValue {
+                StatValue::from_f64(context.get::<f64>("difficulty").unwrap_or(1.0))
+            }
+
+            fn context_keys(&self) -> Vec<String> {
+                vec!["difficulty".to_string()]
+            }
+        }
+
+        let mut resolver = StatResolver::new();
+        let level_id = StatId::from_str("LEVEL");
+        let hp_id = StatId::from_str("HP");
+
+        resolver.register_source(level_id.clone(), Box::new(DifficultySource));
+        resolver.register_source(hp_id.clone(), Box::new(ConstantSource::new(100.0)));
+        resolver.register_transform(
+            hp_id.clone(),
+            Box::new(ScalingTransform::new(level_id.clone(), 10.0)),
+        );
+        resolver.register_transform(
+            hp_id.clone(),
+            Box::new(
+                ConditionalTransform::new(
+                    |ctx| ctx.get::<bool>("in_combat").unwrap_or(false),
+                    Box::new(MultiplicativeTransform::new(2.0)),
+                    "combat",
+                )
+                .with_context_keys(["in_combat"]),
+            ),
+        );
+
+        let mut context = StatContext::new();
+        context.set("in_combat", false);
+        context.set("difficulty", 1.0);
+        let resolved = resolver.resolve(&hp_id, &context).unwrap();
+        assert_eq!(resolved.value, StatValue::from_f64(110.0));
+
+        context.set("in_combat", true);
+        let resolved = resolver.resolve(&hp_id, &context).unwrap();
+        assert_eq!(resolved.value, StatValue::from_f64(220.0));
+
+        // Dependency reads the context: HP must follow LEVEL
+        context.set("difficulty", 2.0);
+        let resolved = resolver.resolve(&hp_id, &context).unwrap();
+        assert_eq!(resolved.value, StatValue::from_f64(240.0));
+
+        // Unrelated keys don't invalidate
+        context.set("zone", "town");
+        resolver.resolve(&hp_id, &context).unwrap();
+        assert!(resolver.get_breakdown(&level_id).is_some());
+    }
 }
@@ -0,0 +1,393 @@
+//! Resource pools module.
+//!
+//! Stats like `MAX_HP` are resolved values; the character's current HP is
+//! state. A `ResourcePool` holds that state next to the max stat it is
+//! bound to and keeps it consistent when the max changes.
+
+use crate::context::StatContext;
+use crate::error::StatError;
+use crate::numeric::{StatNumeric, StatValue};
+use crate::resolver::StatResolver;
+use crate::stat_id::StatId;
+use serde::{Deserialize, Serialize};
+
+/// What happens to the current value when the max changes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MaxChangePolicy {
+    /// Keep the fill ratio: 50/100 becomes 60/120.
+    KeepRatio,
+    /// Keep the current value, clamped to the new max: 50/100 becomes 50/120.
+    KeepAbsolute,
+    /// Keep the missing amount: 50/100 becomes 70/120, and losing max
+    /// drains current by the same amount (never below zero).
+    KeepMissing,
+}
+
+/// A depletable resource (HP, MP, energy) bound to a max stat.
+///
+/// The current value always stays within `0..=max`. Serialization is
+/// deterministic: fields are written in declaration order. Deserializing
+/// a pool with a negative max or a current value outside `0..=max` fails
+/// with `StatError::InvalidResource`.
+///
+/// # Examples
+///
+/// ```rust
+/// use zzstat::*;
+/// use zzstat::resource::{MaxChangePolicy, ResourcePool};
+/// use zzstat::source::ConstantSource;
+///
+/// let mut resolver = StatResolver::new();
+/// let max_hp = StatId::from_str("MAX_HP");
+/// resolver.register_source(max_hp.clone(), Box::new(ConstantSource::new(100.0)));
+///
+/// let context = StatContext::new();
+/// let mut hp = ResourcePool::from_resolver(
+///     &mut resolver,
+///     max_hp.clone(),
+///     MaxChangePolicy::KeepRatio,
+///     &context,
+/// )
+/// .unwrap();
+///
+/// hp.damage(40.0);
+/// assert_eq!(hp.current(), 60.0);
+///
+/// // Equipping gear raises MAX_HP; the pool keeps its ratio
+/// resolver.register_source(max_hp.clone(), Box::new(ConstantSource::new(100.0)));
+/// hp.sync(&mut resolver, &context).unwrap();
+/// assert_eq!(hp.current(), 120.0);
+/// assert_eq!(hp.max(), 200.0);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(bound = "", try_from = "ResourcePoolRepr<N>")]
+pub struct ResourcePool<N: StatNumeric = StatValue> {
+    max_stat: StatId,
+    current: N,
+    max: N,
+    policy: MaxChangePolicy,
+}
+
+/// The serialized form of a `ResourcePool`, checked before it is accepted.
+#[derive(Deserialize)]
+#[serde(bound = "")]
+struct ResourcePoolRepr<N: StatNumeric> {
+    max_stat: StatId,
+    current: N,
+    max: N,
+    policy: MaxChangePolicy,
+}
+
+impl<N: StatNumeric> TryFrom<ResourcePoolRepr<N>> for ResourcePool<N> {
+    type Error = StatError;
+
+    fn try_from(repr: ResourcePoolRepr<N>) -> Result<Self, StatError> {
+        // NaN compares false both ways, so it has to be rejected up front
+        if !repr.max.to_f64().is_finite() || !repr.current.to_f64().is_finite() {
+            return Err(StatError::InvalidResource(format!(
+                "{}: current {} and max {} must be finite",
+                repr.max_stat, repr.current, repr.max
+            )));
+        }
+        if repr.max < N::zero() {
+            return Err(StatError::InvalidResource(format!(
+                "{}: max {} is negative",
+                repr.max_stat, repr.max
+            )));
+        }
+        if repr.current < N::zero() || repr.current > repr.max {
+            return Err(StatError::InvalidResource(format!(
+                "{}: current {} is outside 0..={}",
+                repr.max_stat, repr.current, repr.max
+            )));
+        }
+        Ok(Self {
+            max_stat: repr.max_stat,
+            current: repr.current,
+            max: repr.max,
+            policy: repr.policy,
+        })
+    }
+}
+
+impl ResourcePool {
+    /// Create a full pool.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_stat` - The stat that holds this pool's max
+    /// * `max` - The current value of the max stat
+    /// * `policy` - What happens to the current value when the max changes
+    pub fn new(max_stat: StatId, max: impl Into<StatValue>, policy: MaxChangePolicy) -> Self {
+        Self::full(max_stat, max.into(), policy)
+    }
+}
+
+impl<N: StatNumeric> ResourcePool<N> {
+    /// Create a full pool for any numeric backend.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use zzstat::{FixedPoint, MaxChangePolicy, ResourcePool, StatId};
+    ///
+    /// let hp = ResourcePool::full(
+    ///     StatId::from_str("MAX_HP"),
+    ///     FixedPoint::from_f64(100.0),
+    ///     MaxChangePolicy::KeepRatio,
+    /// );
+    /// assert!(hp.is_full());
+    /// ```
+    pub fn full(max_stat: StatId, max: N, policy: MaxChangePolicy) -> Self {
+        let max = non_negative(max);
+        Self {
+            max_stat,
+            current: max,
+            max,
+            policy,
+        }
+    }
+
+    /// Create a full pool with its max resolved from a resolver.
+    pub fn from_resolver(
+        resolver: &mut StatResolver<N>,
+        max_stat: StatId,
+        policy: MaxChangePolicy,
+        context: &StatContext,
+    ) -> Result<Self, StatError> {
+        let max = resolver.resolve(&max_stat, context)?.value;
+        Ok(Self::full(max_stat, max, policy))
+    }
+
+    /// Get the stat this pool's max is bound to.
+    pub fn max_stat(&self) -> &StatId {
+        &self.max_stat
+    }
+
+    /// Get the current value.
+    pub fn current(&self) -> N {
+        self.current
+    }
+
+    /// Get the max value.
+    pub fn max(&self) -> N {
+        self.max
+    }
+
+    /// Get the policy applied when the max changes.
+    pub fn policy(&self) -> MaxChangePolicy {
+        self.policy
+    }
+
+    /// Get the fill ratio in `0.0..=1.0` (1.0 for a pool with zero max).
+    pub fn ratio(&self) -> f64 {
+        if self.max == N::zero() {
+            return 1.0;
+        }
+        self.current.to_f64() / self.max.to_f64()
+    }
+
+    /// Check whether the pool is empty.
+    pub fn is_empty(&self) -> bool {
+        self.current == N::zero()
+    }
+
+    /// Check whether the pool is full.
+    pub fn is_full(&self) -> bool {
+        self.current == self.max
+    }
+
+    /// Set the current value, clamped to `0..=max`.
+    pub fn set_current(&mut self, value: impl Into<N>) {
+        self.current = StatNumeric::clamp(value.into(), N::zero(), self.max);
+    }
+
+    /// Remove up to `amount` from the pool.
+    ///
+    /// Negative amounts are ignored.
+    ///
+    /// # Returns
+    ///
+    /// The amount actually removed (less than `amount` if the pool ran dry).
+    pub fn damage(&mut self, amount: impl Into<N>) -> N {
+        let amount = non_negative(amount.into());
+        let before = self.current;
+        self.set_current(before - amount);
+        before - self.current
+    }
+
+    /// Add up to `amount` to the pool.
+    ///
+    /// Negative amounts are ignored.
+    ///
+    /// # Returns
+    ///
+    /// The amount actually added (less than `amount` if the pool filled up).
+    pub fn heal(&mut self, amount: impl Into<N>) -> N {
+        let amount = non_negative(amount.into());
+        let before = self.current;
+        self.set_current(before + amount);
+        self.current - before
+    }
+
+    /// Regenerate for a number of ticks at `per_tick` per tick.
+    ///
+    /// `per_tick` is typically a resolved regen stat such as `HP_REGEN`.
+    ///
+    /// # Returns
+    ///
+    /// The amount actually added.
+    pub fn regen(&mut self, per_tick: impl Into<N>, ticks: u64) -> N {
+        self.heal(per_tick.into() * N::from_int(ticks as i64))
+    }
+
+    /// Update the max, adjusting the current value according to the policy.
+    ///
+    /// Negative maxima are treated as zero. If the old max was zero, the
+    /// pool is refilled under `KeepRatio`.
+    pub fn set_max(&mut self, max: impl Into<N>) {
+        let new_max = non_negative(max.into());
+        if new_max == self.max {
+            return;
+        }
+        let current = match self.policy {
+            MaxChangePolicy::KeepRatio if self.max == N::zero() => new_max,
+            MaxChangePolicy::KeepRatio => self.current * new_max / self.max,
+            MaxChangePolicy::KeepAbsolute => self.current,
+            MaxChangePolicy::KeepMissing => self.current + (new_max - self.max),
+        };
+        self.max = new_max;
+        self.set_current(current);
+    }
+
+    /// Re-resolve the max stat and apply the change.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the max changed.
+    pub fn sync(
+        &mut self,
+        resolver: &mut StatResolver<N>,
+        context: &StatContext,
+    ) -> Result<bool, StatError> {
+        let max = resolver.resolve(&self.max_stat, context)?.value;
+        let changed = non_negative(max) != self.max;
+        self.set_max(max);
+        Ok(changed)
+    }
+}
+
+fn non_negative<N: StatNumeric>(value: N) -> N {
+    if value < N::zero() {
+        N::zero()
+    } else {
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pool(policy: MaxChangePolicy) -> ResourcePool {
+        let mut pool = ResourcePool::new(StatId::from_str("MAX_HP"), 100.0, policy);
+        pool.damage(50.0);
+        pool
+    }
+
+    #[test]
+    fn test_damage_heal_and_regen() {
+        let mut hp = ResourcePool::new(
+            StatId::from_str("MAX_HP"),
+            100.0,
+            MaxChangePolicy::KeepAbsolute,
+        );
+        assert!(hp.is_full());
+
+        assert_eq!(hp.damage(30.0), 30.0);
+        assert_eq!(hp.damage(-10.0), 0.0);
+        assert_eq!(hp.damage(500.0), 70.0);
+        assert!(hp.is_empty());
+
+        assert_eq!(hp.regen(4.0, 5), 20.0);
+        assert_eq!(hp.heal(500.0), 80.0);
+        assert!(hp.is_full());
+    }
+
+    #[test]
+    fn test_max_change_policies() {
+        let mut ratio = pool(MaxChangePolicy::KeepRatio);
+        ratio.set_max(120.0);
+        assert_eq!(ratio.current(), 60.0);
+        ratio.set_max(40.0);
+        assert_eq!(ratio.current(), 20.0);
+
+        let mut absolute = pool(MaxChangePolicy::KeepAbsolute);
+        absolute.set_max(120.0);
+        assert_eq!(absolute.current(), 50.0);
+        absolute.set_max(40.0);
+        assert_eq!(absolute.current(), 40.0);
+
+        let mut missing = pool(MaxChangePolicy::KeepMissing);
+        missing.set_max(120.0);
+        assert_eq!(missing.current(), 70.0);
+        missing.set_max(10.0);
+        assert_eq!(missing.current(), 0.0);
+
+        // A pool at zero max is refilled when it regains max
+        ratio.set_max(-5.0);
+        assert_eq!(ratio.max(), 0.0);
+        ratio.set_max(80.0);
+        assert!(ratio.is_full());
+    }
+
+    #[test]
+    fn test_serialization_roundtrip() {
+        let hp = pool(MaxChangePolicy::KeepMissing);
+        let json = serde_json::to_string(&hp).unwrap();
+        #[cfg(not(feature = "fixed-point"))]
+        assert_eq!(
+            json,
+            r#"{"max_stat":"MAX_HP","current":50.0,"max":100.0,"policy":"KeepMissing"}"#
+        );
+        let back: ResourcePool = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, hp);
+    }
+
+    #[test]
+    fn test_deserialize_rejects_out_of_range() {
+        for json in [
+            r#"{"max_stat":"MAX_HP","current":150.0,"max":100.0,"policy":"KeepRatio"}"#,
+            r#"{"max_stat":"MAX_HP","current":-1.0,"max":100.0,"policy":"KeepRatio"}"#,
+            r#"{"max_stat":"MAX_HP","current":0.0,"max":-10.0,"policy":"KeepRatio"}"#,
+        ] {
+            let err = serde_json::from_str::<ResourcePool>(json).unwrap_err();
+            assert!(
+                err.to_string().contains("Invalid resource pool"),
+                "unexpected error for {}: {}",
+                json,
+                err
+            );
+        }
+
+        let empty: ResourcePool = serde_json::from_str(
+            r#"{"max_stat":"MAX_HP","current":0.0,"max":0.0,"policy":"KeepRatio"}"#,
+        )
+        .unwrap();
+        assert!(empty.is_empty());
+
+        // JSON can't spell NaN, but other formats can
+        for (current, max) in [(f64::NAN, 100.0), (50.0, f64::NAN), (50.0, f64::INFINITY)] {
+            let repr = ResourcePoolRepr {
+                max_stat: StatId::from_str("MAX_HP"),
+                current,
+                max,
+                policy: MaxChangePolicy::KeepRatio,
+            };
+            assert!(matches!(
+                ResourcePool::try_from(repr),
+                Err(StatError::InvalidResource(_))
+            ));
+        }
+    }
+}
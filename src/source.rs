@@ -7,7 +7,7 @@
 use crate::context::StatContext;
 use crate::numeric::{StatNumeric, StatValue};
 use crate::stat_id::StatId;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 /// Trait for stat sources that produce base values.
 ///
@@ -15,20 +15,23 @@ use std::collections::HashMap;
 /// same output. Multiple sources for the same stat are summed together
 /// (additive).
 ///
+/// Sources are generic over the numeric backend `N`, which defaults to
+/// `StatValue`.
+///
 /// # Examples
 ///
 /// ```rust
 /// use zzstat::{StatSource, StatId, StatContext};
 /// use zzstat::source::ConstantSource;
 ///
-/// let source = ConstantSource(100.0);
+/// let source: ConstantSource = ConstantSource::new(100.0);
 /// let context = StatContext::new();
 /// let stat_id = StatId::from_str("HP");
 ///
 /// let value = source.get_value(&stat_id, &context);
 /// assert_eq!(value, 100.0);
 /// ```
-pub trait StatSource: Send + Sync {
+pub trait StatSource<N: StatNumeric = StatValue>: Send + Sync {
     /// Get the value for a stat from this source.
     ///
     /// # Arguments
@@ -39,7 +42,41 @@ pub trait StatSource: Send + Sync {
     /// # Returns
     ///
     /// The base value contributed by this source.
-    fn get_value(&self, stat_id: &StatId, context: &StatContext) -> StatValue;
+    fn get_value(&self, stat_id: &StatId, context: &StatContext) -> N;
+
+    /// Get the `StatContext` keys this source reads.
+    ///
+    /// The resolver records the values of these keys when it caches a stat
+    /// and re-resolves the stat (and its dependents) when a later resolve
+    /// is called with different values. Sources that read the context must
+    /// declare every key they read, or cached values will go stale.
+    ///
+    /// Default implementation returns an empty list (context-independent).
+    fn context_keys(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Get a human-readable description of this source.
+    ///
+    /// Shown in `ResolvedStat::sources` (e.g. "Base", "Level 50 growth",
+    /// "Guild perk"). Sources without a description are listed as
+    /// "Source #n" in registration order.
+    ///
+    /// Default implementation returns `None`.
+    fn description(&self) -> Option<String> {
+        None
+    }
+
+    /// Get arbitrary tags attributing this source.
+    ///
+    /// Tags such as origin item ID, category, or equipment slot are copied
+    /// into `ResolvedStat::source_tags`, so UIs and logs can tell where
+    /// every point of a stat came from.
+    ///
+    /// Default implementation returns no tags.
+    fn tags(&self) -> BTreeMap<String, String> {
+        BTreeMap::new()
+    }
 }
 
 /// A constant source that always returns the same value.
@@ -53,18 +90,36 @@ pub trait StatSource: Send + Sync {
 /// use zzstat::source::{ConstantSource, StatSource};
 /// use zzstat::{StatId, StatContext};
 ///
-/// let source = ConstantSource(100.0);
+/// let source: ConstantSource = ConstantSource::new(100.0);
 /// let context = StatContext::new();
 /// let stat_id = StatId::from_str("HP");
 ///
 /// assert_eq!(source.get_value(&stat_id, &context), 100.0);
 /// ```
 #[derive(Debug, Clone)]
-pub struct ConstantSource(pub f64);
+pub struct ConstantSource<N: StatNumeric = StatValue>(pub N);
 
-impl StatSource for ConstantSource {
-    fn get_value(&self, _stat_id: &StatId, _context: &StatContext) -> StatValue {
-        StatValue::from_f64(self.0)
+impl<N: StatNumeric> ConstantSource<N> {
+    /// Create a constant source from anything that converts to the value
+    /// type, such as a float for any backend or an exact `FixedPoint`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use zzstat::source::ConstantSource;
+    /// use zzstat::FixedPoint;
+    ///
+    /// let source: ConstantSource<FixedPoint> = ConstantSource::new("0.1".parse::<FixedPoint>().unwrap());
+    /// assert_eq!(source.0, FixedPoint::new(1, 1));
+    /// ```
+    pub fn new(value: impl Into<N>) -> Self {
+        Self(value.into())
+    }
+}
+
+impl<N: StatNumeric> StatSource<N> for ConstantSource<N> {
+    fn get_value(&self, _stat_id: &StatId, _context: &StatContext) -> N {
+        self.0
     }
 }
 
@@ -92,11 +147,35 @@ impl StatSource for ConstantSource {
 /// assert_eq!(source.get_value(&StatId::from_str("ATK"), &context), 0.0);
 /// ```
 #[derive(Debug, Clone)]
-pub struct MapSource {
-    values: HashMap<StatId, f64>,
+pub struct MapSource<N: StatNumeric = StatValue> {
+    values: HashMap<StatId, N>,
 }
 
 impl MapSource {
+    /// Create a new empty `MapSource`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use zzstat::source::MapSource;
+    ///
+    /// let mut source = MapSource::empty();
+    /// source.insert(zzstat::StatId::from_str("HP"), 100.0);
+    /// ```
+    pub fn empty() -> Self {
+        Self::default()
+    }
+}
+
+impl<N: StatNumeric> Default for MapSource<N> {
+    fn default() -> Self {
+        Self {
+            values: HashMap::new(),
+        }
+    }
+}
+
+impl<N: StatNumeric> MapSource<N> {
     /// Create a new `MapSource` from a `HashMap`.
     ///
     /// # Examples
@@ -110,11 +189,11 @@ impl MapSource {
     /// values.insert(StatId::from_str("HP"), 100.0);
     /// let source = MapSource::new(values);
     /// ```
-    pub fn new(values: HashMap<StatId, f64>) -> Self {
+    pub fn new(values: HashMap<StatId, N>) -> Self {
         Self { values }
     }
 
-    /// Create a new empty `MapSource`.
+    /// Insert a value into the map.
     ///
     /// # Examples
     ///
@@ -124,30 +203,107 @@ impl MapSource {
     /// let mut source = MapSource::empty();
     /// source.insert(zzstat::StatId::from_str("HP"), 100.0);
     /// ```
-    pub fn empty() -> Self {
+    pub fn insert(&mut self, stat_id: StatId, value: impl Into<N>) {
+        self.values.insert(stat_id, value.into());
+    }
+}
+
+impl<N: StatNumeric> StatSource<N> for MapSource<N> {
+    fn get_value(&self, stat_id: &StatId, _context: &StatContext) -> N {
+        self.values.get(stat_id).copied().unwrap_or_else(N::zero)
+    }
+}
+
+/// A source wrapper that adds a description and tags to another source.
+///
+/// Use this to label sources that don't describe themselves, such as
+/// `ConstantSource` or `MapSource`. The value and context keys are taken
+/// from the wrapped source.
+///
+/// # Examples
+///
+/// ```rust
+/// use zzstat::source::{ConstantSource, LabeledSource, StatSource};
+/// use zzstat::{StatId, StatContext};
+///
+/// let source: LabeledSource = LabeledSource::new("Iron Sword", Box::new(ConstantSource::new(20.0)))
+///     .with_tag("item_id", "iron_sword")
+///     .with_tag("slot", "main_hand");
+///
+/// let context = StatContext::new();
+/// assert_eq!(source.get_value(&StatId::from_str("ATK"), &context), 20.0);
+/// assert_eq!(source.description().as_deref(), Some("Iron Sword"));
+/// assert_eq!(source.tags()["slot"], "main_hand");
+/// ```
+pub struct LabeledSource<N: StatNumeric = StatValue> {
+    source: Box<dyn StatSource<N>>,
+    description: String,
+    tags: BTreeMap<String, String>,
+}
+
+impl<N: StatNumeric> LabeledSource<N> {
+    /// Create a new labeled source.
+    ///
+    /// # Arguments
+    ///
+    /// * `description` - Human-readable description shown in the breakdown
+    /// * `source` - The source providing the value
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use zzstat::source::{ConstantSource, LabeledSource};
+    ///
+    /// let source: LabeledSource = LabeledSource::new("Base", Box::new(ConstantSource::new(100.0)));
+    /// ```
+    pub fn new(description: impl Into<String>, source: Box<dyn StatSource<N>>) -> Self {
         Self {
-            values: HashMap::new(),
+            source,
+            description: description.into(),
+            tags: BTreeMap::new(),
         }
     }
 
-    /// Insert a value into the map.
+    /// Add a tag to this source.
+    ///
+    /// Setting the same key twice keeps the last value.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The tag name (e.g. "item_id", "category", "slot")
+    /// * `value` - The tag value
     ///
     /// # Examples
     ///
     /// ```rust
-    /// use zzstat::source::MapSource;
+    /// use zzstat::source::{ConstantSource, LabeledSource};
     ///
-    /// let mut source = MapSource::empty();
-    /// source.insert(zzstat::StatId::from_str("HP"), 100.0);
+    /// let source: LabeledSource = LabeledSource::new("Guild perk", Box::new(ConstantSource::new(5.0)))
+    ///     .with_tag("category", "guild");
     /// ```
-    pub fn insert(&mut self, stat_id: StatId, value: f64) {
-        self.values.insert(stat_id, value);
+    pub fn with_tag(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.tags.insert(key.into(), value.into());
+        self
     }
 }
 
-impl StatSource for MapSource {
-    fn get_value(&self, stat_id: &StatId, _context: &StatContext) -> StatValue {
-        StatValue::from_f64(self.values.get(stat_id).copied().unwrap_or(0.0))
+impl<N: StatNumeric> StatSource<N> for LabeledSource<N> {
+    fn get_value(&self, stat_id: &StatId, context: &StatContext) -> N {
+        self.source.get_value(stat_id, context)
+    }
+
+    fn context_keys(&self) -> Vec<String> {
+        self.source.context_keys()
+    }
+
+    fn description(&self) -> Option<String> {
+        Some(self.description.clone())
+    }
+
+    fn tags(&self) -> BTreeMap<String, String> {
+        let mut tags = self.source.tags();
+        tags.extend(self.tags.clone());
+        tags
     }
 }
 
@@ -157,7 +313,7 @@ mod tests {
 
     #[test]
     fn test_constant_source() {
-        let source = ConstantSource(100.0);
+        let source: ConstantSource = ConstantSource::new(100.0);
         let context = StatContext::new();
         let stat_id = StatId::from_str("HP");
 
@@ -243,7 +399,7 @@ mod tests {
 
     #[test]
     fn test_constant_source_negative() {
-        let source = ConstantSource(-50.0);
+        let source: ConstantSource = ConstantSource::new(-50.0);
         let context = StatContext::new();
         let stat_id = StatId::from_str("HP");
 
@@ -255,7 +411,7 @@ mod tests {
 
     #[test]
     fn test_constant_source_zero() {
-        let source = ConstantSource(0.0);
+        let source: ConstantSource = ConstantSource::new(0.0);
         let context = StatContext::new();
         let stat_id = StatId::from_str("HP");
 
@@ -265,9 +421,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_labeled_source() {
+        let inner: LabeledSource = LabeledSource::new("inner", Box::new(ConstantSource::new(10.0)))
+            .with_tag("category", "item")
+            .with_tag("slot", "head");
+        let source = LabeledSource::new("Crown", Box::new(inner)).with_tag("slot", "crown");
+
+        let context = StatContext::new();
+        let stat_id = StatId::from_str("INT");
+        assert_eq!(
+            source.get_value(&stat_id, &context),
+            StatValue::from_f64(10.0)
+        );
+        assert_eq!(source.description().as_deref(), Some("Crown"));
+
+        // Outer tags override inner ones
+        let tags = source.tags();
+        assert_eq!(tags.len(), 2);
+        assert_eq!(tags["category"], "item");
+        assert_eq!(tags["slot"], "crown");
+
+        assert!(ConstantSource::<StatValue>::new(1.0)
+            .description()
+            .is_none());
+        assert!(ConstantSource::<StatValue>::new(1.0).tags().is_empty());
+    }
+
     #[test]
     fn test_constant_source_clone() {
-        let source1 = ConstantSource(100.0);
+        let source1: ConstantSource = ConstantSource::new(100.0);
         let source2 = source1.clone();
 
         let context = StatContext::new();
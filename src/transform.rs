@@ -6,9 +6,11 @@
 
 use crate::context::StatContext;
 use crate::error::StatError;
-use crate::numeric::{StatNumeric, StatValue};
+use crate::numeric::{max_value, min_value, Decimal, StatNumeric, StatValue};
 use crate::stat_id::StatId;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::Arc;
 
 /// Phase for transform application order.
 ///
@@ -28,7 +30,7 @@ use std::collections::HashMap;
 /// // Custom phase (u8 >= 3)
 /// let custom = TransformPhase::Custom(10);
 /// ```
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum TransformPhase {
     /// Additive phase (phase 0).
     ///
@@ -51,7 +53,8 @@ pub enum TransformPhase {
     /// Custom phase (phase >= 3).
     ///
     /// User-defined phases for custom ordering.
-    /// Higher values are applied later.
+    /// Higher values are applied later, and by default after `Final`;
+    /// use a `PhaseRegistry` to place custom phases before it.
     Custom(u8),
 }
 
@@ -83,12 +86,12 @@ impl TransformPhase {
 /// Stack rules determine how multiple transforms affecting the same stat
 /// in the same phase are combined. Transforms are grouped by stack rule
 /// and applied in a fixed priority order within each phase:
-/// Override → Additive → Multiplicative → Diminishing → Min → Max → MinMax
+/// Override → Additive → Multiplicative → Diminishing → DiminishingReturns → Min → Max → MinMax
 ///
 /// # Examples
 ///
 /// ```rust
-/// use zzstat::transform::StackRule;
+/// use zzstat::transform::{DiminishingMode, StackRule};
 /// use zzstat::numeric::StatNumeric;
 ///
 /// // Additive stacking: values are summed
@@ -103,10 +106,13 @@ impl TransformPhase {
 /// // Diminishing returns: uses exponential formula
 /// let diminishing = StackRule::Diminishing { k: 0.5.into() };
 ///
+/// // Diminishing returns on the summed contributions: x / (x + 100)
+/// let hyperbolic = StackRule::DiminishingReturns(DiminishingMode::hyperbolic(1.0, 100.0).unwrap());
+///
 /// // MinMax: for clamp transforms that provide both min and max bounds
 /// let minmax = StackRule::MinMax;
 /// ```
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum StackRule {
     /// Override: Last transform wins (deterministic order).
     Override,
@@ -115,7 +121,16 @@ pub enum StackRule {
     /// Multiplicative: Base × product of all multipliers.
     Multiplicative,
     /// Diminishing returns: Each transform applies `value × (1 - exp(-k × 1))`.
-    Diminishing { k: StatValue },
+    ///
+    /// Only the number of transforms counts, not their values; use
+    /// `DiminishingReturns` to diminish the actual contributions.
+    Diminishing { k: Decimal },
+    /// Diminishing returns on the summed contributions.
+    ///
+    /// The additive deltas of all transforms are summed (like `Additive`)
+    /// and the sum is passed through the curve before it is added. Entries
+    /// with different modes are diminished separately.
+    DiminishingReturns(DiminishingMode),
     /// Minimum: Clamp to minimum value.
     Min,
     /// Maximum: Clamp to maximum value.
@@ -135,20 +150,237 @@ impl StackRule {
     /// Get the priority value for ordering stack rules.
     ///
     /// Lower values are applied first.
-    /// Order: Override (0) → Additive (1) → Multiplicative (2) → Diminishing (3) → Min (4) → Max (5) → MinMax (6)
-    pub fn priority(self) -> u8 {
+    /// Order: Override (0) → Additive (1) → Multiplicative (2) → Diminishing (3) →
+    /// DiminishingReturns (4) → Min (5) → Max (6) → MinMax (7)
+    pub fn priority(&self) -> u8 {
         match self {
             StackRule::Override => 0,
             StackRule::Additive => 1,
             StackRule::Multiplicative => 2,
             StackRule::Diminishing { .. } => 3,
-            StackRule::Min => 4,
-            StackRule::Max => 5,
-            StackRule::MinMax => 6,
+            StackRule::DiminishingReturns(_) => 4,
+            StackRule::Min => 5,
+            StackRule::Max => 6,
+            StackRule::MinMax => 7,
+        }
+    }
+
+    /// Check the rule's parameters.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - The rule can be used
+    /// * `Err(StatError::InvalidCurve)` - A `DiminishingReturns` curve is
+    ///   invalid (see `DiminishingMode::validate`)
+    pub fn validate(&self) -> Result<(), StatError> {
+        match self {
+            StackRule::DiminishingReturns(mode) => mode.validate(),
+            _ => Ok(()),
         }
     }
 }
 
+/// A diminishing returns curve for `StackRule::DiminishingReturns`.
+///
+/// Maps the raw sum of contributions `x` to the value actually added.
+/// Negative sums are added as they are.
+///
+/// # Examples
+///
+/// ```rust
+/// use zzstat::transform::DiminishingMode;
+/// use zzstat::numeric::{StatNumeric, StatValue};
+///
+/// let armor = DiminishingMode::hyperbolic(1.0, 100.0).unwrap();
+/// assert_eq!(armor.apply(StatValue::from_f64(100.0)), StatValue::from_f64(0.5));
+///
+/// let dodge = DiminishingMode::exponential(0.5, 1.0).unwrap();
+/// assert!(dodge.apply(StatValue::from_f64(2.0)).to_f64() < 0.5);
+///
+/// // `k` must be positive
+/// assert!(DiminishingMode::hyperbolic(1.0, 0.0).is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum DiminishingMode {
+    /// `cap × (1 - exp(-x / k))`: close to linear for small `x`,
+    /// approaching `cap`.
+    ///
+    /// The exponential comes from `StatNumeric::exp_neg`, which is
+    /// deterministic for `FixedPoint` but uses the platform's `f64::exp`
+    /// for `f64`.
+    Exponential { cap: Decimal, k: Decimal },
+    /// `cap × x / (x + k)`: reaches half of `cap` at `x = k`.
+    Hyperbolic { cap: Decimal, k: Decimal },
+    /// Rating brackets, each counting at a reduced rate.
+    Piecewise(PiecewiseCurve),
+}
+
+impl DiminishingMode {
+    /// Create an `Exponential` curve, checking that `k` is positive.
+    pub fn exponential(cap: impl Into<Decimal>, k: impl Into<Decimal>) -> Result<Self, StatError> {
+        let mode = DiminishingMode::Exponential {
+            cap: cap.into(),
+            k: k.into(),
+        };
+        mode.validate()?;
+        Ok(mode)
+    }
+
+    /// Create a `Hyperbolic` curve, checking that `k` is positive.
+    pub fn hyperbolic(cap: impl Into<Decimal>, k: impl Into<Decimal>) -> Result<Self, StatError> {
+        let mode = DiminishingMode::Hyperbolic {
+            cap: cap.into(),
+            k: k.into(),
+        };
+        mode.validate()?;
+        Ok(mode)
+    }
+
+    /// Check the curve's parameters.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - The curve can be used
+    /// * `Err(StatError::InvalidCurve)` - `k` of an `Exponential` or
+    ///   `Hyperbolic` curve is not positive
+    pub fn validate(&self) -> Result<(), StatError> {
+        match self {
+            DiminishingMode::Exponential { k, .. } | DiminishingMode::Hyperbolic { k, .. }
+                if *k <= Decimal::ZERO =>
+            {
+                Err(StatError::InvalidCurve(format!(
+                    "k must be positive, got {}",
+                    k
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Diminish a raw sum of contributions.
+    pub fn apply<N: StatNumeric>(&self, raw: N) -> N {
+        if raw <= N::zero() {
+            return raw;
+        }
+        match self {
+            DiminishingMode::Exponential { cap, k } => {
+                let x = raw / N::from_decimal(*k);
+                N::from_decimal(*cap) * (N::from_int(1) - x.exp_neg())
+            }
+            DiminishingMode::Hyperbolic { cap, k } => {
+                N::from_decimal(*cap) * raw / (raw + N::from_decimal(*k))
+            }
+            DiminishingMode::Piecewise(curve) => curve.apply(raw),
+        }
+    }
+}
+
+/// A piecewise-linear diminishing returns curve, like WoW rating brackets.
+///
+/// Each bracket is `(start, rate)`: the part of the raw sum above `start`
+/// (up to the next bracket's start) counts at `rate`. The part below the
+/// first bracket counts in full. The brackets are shared between clones.
+///
+/// # Examples
+///
+/// ```rust
+/// use zzstat::transform::PiecewiseCurve;
+/// use zzstat::numeric::{StatNumeric, StatValue};
+///
+/// // Full value up to 30, 90% up to 40, 80% beyond
+/// let curve = PiecewiseCurve::new(&[(30.0, 0.9), (40.0, 0.8)]).unwrap();
+/// // 30 + 10 * 0.9 + 10 * 0.8
+/// assert_eq!(curve.apply(StatValue::from_f64(50.0)), StatValue::from_f64(47.0));
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "Vec<(Decimal, Decimal)>", into = "Vec<(Decimal, Decimal)>")]
+pub struct PiecewiseCurve {
+    brackets: Arc<[(Decimal, Decimal)]>,
+}
+
+impl PiecewiseCurve {
+    /// Create a curve from `(start, rate)` brackets.
+    ///
+    /// The brackets can be floats, `StatValue`s, `FixedPoint`s or
+    /// `Decimal`s; they are stored exactly as `Decimal`s.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(PiecewiseCurve)` - The curve
+    /// * `Err(StatError::InvalidCurve)` - If the starts are negative or not
+    ///   strictly increasing, or a rate is negative
+    pub fn new<T: Into<Decimal> + Copy>(brackets: &[(T, T)]) -> Result<Self, StatError> {
+        Self::try_from(
+            brackets
+                .iter()
+                .map(|&(start, rate)| (start.into(), rate.into()))
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    /// Get the `(start, rate)` brackets.
+    pub fn brackets(&self) -> &[(Decimal, Decimal)] {
+        &self.brackets
+    }
+
+    /// Diminish a raw value.
+    pub fn apply<N: StatNumeric>(&self, raw: N) -> N {
+        let brackets = self.brackets();
+        let Some(&(first, _)) = brackets.first() else {
+            return raw;
+        };
+        let first = N::from_decimal(first);
+        if raw <= first {
+            return raw;
+        }
+        let mut result = first;
+        for (i, &(start, rate)) in brackets.iter().enumerate() {
+            let start = N::from_decimal(start);
+            if raw <= start {
+                break;
+            }
+            let end = match brackets.get(i + 1) {
+                Some(&(next, _)) if N::from_decimal(next) < raw => N::from_decimal(next),
+                _ => raw,
+            };
+            result += (end - start) * N::from_decimal(rate);
+        }
+        result
+    }
+}
+
+impl TryFrom<Vec<(Decimal, Decimal)>> for PiecewiseCurve {
+    type Error = StatError;
+
+    fn try_from(brackets: Vec<(Decimal, Decimal)>) -> Result<Self, StatError> {
+        let mut previous: Option<Decimal> = None;
+        for &(start, rate) in &brackets {
+            if start < Decimal::ZERO || rate < Decimal::ZERO {
+                return Err(StatError::InvalidCurve(format!(
+                    "bracket ({}, {}) is negative",
+                    start, rate
+                )));
+            }
+            if previous.is_some_and(|previous| start <= previous) {
+                return Err(StatError::InvalidCurve(
+                    "bracket starts must be strictly increasing".to_string(),
+                ));
+            }
+            previous = Some(start);
+        }
+
+        Ok(PiecewiseCurve {
+            brackets: brackets.into(),
+        })
+    }
+}
+
+impl From<PiecewiseCurve> for Vec<(Decimal, Decimal)> {
+    fn from(curve: PiecewiseCurve) -> Self {
+        curve.brackets().to_vec()
+    }
+}
+
 impl PartialOrd for StackRule {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
         Some(self.priority().cmp(&other.priority()))
@@ -159,16 +391,16 @@ impl PartialOrd for StackRule {
 ///
 /// This wrapper stores the metadata needed for stack rule resolution
 /// while maintaining the transform itself.
-pub struct TransformEntry {
+pub struct TransformEntry<N: StatNumeric = StatValue> {
     /// The phase this transform belongs to.
     pub phase: TransformPhase,
     /// The stack rule for combining this transform with others in the same phase.
     pub rule: StackRule,
     /// The actual transform to apply.
-    pub transform: Box<dyn StatTransform>,
+    pub transform: Box<dyn StatTransform<N>>,
 }
 
-impl std::fmt::Debug for TransformEntry {
+impl<N: StatNumeric> std::fmt::Debug for TransformEntry<N> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("TransformEntry")
             .field("phase", &self.phase)
@@ -180,10 +412,9 @@ impl std::fmt::Debug for TransformEntry {
 
 /// Trait for transforms that provide clamp bounds.
 ///
-/// This trait allows the resolver to extract min/max bounds directly from
-/// transforms without applying them to extreme values. This is more efficient
-/// and deterministic than the fallback method of applying transforms to
-/// very large/small values.
+/// Clamp transforms implement this trait to describe their bounds as data;
+/// their `StatTransform::bounds` implementation then returns
+/// `Some(Bounds::of(self))` so the resolver can read them.
 ///
 /// # Examples
 ///
@@ -199,16 +430,16 @@ impl std::fmt::Debug for TransformEntry {
 /// assert_eq!(clamp.min_bound(), Some(StatValue::from_f64(0.0)));
 /// assert_eq!(clamp.max_bound(), Some(StatValue::from_f64(100.0)));
 /// ```
-pub trait ClampBounds {
+pub trait ClampBounds<N: StatNumeric = StatValue> {
     /// Get the minimum bound provided by this transform.
     ///
     /// Returns `None` if this transform does not provide a minimum bound.
-    fn min_bound(&self) -> Option<StatValue>;
+    fn min_bound(&self) -> Option<N>;
 
     /// Get the maximum bound provided by this transform.
     ///
     /// Returns `None` if this transform does not provide a maximum bound.
-    fn max_bound(&self) -> Option<StatValue>;
+    fn max_bound(&self) -> Option<N>;
 }
 
 /// Infer a default stack rule for a transform based on its phase.
@@ -224,7 +455,7 @@ pub trait ClampBounds {
 /// # Returns
 ///
 /// A `StackRule` that should be used for this transform.
-pub fn infer_stack_rule(transform: &dyn StatTransform) -> StackRule {
+pub fn infer_stack_rule<N: StatNumeric>(transform: &dyn StatTransform<N>) -> StackRule {
     match transform.phase() {
         TransformPhase::Additive => StackRule::Additive,
         TransformPhase::Multiplicative => StackRule::Multiplicative,
@@ -233,6 +464,34 @@ pub fn infer_stack_rule(transform: &dyn StatTransform) -> StackRule {
     }
 }
 
+/// Infer a default stack rule for a transform registered in `phase`.
+///
+/// The built-in phases determine the rule (`Additive` stacks additively,
+/// `Multiplicative` multiplicatively, `Final` as a clamp), whatever phase
+/// the transform declares itself. Custom phases have no rule of their own,
+/// so the rule is inferred from the transform as in `infer_stack_rule`.
+///
+/// # Examples
+///
+/// ```rust
+/// use zzstat::transform::{infer_stack_rule_in_phase, MultiplicativeTransform, StackRule, TransformPhase};
+///
+/// let transform = MultiplicativeTransform::new(1.5);
+/// assert_eq!(infer_stack_rule_in_phase(&transform, TransformPhase::Additive), StackRule::Additive);
+/// assert_eq!(infer_stack_rule_in_phase(&transform, TransformPhase::Custom(3)), StackRule::Multiplicative);
+/// ```
+pub fn infer_stack_rule_in_phase<N: StatNumeric>(
+    transform: &dyn StatTransform<N>,
+    phase: TransformPhase,
+) -> StackRule {
+    match phase {
+        TransformPhase::Additive => StackRule::Additive,
+        TransformPhase::Multiplicative => StackRule::Multiplicative,
+        TransformPhase::Final => StackRule::MinMax,
+        TransformPhase::Custom(_) => infer_stack_rule(transform),
+    }
+}
+
 /// Trait for stat transforms that modify stat values.
 ///
 /// Transforms can read other stats (dependencies) and must declare
@@ -254,7 +513,7 @@ pub fn infer_stack_rule(transform: &dyn StatTransform) -> StackRule {
 /// let result = transform.apply(100.0.into(), &deps, &context).unwrap();
 /// assert_eq!(result.to_f64(), 150.0);
 /// ```
-pub trait StatTransform: Send + Sync {
+pub trait StatTransform<N: StatNumeric = StatValue>: Send + Sync {
     /// Get the list of stat IDs this transform depends on.
     ///
     /// These stats must be resolved before this transform can be applied.
@@ -293,10 +552,10 @@ pub trait StatTransform: Send + Sync {
     /// The transformed value, or an error if the transform cannot be applied.
     fn apply(
         &self,
-        input: StatValue,
-        dependencies: &HashMap<StatId, StatValue>,
+        input: N,
+        dependencies: &HashMap<StatId, N>,
         context: &StatContext,
-    ) -> Result<StatValue, StatError>;
+    ) -> Result<N, StatError>;
 
     /// Get a human-readable description of this transform.
     ///
@@ -306,6 +565,143 @@ pub trait StatTransform: Send + Sync {
     ///
     /// A string describing what this transform does.
     fn description(&self) -> String;
+
+    /// Get the `StatContext` keys this transform reads.
+    ///
+    /// The resolver records the values of these keys when it caches a stat
+    /// and re-resolves the stat (and its dependents) when a later resolve
+    /// is called with different values. Transforms that read the context
+    /// must declare every key they read, or cached values will go stale.
+    ///
+    /// Default implementation returns an empty list (context-independent).
+    fn context_keys(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Get an identifier for where this transform came from.
+    ///
+    /// For example the item ID, buff ID, or talent that registered it.
+    /// Reported as `TransformBreakdown::origin` in `ResolvedStat::breakdown`.
+    ///
+    /// Default implementation returns `None`.
+    fn origin(&self) -> Option<String> {
+        None
+    }
+
+    /// Get the clamp bounds this transform enforces.
+    ///
+    /// The `Min`, `Max` and `MinMax` stack rules read bounds through this
+    /// method to combine clamps (most restrictive bound wins). Transforms
+    /// that return `None` are not clamps: under those rules they are applied
+    /// in registration order before the bounds.
+    ///
+    /// Default implementation returns `None`.
+    fn bounds(
+        &self,
+        _dependencies: &HashMap<StatId, N>,
+        _context: &StatContext,
+    ) -> Option<Bounds<N>> {
+        None
+    }
+
+    /// Describe this transform's effect independently of its input.
+    ///
+    /// Stack rules consume the contribution directly: `Additive` and
+    /// `DiminishingReturns` sum deltas, `Multiplicative` multiplies factors
+    /// and the clamp rules combine bounds. A contribution of another kind is
+    /// converted against the value entering the stack, so a factor under the
+    /// `Additive` rule adds `input × (factor - 1)`.
+    ///
+    /// Transforms that return `None` are probed instead: applied to zero to
+    /// read a delta and to one to read a factor. That only works when the
+    /// transform's effect doesn't depend on its input.
+    ///
+    /// Default implementation returns `self.bounds()` as a `Bound`, or `None`.
+    ///
+    /// # Returns
+    ///
+    /// The contribution, or an error if a dependency is missing.
+    fn contribution(
+        &self,
+        dependencies: &HashMap<StatId, N>,
+        context: &StatContext,
+    ) -> Result<Option<Contribution<N>>, StatError> {
+        Ok(self.bounds(dependencies, context).map(Contribution::Bound))
+    }
+}
+
+/// The effect of a transform, as reported by `StatTransform::contribution`.
+///
+/// # Examples
+///
+/// ```rust
+/// use zzstat::transform::Contribution;
+/// use zzstat::{StatNumeric, StatValue};
+///
+/// let factor = Contribution::Factor(StatValue::from_f64(1.5));
+/// assert_eq!(factor.apply_to(StatValue::from_f64(100.0)), 150.0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Contribution<N: StatNumeric = StatValue> {
+    /// Adds a flat amount.
+    Delta(N),
+    /// Multiplies by a factor.
+    Factor(N),
+    /// Replaces the value.
+    Override(N),
+    /// Clamps the value.
+    Bound(Bounds<N>),
+}
+
+impl<N: StatNumeric> Contribution<N> {
+    /// Apply this contribution to a value.
+    pub fn apply_to(&self, input: N) -> N {
+        match *self {
+            Contribution::Delta(delta) => input + delta,
+            Contribution::Factor(factor) => input * factor,
+            Contribution::Override(value) => value,
+            Contribution::Bound(bounds) => bounds.clamp(input),
+        }
+    }
+
+    /// Get the contribution of the same kind that leaves every value
+    /// unchanged (an override has none, so it becomes a zero delta).
+    pub fn neutral(&self) -> Contribution<N> {
+        match self {
+            Contribution::Delta(_) | Contribution::Override(_) => Contribution::Delta(N::zero()),
+            Contribution::Factor(_) => Contribution::Factor(N::from_int(1)),
+            Contribution::Bound(_) => Contribution::Bound(Bounds::default()),
+        }
+    }
+}
+
+/// Clamp bounds reported by `StatTransform::bounds`.
+///
+/// A clamp whose bounds are both `None` currently imposes nothing (for
+/// example a conditional clamp whose condition is false).
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct Bounds<N: StatNumeric = StatValue> {
+    /// The lower bound, if any.
+    pub min: Option<N>,
+    /// The upper bound, if any.
+    pub max: Option<N>,
+}
+
+impl<N: StatNumeric> Bounds<N> {
+    /// Read the bounds of a `ClampBounds` implementation.
+    pub fn of(clamp: &impl ClampBounds<N>) -> Self {
+        Self {
+            min: clamp.min_bound(),
+            max: clamp.max_bound(),
+        }
+    }
+
+    /// Clamp a value to these bounds.
+    pub fn clamp(&self, value: N) -> N {
+        let value = self.min.map_or(value, |min| max_value(value, min));
+        self.max.map_or(value, |max| min_value(value, max))
+    }
 }
 
 /// A multiplicative transform (percentage modifier).
@@ -316,19 +712,20 @@ pub trait StatTransform: Send + Sync {
 ///
 /// ```rust
 /// use zzstat::transform::{StatTransform, MultiplicativeTransform};
+/// use zzstat::numeric::{StatNumeric, StatValue};
 /// use zzstat::StatContext;
 /// use std::collections::HashMap;
 ///
-/// let transform = MultiplicativeTransform::new(1.5);
+/// let transform: MultiplicativeTransform = MultiplicativeTransform::new(1.5);
 /// let context = StatContext::new();
 /// let deps = HashMap::new();
 ///
 /// // 100 * 1.5 = 150
-/// assert_eq!(transform.apply(100.0, &deps, &context).unwrap(), 150.0);
+/// assert_eq!(transform.apply(StatValue::from_f64(100.0), &deps, &context).unwrap(), 150.0);
 /// ```
 #[derive(Debug, Clone)]
-pub struct MultiplicativeTransform {
-    multiplier: f64,
+pub struct MultiplicativeTransform<N: StatNumeric = StatValue> {
+    multiplier: N,
 }
 
 impl MultiplicativeTransform {
@@ -349,7 +746,24 @@ impl MultiplicativeTransform {
     /// // -20% penalty
     /// let penalty = MultiplicativeTransform::new(0.8);
     /// ```
-    pub fn new(multiplier: f64) -> Self {
+    pub fn new(multiplier: impl Into<StatValue>) -> Self {
+        Self::from_value(multiplier.into())
+    }
+}
+
+impl<N: StatNumeric> MultiplicativeTransform<N> {
+    /// Create a multiplicative transform for any numeric backend.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use zzstat::transform::MultiplicativeTransform;
+    /// use zzstat::FixedPoint;
+    ///
+    /// let bonus = MultiplicativeTransform::from_value(FixedPoint::from_f64(1.5));
+    /// assert_eq!(bonus.multiplier(), FixedPoint::from_f64(1.5));
+    /// ```
+    pub fn from_value(multiplier: N) -> Self {
         Self { multiplier }
     }
 
@@ -363,27 +777,35 @@ impl MultiplicativeTransform {
     /// let transform = MultiplicativeTransform::new(1.5);
     /// assert_eq!(transform.multiplier(), 1.5);
     /// ```
-    pub fn multiplier(&self) -> f64 {
+    pub fn multiplier(&self) -> N {
         self.multiplier
     }
 }
 
-impl StatTransform for MultiplicativeTransform {
+impl<N: StatNumeric> StatTransform<N> for MultiplicativeTransform<N> {
     fn depends_on(&self) -> Vec<StatId> {
         Vec::new()
     }
 
     fn apply(
         &self,
-        input: StatValue,
-        _dependencies: &HashMap<StatId, StatValue>,
+        input: N,
+        _dependencies: &HashMap<StatId, N>,
         _context: &StatContext,
-    ) -> Result<StatValue, StatError> {
-        Ok(input * StatValue::from_f64(self.multiplier))
+    ) -> Result<N, StatError> {
+        Ok(input * self.multiplier)
     }
 
     fn description(&self) -> String {
-        format!("×{:.2}", self.multiplier)
+        format!("×{:.2}", self.multiplier.to_f64())
+    }
+
+    fn contribution(
+        &self,
+        _dependencies: &HashMap<StatId, N>,
+        _context: &StatContext,
+    ) -> Result<Option<Contribution<N>>, StatError> {
+        Ok(Some(Contribution::Factor(self.multiplier)))
     }
 }
 
@@ -395,19 +817,20 @@ impl StatTransform for MultiplicativeTransform {
 ///
 /// ```rust
 /// use zzstat::transform::{StatTransform, AdditiveTransform};
+/// use zzstat::numeric::{StatNumeric, StatValue};
 /// use zzstat::StatContext;
 /// use std::collections::HashMap;
 ///
-/// let transform = AdditiveTransform::new(25.0);
+/// let transform: AdditiveTransform = AdditiveTransform::new(25.0);
 /// let context = StatContext::new();
 /// let deps = HashMap::new();
 ///
 /// // 100 + 25 = 125
-/// assert_eq!(transform.apply(100.0, &deps, &context).unwrap(), 125.0);
+/// assert_eq!(transform.apply(StatValue::from_f64(100.0), &deps, &context).unwrap(), 125.0);
 /// ```
 #[derive(Debug, Clone)]
-pub struct AdditiveTransform {
-    bonus: f64,
+pub struct AdditiveTransform<N: StatNumeric = StatValue> {
+    bonus: N,
 }
 
 impl AdditiveTransform {
@@ -428,7 +851,24 @@ impl AdditiveTransform {
     /// // -10 flat penalty
     /// let penalty = AdditiveTransform::new(-10.0);
     /// ```
-    pub fn new(bonus: f64) -> Self {
+    pub fn new(bonus: impl Into<StatValue>) -> Self {
+        Self::from_value(bonus.into())
+    }
+}
+
+impl<N: StatNumeric> AdditiveTransform<N> {
+    /// Create an additive transform for any numeric backend.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use zzstat::transform::AdditiveTransform;
+    /// use zzstat::FixedPoint;
+    ///
+    /// let bonus = AdditiveTransform::from_value(FixedPoint::from_f64(25.0));
+    /// assert_eq!(bonus.bonus(), FixedPoint::from_f64(25.0));
+    /// ```
+    pub fn from_value(bonus: N) -> Self {
         Self { bonus }
     }
 
@@ -442,12 +882,12 @@ impl AdditiveTransform {
     /// let transform = AdditiveTransform::new(25.0);
     /// assert_eq!(transform.bonus(), 25.0);
     /// ```
-    pub fn bonus(&self) -> f64 {
+    pub fn bonus(&self) -> N {
         self.bonus
     }
 }
 
-impl StatTransform for AdditiveTransform {
+impl<N: StatNumeric> StatTransform<N> for AdditiveTransform<N> {
     fn depends_on(&self) -> Vec<StatId> {
         Vec::new()
     }
@@ -458,15 +898,23 @@ impl StatTransform for AdditiveTransform {
 
     fn apply(
         &self,
-        input: StatValue,
-        _dependencies: &HashMap<StatId, StatValue>,
+        input: N,
+        _dependencies: &HashMap<StatId, N>,
         _context: &StatContext,
-    ) -> Result<StatValue, StatError> {
-        Ok(input + StatValue::from_f64(self.bonus))
+    ) -> Result<N, StatError> {
+        Ok(input + self.bonus)
     }
 
     fn description(&self) -> String {
-        format!("+{:.2}", self.bonus)
+        format!("+{:.2}", self.bonus.to_f64())
+    }
+
+    fn contribution(
+        &self,
+        _dependencies: &HashMap<StatId, N>,
+        _context: &StatContext,
+    ) -> Result<Option<Contribution<N>>, StatError> {
+        Ok(Some(Contribution::Delta(self.bonus)))
     }
 }
 
@@ -513,18 +961,18 @@ impl StatTransform for AdditiveTransform {
 /// assert_eq!(move_speed_floor.apply(50.0.into(), &deps, &context).unwrap().to_f64(), 100.0);
 /// ```
 #[derive(Debug, Clone)]
-pub struct ClampTransform {
+pub struct ClampTransform<N: StatNumeric = StatValue> {
     /// Minimum allowed value (inclusive). `None` means no lower bound.
-    pub min: Option<StatValue>,
+    pub min: Option<N>,
     /// Maximum allowed value (inclusive). `None` means no upper bound.
-    pub max: Option<StatValue>,
+    pub max: Option<N>,
 }
 
 impl ClampTransform {
-    /// Create a new clamp transform from f64 values (backward compatibility).
+    /// Create a new clamp transform with both bounds.
     ///
-    /// This is a convenience constructor that maintains backward compatibility
-    /// with existing code. Both min and max are required.
+    /// The bounds are `StatValue`s (plain floats convert), so under the
+    /// `fixed-point` feature they can be given exactly.
     ///
     /// # Arguments
     ///
@@ -539,13 +987,15 @@ impl ClampTransform {
     /// // Clamp between 0 and 100
     /// let clamp = ClampTransform::new(0.0, 100.0);
     /// ```
-    pub fn new(min: f64, max: f64) -> Self {
+    pub fn new(min: impl Into<StatValue>, max: impl Into<StatValue>) -> Self {
         Self {
-            min: Some(StatValue::from_f64(min)),
-            max: Some(StatValue::from_f64(max)),
+            min: Some(min.into()),
+            max: Some(max.into()),
         }
     }
+}
 
+impl<N: StatNumeric> ClampTransform<N> {
     /// Create a new clamp transform with optional min and max bounds.
     ///
     /// # Arguments
@@ -568,7 +1018,7 @@ impl ClampTransform {
     /// // Only max bound (crit chance cap)
     /// let crit_cap = ClampTransform::with_bounds(None, Some(StatValue::from_f64(0.75)));
     /// ```
-    pub fn with_bounds(min: Option<StatValue>, max: Option<StatValue>) -> Self {
+    pub fn with_bounds(min: Option<N>, max: Option<N>) -> Self {
         Self { min, max }
     }
 
@@ -587,7 +1037,7 @@ impl ClampTransform {
     /// // Movement speed floor
     /// let floor = ClampTransform::with_min(StatValue::from_f64(100.0));
     /// ```
-    pub fn with_min(min: StatValue) -> Self {
+    pub fn with_min(min: N) -> Self {
         Self {
             min: Some(min),
             max: None,
@@ -609,7 +1059,7 @@ impl ClampTransform {
     /// // Crit chance cap
     /// let cap = ClampTransform::with_max(StatValue::from_f64(0.75));
     /// ```
-    pub fn with_max(max: StatValue) -> Self {
+    pub fn with_max(max: N) -> Self {
         Self {
             min: None,
             max: Some(max),
@@ -632,7 +1082,7 @@ impl ClampTransform {
     /// let cap_only = ClampTransform::with_max(StatValue::from_f64(100.0));
     /// assert_eq!(cap_only.min(), None);
     /// ```
-    pub fn min(&self) -> Option<StatValue> {
+    pub fn min(&self) -> Option<N> {
         self.min
     }
 
@@ -652,12 +1102,12 @@ impl ClampTransform {
     /// let floor_only = ClampTransform::with_min(StatValue::from_f64(0.0));
     /// assert_eq!(floor_only.max(), None);
     /// ```
-    pub fn max(&self) -> Option<StatValue> {
+    pub fn max(&self) -> Option<N> {
         self.max
     }
 }
 
-impl StatTransform for ClampTransform {
+impl<N: StatNumeric> StatTransform<N> for ClampTransform<N> {
     fn depends_on(&self) -> Vec<StatId> {
         Vec::new()
     }
@@ -668,23 +1118,11 @@ impl StatTransform for ClampTransform {
 
     fn apply(
         &self,
-        input: StatValue,
-        _dependencies: &HashMap<StatId, StatValue>,
+        input: N,
+        _dependencies: &HashMap<StatId, N>,
         _context: &StatContext,
-    ) -> Result<StatValue, StatError> {
-        let mut result = input;
-
-        // Apply min bound if present
-        if let Some(min) = self.min {
-            result = result.max(min);
-        }
-
-        // Apply max bound if present
-        if let Some(max) = self.max {
-            result = result.min(max);
-        }
-
-        Ok(result)
+    ) -> Result<N, StatError> {
+        Ok(Bounds::of(self).clamp(input))
     }
 
     fn description(&self) -> String {
@@ -695,14 +1133,22 @@ impl StatTransform for ClampTransform {
             (None, None) => "clamp(none)".to_string(),
         }
     }
+
+    fn bounds(
+        &self,
+        _dependencies: &HashMap<StatId, N>,
+        _context: &StatContext,
+    ) -> Option<Bounds<N>> {
+        Some(Bounds::of(self))
+    }
 }
 
-impl ClampBounds for ClampTransform {
-    fn min_bound(&self) -> Option<StatValue> {
+impl<N: StatNumeric> ClampBounds<N> for ClampTransform<N> {
+    fn min_bound(&self) -> Option<N> {
         self.min
     }
 
-    fn max_bound(&self) -> Option<StatValue> {
+    fn max_bound(&self) -> Option<N> {
         self.max
     }
 }
@@ -713,10 +1159,16 @@ impl ClampBounds for ClampTransform {
 /// when called with the current `StatContext`. Otherwise, returns the input
 /// value unchanged.
 ///
+/// The condition is an opaque closure, so the context keys it reads must be
+/// declared with [`with_context_keys`](Self::with_context_keys). Without
+/// them, the resolver cannot tell that a cached result depends on the
+/// context and will not re-evaluate the condition when the context changes.
+///
 /// # Examples
 ///
 /// ```rust
 /// use zzstat::transform::{StatTransform, ConditionalTransform, MultiplicativeTransform};
+/// use zzstat::numeric::{StatNumeric, StatValue};
 /// use zzstat::StatContext;
 /// use std::collections::HashMap;
 ///
@@ -724,27 +1176,29 @@ impl ClampBounds for ClampTransform {
 /// context.set("in_combat", true);
 ///
 /// let inner_transform = Box::new(MultiplicativeTransform::new(1.2));
-/// let transform = ConditionalTransform::new(
+/// let transform: ConditionalTransform = ConditionalTransform::new(
 ///     |ctx| ctx.get::<bool>("in_combat").unwrap_or(false),
 ///     inner_transform,
 ///     "combat bonus",
-/// );
+/// )
+/// .with_context_keys(["in_combat"]);
 ///
 /// let deps = HashMap::new();
 /// // In combat: 100 * 1.2 = 120
-/// assert_eq!(transform.apply(100.0, &deps, &context).unwrap(), 120.0);
+/// assert_eq!(transform.apply(StatValue::from_f64(100.0), &deps, &context).unwrap(), 120.0);
 ///
 /// context.set("in_combat", false);
 /// // Out of combat: 100 (unchanged)
-/// assert_eq!(transform.apply(100.0, &deps, &context).unwrap(), 100.0);
+/// assert_eq!(transform.apply(StatValue::from_f64(100.0), &deps, &context).unwrap(), 100.0);
 /// ```
-pub struct ConditionalTransform {
+pub struct ConditionalTransform<N: StatNumeric = StatValue> {
     condition: Box<dyn Fn(&StatContext) -> bool + Send + Sync>,
-    transform: Box<dyn StatTransform>,
+    transform: Box<dyn StatTransform<N>>,
     description: String,
+    context_keys: Vec<String>,
 }
 
-impl ConditionalTransform {
+impl<N: StatNumeric> ConditionalTransform<N> {
     /// Create a new conditional transform.
     ///
     /// # Arguments
@@ -767,7 +1221,7 @@ impl ConditionalTransform {
     /// ```
     pub fn new<F>(
         condition: F,
-        transform: Box<dyn StatTransform>,
+        transform: Box<dyn StatTransform<N>>,
         description: impl Into<String>,
     ) -> Self
     where
@@ -777,21 +1231,55 @@ impl ConditionalTransform {
             condition: Box::new(condition),
             transform,
             description: description.into(),
+            context_keys: Vec::new(),
         }
     }
+
+    /// Declare the context keys read by the condition.
+    ///
+    /// # Arguments
+    ///
+    /// * `keys` - The `StatContext` keys the condition function reads
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use zzstat::transform::{ConditionalTransform, MultiplicativeTransform, StatTransform};
+    ///
+    /// let transform = ConditionalTransform::new(
+    ///     |ctx| ctx.get::<bool>("in_combat").unwrap_or(false),
+    ///     Box::new(MultiplicativeTransform::new(1.5)),
+    ///     "combat bonus +50%",
+    /// )
+    /// .with_context_keys(["in_combat"]);
+    ///
+    /// assert_eq!(transform.context_keys(), vec!["in_combat".to_string()]);
+    /// ```
+    pub fn with_context_keys<I, K>(mut self, keys: I) -> Self
+    where
+        I: IntoIterator<Item = K>,
+        K: Into<String>,
+    {
+        self.context_keys.extend(keys.into_iter().map(Into::into));
+        self
+    }
 }
 
-impl StatTransform for ConditionalTransform {
+impl<N: StatNumeric> StatTransform<N> for ConditionalTransform<N> {
     fn depends_on(&self) -> Vec<StatId> {
         self.transform.depends_on()
     }
 
+    fn phase(&self) -> TransformPhase {
+        self.transform.phase()
+    }
+
     fn apply(
         &self,
-        input: StatValue,
-        dependencies: &HashMap<StatId, StatValue>,
+        input: N,
+        dependencies: &HashMap<StatId, N>,
         context: &StatContext,
-    ) -> Result<StatValue, StatError> {
+    ) -> Result<N, StatError> {
         if (self.condition)(context) {
             self.transform.apply(input, dependencies, context)
         } else {
@@ -802,6 +1290,135 @@ impl StatTransform for ConditionalTransform {
     fn description(&self) -> String {
         self.description.clone()
     }
+
+    fn context_keys(&self) -> Vec<String> {
+        let mut keys = self.context_keys.clone();
+        keys.extend(self.transform.context_keys());
+        keys
+    }
+
+    fn origin(&self) -> Option<String> {
+        self.transform.origin()
+    }
+
+    fn bounds(
+        &self,
+        dependencies: &HashMap<StatId, N>,
+        context: &StatContext,
+    ) -> Option<Bounds<N>> {
+        // Still a clamp while the condition is false, just an inactive one
+        let bounds = self.transform.bounds(dependencies, context)?;
+        if (self.condition)(context) {
+            Some(bounds)
+        } else {
+            Some(Bounds::default())
+        }
+    }
+
+    fn contribution(
+        &self,
+        dependencies: &HashMap<StatId, N>,
+        context: &StatContext,
+    ) -> Result<Option<Contribution<N>>, StatError> {
+        let contribution = self.transform.contribution(dependencies, context)?;
+        if (self.condition)(context) {
+            Ok(contribution)
+        } else {
+            // An inactive transform leaves the value as it is
+            Ok(Some(
+                contribution.map_or(Contribution::Delta(N::zero()), |c| c.neutral()),
+            ))
+        }
+    }
+}
+
+/// A transform wrapper that records where another transform came from.
+///
+/// Everything except the origin is taken from the wrapped transform, so
+/// wrapping doesn't change how (or in which phase) the transform applies.
+///
+/// # Examples
+///
+/// ```rust
+/// use zzstat::transform::{AdditiveTransform, OriginTransform, StatTransform};
+///
+/// let transform = OriginTransform::new("iron_sword", Box::new(AdditiveTransform::new(20.0)));
+/// assert_eq!(transform.origin().as_deref(), Some("iron_sword"));
+/// assert_eq!(transform.description(), "+20.00");
+/// ```
+pub struct OriginTransform<N: StatNumeric = StatValue> {
+    origin: String,
+    transform: Box<dyn StatTransform<N>>,
+}
+
+impl<N: StatNumeric> OriginTransform<N> {
+    /// Create a new origin-tagged transform.
+    ///
+    /// # Arguments
+    ///
+    /// * `origin` - Identifier of where the transform came from
+    /// * `transform` - The transform to wrap
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use zzstat::transform::{MultiplicativeTransform, OriginTransform};
+    ///
+    /// let transform = OriginTransform::new("battle_cry", Box::new(MultiplicativeTransform::new(1.2)));
+    /// ```
+    pub fn new(origin: impl Into<String>, transform: Box<dyn StatTransform<N>>) -> Self {
+        Self {
+            origin: origin.into(),
+            transform,
+        }
+    }
+}
+
+impl<N: StatNumeric> StatTransform<N> for OriginTransform<N> {
+    fn depends_on(&self) -> Vec<StatId> {
+        self.transform.depends_on()
+    }
+
+    fn phase(&self) -> TransformPhase {
+        self.transform.phase()
+    }
+
+    fn apply(
+        &self,
+        input: N,
+        dependencies: &HashMap<StatId, N>,
+        context: &StatContext,
+    ) -> Result<N, StatError> {
+        self.transform.apply(input, dependencies, context)
+    }
+
+    fn description(&self) -> String {
+        self.transform.description()
+    }
+
+    fn context_keys(&self) -> Vec<String> {
+        self.transform.context_keys()
+    }
+
+    fn origin(&self) -> Option<String> {
+        Some(self.origin.clone())
+    }
+
+    fn bounds(
+        &self,
+        dependencies: &HashMap<StatId, N>,
+        context: &StatContext,
+    ) -> Option<Bounds<N>> {
+        self.transform.bounds(dependencies, context)
+    }
+
+    fn contribution(
+        &self,
+        dependencies: &HashMap<StatId, N>,
+        context: &StatContext,
+    ) -> Result<Option<Contribution<N>>, StatError> {
+        self.transform.contribution(dependencies, context)
+    }
 }
 
 /// A transform that scales based on another stat.
@@ -813,23 +1430,24 @@ impl StatTransform for ConditionalTransform {
 ///
 /// ```rust
 /// use zzstat::transform::{StatTransform, ScalingTransform};
+/// use zzstat::numeric::{StatNumeric, StatValue};
 /// use zzstat::{StatId, StatContext};
 /// use std::collections::HashMap;
 ///
 /// let str_id = StatId::from_str("STR");
-/// let transform = ScalingTransform::new(str_id.clone(), 2.0);
+/// let transform: ScalingTransform = ScalingTransform::new(str_id.clone(), 2.0);
 ///
 /// let mut deps = HashMap::new();
-/// deps.insert(str_id.clone(), 10.0);
+/// deps.insert(str_id.clone(), StatValue::from_f64(10.0));
 ///
 /// let context = StatContext::new();
 /// // 100 (base) + 10 (STR) * 2 = 120
-/// assert_eq!(transform.apply(100.0, &deps, &context).unwrap(), 120.0);
+/// assert_eq!(transform.apply(StatValue::from_f64(100.0), &deps, &context).unwrap(), 120.0);
 /// ```
 #[derive(Debug, Clone)]
-pub struct ScalingTransform {
+pub struct ScalingTransform<N: StatNumeric = StatValue> {
     dependency: StatId,
-    scale_factor: f64,
+    scale_factor: N,
 }
 
 impl ScalingTransform {
@@ -850,7 +1468,23 @@ impl ScalingTransform {
     /// // ATK scales with STR: ATK = base + STR * 2
     /// let transform = ScalingTransform::new(str_id, 2.0);
     /// ```
-    pub fn new(dependency: StatId, scale_factor: f64) -> Self {
+    pub fn new(dependency: StatId, scale_factor: impl Into<StatValue>) -> Self {
+        Self::from_value(dependency, scale_factor.into())
+    }
+}
+
+impl<N: StatNumeric> ScalingTransform<N> {
+    /// Create a scaling transform for any numeric backend.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use zzstat::transform::ScalingTransform;
+    /// use zzstat::{FixedPoint, StatId};
+    ///
+    /// let transform = ScalingTransform::from_value(StatId::from_str("STR"), FixedPoint::from_f64(2.0));
+    /// ```
+    pub fn from_value(dependency: StatId, scale_factor: N) -> Self {
         Self {
             dependency,
             scale_factor,
@@ -858,7 +1492,7 @@ impl ScalingTransform {
     }
 }
 
-impl StatTransform for ScalingTransform {
+impl<N: StatNumeric> StatTransform<N> for ScalingTransform<N> {
     fn depends_on(&self) -> Vec<StatId> {
         vec![self.dependency.clone()]
     }
@@ -869,24 +1503,40 @@ impl StatTransform for ScalingTransform {
 
     fn apply(
         &self,
-        input: StatValue,
-        dependencies: &HashMap<StatId, StatValue>,
+        input: N,
+        dependencies: &HashMap<StatId, N>,
         _context: &StatContext,
-    ) -> Result<StatValue, StatError> {
+    ) -> Result<N, StatError> {
         let dep_value = dependencies
             .get(&self.dependency)
             .ok_or_else(|| StatError::MissingDependency(self.dependency.clone()))?;
-        Ok(input + (*dep_value * StatValue::from_f64(self.scale_factor)))
+        Ok(input + (*dep_value * self.scale_factor))
     }
 
     fn description(&self) -> String {
-        format!("scale({}, {:.2})", self.dependency, self.scale_factor)
+        format!(
+            "scale({}, {:.2})",
+            self.dependency,
+            self.scale_factor.to_f64()
+        )
+    }
+
+    fn contribution(
+        &self,
+        dependencies: &HashMap<StatId, N>,
+        _context: &StatContext,
+    ) -> Result<Option<Contribution<N>>, StatError> {
+        let dep_value = dependencies
+            .get(&self.dependency)
+            .ok_or_else(|| StatError::MissingDependency(self.dependency.clone()))?;
+        Ok(Some(Contribution::Delta(*dep_value * self.scale_factor)))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::numeric::FixedPoint;
 
     #[test]
     fn test_multiplicative_transform() {
@@ -1076,6 +1726,91 @@ mod tests {
         assert_eq!(max_only.max_bound(), Some(StatValue::from_f64(75.0)));
     }
 
+    #[test]
+    fn test_transform_bounds() {
+        let context = StatContext::new();
+        let deps = HashMap::new();
+
+        let clamp = ClampTransform::with_max(StatValue::from_f64(75.0));
+        assert_eq!(
+            clamp.bounds(&deps, &context),
+            Some(Bounds {
+                min: None,
+                max: Some(StatValue::from_f64(75.0)),
+            })
+        );
+        assert_eq!(
+            MultiplicativeTransform::new(2.0).bounds(&deps, &context),
+            None
+        );
+
+        let conditional = ConditionalTransform::new(
+            |ctx| ctx.get::<bool>("capped").unwrap_or(false),
+            Box::new(ClampTransform::new(0.0, 1e12)),
+            "cap",
+        );
+        assert_eq!(conditional.bounds(&deps, &context), Some(Bounds::default()));
+        let mut capped = StatContext::new();
+        capped.set("capped", true);
+        assert_eq!(
+            conditional.bounds(&deps, &capped),
+            Some(Bounds {
+                min: Some(StatValue::from_f64(0.0)),
+                max: Some(StatValue::from_f64(1e12)),
+            })
+        );
+    }
+
+    #[test]
+    fn test_transform_contributions() {
+        let context = StatContext::new();
+        let str_id = StatId::from_str("STR");
+        let mut deps = HashMap::new();
+        deps.insert(str_id.clone(), StatValue::from_f64(10.0));
+
+        assert_eq!(
+            AdditiveTransform::new(5.0)
+                .contribution(&deps, &context)
+                .unwrap(),
+            Some(Contribution::Delta(StatValue::from_f64(5.0)))
+        );
+        assert_eq!(
+            MultiplicativeTransform::new(1.5)
+                .contribution(&deps, &context)
+                .unwrap(),
+            Some(Contribution::Factor(StatValue::from_f64(1.5)))
+        );
+        assert_eq!(
+            ScalingTransform::new(str_id.clone(), 2.0)
+                .contribution(&deps, &context)
+                .unwrap(),
+            Some(Contribution::Delta(StatValue::from_f64(20.0)))
+        );
+        assert_eq!(
+            ClampTransform::with_max(StatValue::from_f64(75.0))
+                .contribution(&deps, &context)
+                .unwrap(),
+            Some(Contribution::Bound(Bounds {
+                min: None,
+                max: Some(StatValue::from_f64(75.0)),
+            }))
+        );
+        assert!(ScalingTransform::new(StatId::from_str("DEX"), 1.0)
+            .contribution(&deps, &context)
+            .is_err());
+
+        // Inactive conditionals contribute the neutral value of their kind
+        let inactive = ConditionalTransform::new(
+            |_| false,
+            Box::new(MultiplicativeTransform::new(1.5)),
+            "never",
+        );
+        assert_eq!(
+            inactive.contribution(&deps, &context).unwrap(),
+            Some(Contribution::Factor(StatValue::from_f64(1.0)))
+        );
+    }
+
     #[test]
     fn test_scaling_transform() {
         let str_id = StatId::from_str("STR");
@@ -1322,4 +2057,109 @@ mod tests {
         assert!(desc.contains("STR"));
         assert!(desc.contains("2.00"));
     }
+
+    #[test]
+    fn test_diminishing_modes() {
+        let raw = StatValue::from_f64(100.0);
+        let hyperbolic = DiminishingMode::Hyperbolic {
+            cap: 1.0.into(),
+            k: 100.0.into(),
+        };
+        assert_eq!(hyperbolic.apply(raw), StatValue::from_f64(0.5));
+
+        let exponential = DiminishingMode::Exponential {
+            cap: 50.0.into(),
+            k: 100.0.into(),
+        };
+        let expected = 50.0 * (1.0 - (-1.0f64).exp());
+        let tolerance = if cfg!(feature = "fixed-point") {
+            5e-3
+        } else {
+            1e-12
+        };
+        assert!((exponential.apply(raw).to_f64() - expected).abs() < tolerance);
+        // The fixed-point curve is computed without f64::exp: 50 × (1 - 0.3679)
+        assert_eq!(
+            exponential.apply(FixedPoint::from_int(100)),
+            "31.605".parse::<FixedPoint>().unwrap()
+        );
+
+        // Negative sums are not diminished
+        let negative = StatValue::from_f64(-20.0);
+        assert_eq!(hyperbolic.apply(negative), negative);
+        assert_eq!(exponential.apply(negative), negative);
+
+        // k must be positive
+        assert!(DiminishingMode::exponential(1.0, 0.0).is_err());
+        assert!(matches!(
+            DiminishingMode::hyperbolic(1.0, -100.0),
+            Err(StatError::InvalidCurve(_))
+        ));
+        assert!(StackRule::DiminishingReturns(DiminishingMode::Hyperbolic {
+            cap: 1.0.into(),
+            k: Decimal::ZERO,
+        })
+        .validate()
+        .is_err());
+        assert!(StackRule::DiminishingReturns(hyperbolic).validate().is_ok());
+    }
+
+    #[test]
+    fn test_piecewise_curve() {
+        let curve = PiecewiseCurve::new(&[(30.0, 0.9), (39.0, 0.8), (47.0, 0.7)]).unwrap();
+        let apply = |x: f64| curve.apply(StatValue::from_f64(x)).to_f64();
+        assert_eq!(apply(20.0), 20.0);
+        assert_eq!(apply(30.0), 30.0);
+        assert!((apply(35.0) - 34.5).abs() < 1e-12);
+        // 30 + 9 * 0.9 + 8 * 0.8 + 3 * 0.7
+        assert!((apply(50.0) - 46.6).abs() < 1e-12);
+
+        assert!(PiecewiseCurve::new(&[(10.0, 0.5), (10.0, 0.4)]).is_err());
+        assert!(PiecewiseCurve::new(&[(10.0, -0.5)]).is_err());
+
+        // Brackets can be given in any numeric type
+        let fixed = PiecewiseCurve::new(&[
+            (
+                FixedPoint::from_int(30),
+                "0.9".parse::<FixedPoint>().unwrap(),
+            ),
+            (
+                FixedPoint::from_int(39),
+                "0.8".parse::<FixedPoint>().unwrap(),
+            ),
+            (
+                FixedPoint::from_int(47),
+                "0.7".parse::<FixedPoint>().unwrap(),
+            ),
+        ])
+        .unwrap();
+        assert_eq!(fixed, curve);
+        let values = PiecewiseCurve::new(&[(StatValue::from_f64(30.0), StatValue::from_f64(0.9))]);
+        assert!(values.is_ok());
+        // Many brackets are fine
+        let many: Vec<(Decimal, Decimal)> = (0..100)
+            .map(|i| (Decimal::from(i * 10), Decimal::from(0.99)))
+            .collect();
+        assert!(PiecewiseCurve::new(&many).is_ok());
+    }
+
+    #[test]
+    fn test_diminishing_rule_serde() {
+        let rule = StackRule::DiminishingReturns(DiminishingMode::Piecewise(
+            PiecewiseCurve::new(&[(30.0, 0.9), (40.0, 0.8)]).unwrap(),
+        ));
+        let json = serde_json::to_string(&rule).unwrap();
+        assert_eq!(
+            json,
+            r#"{"DiminishingReturns":{"Piecewise":[["30","0.9"],["40","0.8"]]}}"#
+        );
+        assert_eq!(serde_json::from_str::<StackRule>(&json).unwrap(), rule);
+
+        // Plain numbers are accepted too
+        let numbers = r#"{"DiminishingReturns":{"Piecewise":[[30.0,0.9],[40,0.8]]}}"#;
+        assert_eq!(serde_json::from_str::<StackRule>(numbers).unwrap(), rule);
+
+        let unsorted = r#"{"DiminishingReturns":{"Piecewise":[[40.0,0.9],[30.0,0.8]]}}"#;
+        assert!(serde_json::from_str::<StackRule>(unsorted).is_err());
+    }
 }
@@ -25,7 +25,7 @@ fn test_bonus_add_flat() {
 
     assert_eq!(bonus.target, hp_id);
     assert_eq!(bonus.operation, zzstat::bonus::BonusOp::Add);
-    assert_eq!(bonus.value, zzstat::bonus::BonusValue::Flat(50.0));
+    assert_eq!(bonus.value, zzstat::bonus::BonusValue::Flat(50.0.into()));
     assert_eq!(bonus.phase, TransformPhase::Custom(3));
 }
 
@@ -38,7 +38,7 @@ fn test_bonus_add_percent() {
 
     assert_eq!(bonus.target, hp_id);
     assert_eq!(bonus.operation, zzstat::bonus::BonusOp::Add);
-    assert_eq!(bonus.value, zzstat::bonus::BonusValue::Percent(0.10));
+    assert_eq!(bonus.value, zzstat::bonus::BonusValue::Percent(0.10.into()));
 }
 
 #[test]
@@ -50,7 +50,7 @@ fn test_bonus_multiply() {
 
     assert_eq!(bonus.target, atk_id);
     assert_eq!(bonus.operation, zzstat::bonus::BonusOp::Multiply);
-    assert_eq!(bonus.value, zzstat::bonus::BonusValue::Percent(0.20));
+    assert_eq!(bonus.value, zzstat::bonus::BonusValue::Percent(0.20.into()));
 }
 
 #[test]
@@ -60,7 +60,7 @@ fn test_bonus_override() {
 
     assert_eq!(bonus.target, hp_id);
     assert_eq!(bonus.operation, zzstat::bonus::BonusOp::Override);
-    assert_eq!(bonus.value, zzstat::bonus::BonusValue::Flat(500.0));
+    assert_eq!(bonus.value, zzstat::bonus::BonusValue::Flat(500.0.into()));
 }
 
 #[test]
@@ -70,7 +70,7 @@ fn test_bonus_clamp_min() {
 
     assert_eq!(bonus.target, hp_id);
     assert_eq!(bonus.operation, zzstat::bonus::BonusOp::ClampMin);
-    assert_eq!(bonus.value, zzstat::bonus::BonusValue::Flat(100.0));
+    assert_eq!(bonus.value, zzstat::bonus::BonusValue::Flat(100.0.into()));
 }
 
 #[test]
@@ -80,7 +80,7 @@ fn test_bonus_clamp_max() {
 
     assert_eq!(bonus.target, crit_id);
     assert_eq!(bonus.operation, zzstat::bonus::BonusOp::ClampMax);
-    assert_eq!(bonus.value, zzstat::bonus::BonusValue::Flat(0.75));
+    assert_eq!(bonus.value, zzstat::bonus::BonusValue::Flat(0.75.into()));
 }
 
 // ============================================================================
@@ -94,7 +94,7 @@ fn test_compile_add_flat() {
         .flat(50.0)
         .in_phase(TransformPhase::Custom(3));
 
-    let compiled = compile_bonus::<f64>(&bonus);
+    let compiled = compile_bonus::<StatValue>(&bonus);
 
     assert_eq!(compiled.stat, hp_id);
     assert_eq!(compiled.phase, TransformPhase::Custom(3));
@@ -108,7 +108,7 @@ fn test_compile_add_percent() {
         .percent(0.10)
         .in_phase(TransformPhase::Custom(3));
 
-    let compiled = compile_bonus::<f64>(&bonus);
+    let compiled = compile_bonus::<StatValue>(&bonus);
 
     assert_eq!(compiled.stat, hp_id);
     assert_eq!(compiled.phase, TransformPhase::Custom(3));
@@ -122,7 +122,7 @@ fn test_compile_multiply() {
         .percent(0.20)
         .in_phase(TransformPhase::Custom(3));
 
-    let compiled = compile_bonus::<f64>(&bonus);
+    let compiled = compile_bonus::<StatValue>(&bonus);
 
     assert_eq!(compiled.stat, atk_id);
     assert_eq!(compiled.phase, TransformPhase::Custom(3));
@@ -134,7 +134,7 @@ fn test_compile_override() {
     let hp_id = StatId::from_str("HP");
     let bonus = Bonus::r#override(hp_id.clone(), 500.0).in_phase(TransformPhase::Custom(4));
 
-    let compiled = compile_bonus::<f64>(&bonus);
+    let compiled = compile_bonus::<StatValue>(&bonus);
 
     assert_eq!(compiled.stat, hp_id);
     assert_eq!(compiled.phase, TransformPhase::Custom(4));
@@ -146,7 +146,7 @@ fn test_compile_clamp_min() {
     let hp_id = StatId::from_str("HP");
     let bonus = Bonus::clamp_min(hp_id.clone(), 100.0).in_phase(TransformPhase::Final);
 
-    let compiled = compile_bonus::<f64>(&bonus);
+    let compiled = compile_bonus::<StatValue>(&bonus);
 
     assert_eq!(compiled.stat, hp_id);
     assert_eq!(compiled.phase, TransformPhase::Final);
@@ -158,7 +158,7 @@ fn test_compile_clamp_max() {
     let crit_id = StatId::from_str("CRIT_CHANCE");
     let bonus = Bonus::clamp_max(crit_id.clone(), 0.75).in_phase(TransformPhase::Final);
 
-    let compiled = compile_bonus::<f64>(&bonus);
+    let compiled = compile_bonus::<StatValue>(&bonus);
 
     assert_eq!(compiled.stat, crit_id);
     assert_eq!(compiled.phase, TransformPhase::Final);
@@ -174,12 +174,12 @@ fn test_apply_compiled_bonus_to_resolver() {
     let mut resolver = StatResolver::new();
     let hp_id = StatId::from_str("HP");
 
-    resolver.register_source(hp_id.clone(), Box::new(ConstantSource(100.0)));
+    resolver.register_source(hp_id.clone(), Box::new(ConstantSource::new(100.0)));
 
     let bonus = Bonus::add(hp_id.clone())
         .flat(50.0)
         .in_phase(TransformPhase::Additive);
-    let compiled = compile_bonus::<f64>(&bonus);
+    let compiled = compile_bonus::<StatValue>(&bonus);
 
     apply_compiled_bonus(&mut resolver, &compiled);
 
@@ -194,10 +194,10 @@ fn test_apply_compiled_bonuses_to_fork() {
     let hp_id = StatId::from_str("HP");
     let atk_id = StatId::from_str("ATK");
 
-    base_resolver.register_source(hp_id.clone(), Box::new(ConstantSource(1000.0)));
-    base_resolver.register_source(atk_id.clone(), Box::new(ConstantSource(100.0)));
+    base_resolver.register_source(hp_id.clone(), Box::new(ConstantSource::new(1000.0)));
+    base_resolver.register_source(atk_id.clone(), Box::new(ConstantSource::new(100.0)));
 
-    let bonuses = vec![
+    let bonuses = [
         Bonus::add(hp_id.clone())
             .flat(50.0)
             .in_phase(TransformPhase::Custom(3)),
@@ -206,7 +206,7 @@ fn test_apply_compiled_bonuses_to_fork() {
             .in_phase(TransformPhase::Custom(3)),
     ];
 
-    let compiled: Vec<_> = bonuses.iter().map(|b| compile_bonus::<f64>(b)).collect();
+    let compiled: Vec<_> = bonuses.iter().map(compile_bonus::<StatValue>).collect();
 
     let mut fork = base_resolver.fork();
     apply_compiled_bonuses(&mut fork, &compiled);
@@ -225,12 +225,12 @@ fn test_fork_isolation() {
     let mut base_resolver = StatResolver::new();
     let hp_id = StatId::from_str("HP");
 
-    base_resolver.register_source(hp_id.clone(), Box::new(ConstantSource(1000.0)));
+    base_resolver.register_source(hp_id.clone(), Box::new(ConstantSource::new(1000.0)));
 
     let bonus = Bonus::add(hp_id.clone())
         .flat(200.0)
         .in_phase(TransformPhase::Custom(3));
-    let compiled = compile_bonus::<f64>(&bonus);
+    let compiled = compile_bonus::<StatValue>(&bonus);
 
     let mut fork = base_resolver.fork();
     apply_compiled_bonus(&mut fork, &compiled);
@@ -257,10 +257,10 @@ fn test_override_resets_value_in_phase() {
     let hp_id = StatId::from_str("HP");
 
     // Base HP = 1000
-    resolver.register_source(hp_id.clone(), Box::new(ConstantSource(1000.0)));
+    resolver.register_source(hp_id.clone(), Box::new(ConstantSource::new(1000.0)));
 
     // Item phase: +200 HP, +10% HP
-    let item_bonuses = vec![
+    let item_bonuses = [
         Bonus::add(hp_id.clone())
             .flat(200.0)
             .in_phase(TransformPhase::Custom(3)),
@@ -270,7 +270,7 @@ fn test_override_resets_value_in_phase() {
     ];
     let item_compiled: Vec<_> = item_bonuses
         .iter()
-        .map(|b| compile_bonus::<f64>(b))
+        .map(compile_bonus::<StatValue>)
         .collect();
 
     let mut item_fork = resolver.fork();
@@ -282,7 +282,7 @@ fn test_override_resets_value_in_phase() {
     assert_eq!(item_stats.value.to_f64(), 1320.0);
 
     // Buff phase: Override HP = 500, then +50% HP
-    let buff_bonuses = vec![
+    let buff_bonuses = [
         Bonus::r#override(hp_id.clone(), 500.0).in_phase(TransformPhase::Custom(4)),
         Bonus::mul(hp_id.clone())
             .percent(0.50)
@@ -290,7 +290,7 @@ fn test_override_resets_value_in_phase() {
     ];
     let buff_compiled: Vec<_> = buff_bonuses
         .iter()
-        .map(|b| compile_bonus::<f64>(b))
+        .map(compile_bonus::<StatValue>)
         .collect();
 
     let mut buff_fork = item_fork.fork();
@@ -308,14 +308,14 @@ fn test_override_does_not_affect_previous_phases() {
     let hp_id = StatId::from_str("HP");
 
     // Base HP = 1000
-    resolver.register_source(hp_id.clone(), Box::new(ConstantSource(1000.0)));
+    resolver.register_source(hp_id.clone(), Box::new(ConstantSource::new(1000.0)));
 
     // Phase 3: +200 HP
     let phase3_bonus = Bonus::add(hp_id.clone())
         .flat(200.0)
         .in_phase(TransformPhase::Custom(3));
     let mut phase3_fork = resolver.fork();
-    apply_compiled_bonus(&mut phase3_fork, &compile_bonus::<f64>(&phase3_bonus));
+    apply_compiled_bonus(&mut phase3_fork, &compile_bonus::<StatValue>(&phase3_bonus));
 
     let context = StatContext::new();
     let phase3_stats = phase3_fork.resolve(&hp_id, &context).unwrap();
@@ -324,7 +324,7 @@ fn test_override_does_not_affect_previous_phases() {
     // Phase 4: Override to 500
     let phase4_bonus = Bonus::r#override(hp_id.clone(), 500.0).in_phase(TransformPhase::Custom(4));
     let mut phase4_fork = phase3_fork.fork();
-    apply_compiled_bonus(&mut phase4_fork, &compile_bonus::<f64>(&phase4_bonus));
+    apply_compiled_bonus(&mut phase4_fork, &compile_bonus::<StatValue>(&phase4_bonus));
 
     let phase4_stats = phase4_fork.resolve(&hp_id, &context).unwrap();
     // Override resets to 500, but phase 3 result (1200) is the input to phase 4
@@ -338,16 +338,16 @@ fn test_override_composes_with_other_transforms() {
     let mut resolver = StatResolver::new();
     let hp_id = StatId::from_str("HP");
 
-    resolver.register_source(hp_id.clone(), Box::new(ConstantSource(1000.0)));
+    resolver.register_source(hp_id.clone(), Box::new(ConstantSource::new(1000.0)));
 
     // Same phase: Override to 500, then +50% HP
-    let bonuses = vec![
+    let bonuses = [
         Bonus::r#override(hp_id.clone(), 500.0).in_phase(TransformPhase::Custom(4)),
         Bonus::mul(hp_id.clone())
             .percent(0.50)
             .in_phase(TransformPhase::Custom(4)),
     ];
-    let compiled: Vec<_> = bonuses.iter().map(|b| compile_bonus::<f64>(b)).collect();
+    let compiled: Vec<_> = bonuses.iter().map(compile_bonus::<StatValue>).collect();
 
     let mut fork = resolver.fork();
     apply_compiled_bonuses(&mut fork, &compiled);
@@ -364,15 +364,15 @@ fn test_multiple_overrides_last_wins() {
     let mut resolver = StatResolver::new();
     let hp_id = StatId::from_str("HP");
 
-    resolver.register_source(hp_id.clone(), Box::new(ConstantSource(1000.0)));
+    resolver.register_source(hp_id.clone(), Box::new(ConstantSource::new(1000.0)));
 
     // Multiple overrides in same phase
-    let bonuses = vec![
+    let bonuses = [
         Bonus::r#override(hp_id.clone(), 200.0).in_phase(TransformPhase::Custom(4)),
         Bonus::r#override(hp_id.clone(), 300.0).in_phase(TransformPhase::Custom(4)),
         Bonus::r#override(hp_id.clone(), 400.0).in_phase(TransformPhase::Custom(4)),
     ];
-    let compiled: Vec<_> = bonuses.iter().map(|b| compile_bonus::<f64>(b)).collect();
+    let compiled: Vec<_> = bonuses.iter().map(compile_bonus::<StatValue>).collect();
 
     let mut fork = resolver.fork();
     apply_compiled_bonuses(&mut fork, &compiled);
@@ -390,7 +390,7 @@ fn test_override_does_not_mutate_resolver_state() {
     let mut resolver = StatResolver::new();
     let hp_id = StatId::from_str("HP");
 
-    resolver.register_source(hp_id.clone(), Box::new(ConstantSource(1000.0)));
+    resolver.register_source(hp_id.clone(), Box::new(ConstantSource::new(1000.0)));
 
     // Add some transforms first
     let add_bonus = Bonus::add(hp_id.clone())
@@ -401,8 +401,8 @@ fn test_override_does_not_mutate_resolver_state() {
         .in_phase(TransformPhase::Custom(3));
 
     let mut fork = resolver.fork();
-    apply_compiled_bonus(&mut fork, &compile_bonus::<f64>(&add_bonus));
-    apply_compiled_bonus(&mut fork, &compile_bonus::<f64>(&mul_bonus));
+    apply_compiled_bonus(&mut fork, &compile_bonus::<StatValue>(&add_bonus));
+    apply_compiled_bonus(&mut fork, &compile_bonus::<StatValue>(&mul_bonus));
 
     let context = StatContext::new();
     let before_override = fork.resolve(&hp_id, &context).unwrap();
@@ -411,7 +411,7 @@ fn test_override_does_not_mutate_resolver_state() {
     // Add override in different phase
     let override_bonus =
         Bonus::r#override(hp_id.clone(), 500.0).in_phase(TransformPhase::Custom(4));
-    apply_compiled_bonus(&mut fork, &compile_bonus::<f64>(&override_bonus));
+    apply_compiled_bonus(&mut fork, &compile_bonus::<StatValue>(&override_bonus));
 
     let after_override = fork.resolve(&hp_id, &context).unwrap();
     // Override resets to 500 in phase 4 (ignores phase 3 result of 1320)
@@ -428,20 +428,20 @@ fn test_override_works_with_resolver_forks() {
     let mut base_resolver = StatResolver::new();
     let hp_id = StatId::from_str("HP");
 
-    base_resolver.register_source(hp_id.clone(), Box::new(ConstantSource(1000.0)));
+    base_resolver.register_source(hp_id.clone(), Box::new(ConstantSource::new(1000.0)));
 
     // Fork 1: Add +200 HP
     let add_bonus = Bonus::add(hp_id.clone())
         .flat(200.0)
         .in_phase(TransformPhase::Custom(3));
     let mut fork1 = base_resolver.fork();
-    apply_compiled_bonus(&mut fork1, &compile_bonus::<f64>(&add_bonus));
+    apply_compiled_bonus(&mut fork1, &compile_bonus::<StatValue>(&add_bonus));
 
     // Fork 2: Override to 500
     let override_bonus =
         Bonus::r#override(hp_id.clone(), 500.0).in_phase(TransformPhase::Custom(4));
     let mut fork2 = base_resolver.fork();
-    apply_compiled_bonus(&mut fork2, &compile_bonus::<f64>(&override_bonus));
+    apply_compiled_bonus(&mut fork2, &compile_bonus::<StatValue>(&override_bonus));
 
     let context = StatContext::new();
 
@@ -472,10 +472,10 @@ fn test_clamp_bonuses() {
     let crit_id = StatId::from_str("CRIT_CHANCE");
 
     // Set crit chance to 100% (will be clamped)
-    resolver.register_source(crit_id.clone(), Box::new(ConstantSource(1.0)));
+    resolver.register_source(crit_id.clone(), Box::new(ConstantSource::new(1.0)));
 
     let clamp_bonus = Bonus::clamp_max(crit_id.clone(), 0.75).in_phase(TransformPhase::Final);
-    let compiled = compile_bonus::<f64>(&clamp_bonus);
+    let compiled = compile_bonus::<StatValue>(&clamp_bonus);
 
     let mut fork = resolver.fork();
     apply_compiled_bonus(&mut fork, &compiled);
@@ -492,8 +492,8 @@ fn test_complete_item_system() {
     let hp_id = StatId::from_str("HP");
     let atk_id = StatId::from_str("ATK");
 
-    base_resolver.register_source(hp_id.clone(), Box::new(ConstantSource(1000.0)));
-    base_resolver.register_source(atk_id.clone(), Box::new(ConstantSource(100.0)));
+    base_resolver.register_source(hp_id.clone(), Box::new(ConstantSource::new(1000.0)));
+    base_resolver.register_source(atk_id.clone(), Box::new(ConstantSource::new(100.0)));
 
     // Define item bonuses
     let sword_bonuses = vec![
@@ -513,10 +513,7 @@ fn test_complete_item_system() {
     let mut all_bonuses = Vec::new();
     all_bonuses.extend(sword_bonuses);
     all_bonuses.extend(armor_bonuses);
-    let all_compiled: Vec<_> = all_bonuses
-        .iter()
-        .map(|b| compile_bonus::<f64>(b))
-        .collect();
+    let all_compiled: Vec<_> = all_bonuses.iter().map(compile_bonus::<StatValue>).collect();
 
     // Apply to character
     let mut equipped_fork = base_resolver.fork();
@@ -533,3 +530,172 @@ fn test_complete_item_system() {
     // ATK: (100 + 25) * 1.15 = 143.75
     assert_eq!(stats[&atk_id].value.to_f64(), 143.75);
 }
+
+// ============================================================================
+// Serialization Tests
+// ============================================================================
+
+#[test]
+fn test_bonus_serde_roundtrip() {
+    let hp_id = StatId::from_str("HP");
+    let bonuses = vec![
+        Bonus::add(hp_id.clone())
+            .flat(50.0)
+            .in_phase(TransformPhase::Custom(3)),
+        Bonus::add(hp_id.clone())
+            .percent(0.10)
+            .in_phase(TransformPhase::Additive),
+        Bonus::mul(hp_id.clone())
+            .percent(0.25)
+            .in_phase(TransformPhase::Multiplicative),
+        Bonus::r#override(hp_id.clone(), 1.0).in_phase(TransformPhase::Final),
+        Bonus::clamp_max(hp_id.clone(), 999.0).in_phase(TransformPhase::Final),
+    ];
+
+    for bonus in &bonuses {
+        let json = serde_json::to_string(bonus).unwrap();
+        let back: Bonus = serde_json::from_str(&json).unwrap();
+        assert_eq!(&back, bonus);
+    }
+
+    // Values are written as decimal strings whatever the numeric backend
+    let json = serde_json::to_string(&bonuses[1]).unwrap();
+    assert_eq!(
+        json,
+        r#"{"target":"HP","operation":"Add","value":{"Percent":"0.1"},"phase":"Additive"}"#
+    );
+    let number: Bonus = serde_json::from_str(
+        r#"{"target":"HP","operation":"Add","value":{"Percent":0.1},"phase":"Additive"}"#,
+    )
+    .unwrap();
+    assert_eq!(number, bonuses[1]);
+}
+
+#[test]
+fn test_compiled_bonus_serde_roundtrip() {
+    let atk_id = StatId::from_str("ATK");
+    let compiled = compile_bonus::<StatValue>(
+        &Bonus::mul(atk_id.clone())
+            .percent(0.10)
+            .in_phase(TransformPhase::Multiplicative),
+    );
+
+    let json = serde_json::to_string(&compiled).unwrap();
+    let back: zzstat::bonus::CompiledBonus<StatValue> = serde_json::from_str(&json).unwrap();
+    assert_eq!(back, compiled);
+    assert_eq!(back.stack_rule, StackRule::Multiplicative);
+
+    // A deserialized bonus applies just like the original
+    let mut resolver = StatResolver::new();
+    resolver.register_source(atk_id.clone(), Box::new(ConstantSource::new(100.0)));
+    let mut fork = resolver.fork();
+    apply_compiled_bonus(&mut fork, &back);
+    let context = StatContext::new();
+    assert!((fork.resolve(&atk_id, &context).unwrap().value.to_f64() - 110.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_fixed_point_compiled_bonus_is_exact() {
+    let crit_id = StatId::from_str("CRIT");
+    let bonuses = [
+        Bonus::add(crit_id.clone())
+            .flat(0.1)
+            .in_phase(TransformPhase::Additive),
+        Bonus::mul(crit_id.clone())
+            .percent(0.15)
+            .in_phase(TransformPhase::Multiplicative),
+    ];
+    let compiled: Vec<_> = bonuses.iter().map(compile_bonus::<FixedPoint>).collect();
+
+    // The compiled values are fixed-point and survive serde bit-exactly
+    let json = serde_json::to_string(&compiled).unwrap();
+    let back: Vec<zzstat::bonus::CompiledBonus<FixedPoint>> = serde_json::from_str(&json).unwrap();
+    assert_eq!(back, compiled);
+    assert_eq!(json, serde_json::to_string(&back).unwrap());
+
+    let mut resolver: StatResolver<FixedPoint> = StatResolver::default();
+    resolver.register_source(
+        crit_id.clone(),
+        Box::new(ConstantSource::new("0.2".parse::<FixedPoint>().unwrap())),
+    );
+    apply_compiled_bonuses(&mut resolver, &back);
+    let context = StatContext::new();
+    // (0.2 + 0.1) * 1.15 = 0.345, exactly
+    assert_eq!(
+        resolver.resolve(&crit_id, &context).unwrap().value,
+        FixedPoint::new(345, 3)
+    );
+}
+
+#[test]
+fn test_bonus_template_versioning() {
+    use zzstat::bonus::{BonusTemplate, BONUS_FORMAT_VERSION};
+
+    let atk_id = StatId::from_str("ATK");
+    let template = BonusTemplate::new(vec![
+        Bonus::add(atk_id.clone())
+            .flat(25.0)
+            .in_phase(TransformPhase::Additive),
+        Bonus::mul(atk_id.clone())
+            .percent(0.2)
+            .in_phase(TransformPhase::Multiplicative),
+    ]);
+    assert_eq!(template.version, BONUS_FORMAT_VERSION);
+
+    let json = template.to_json().unwrap();
+    let loaded = BonusTemplate::from_json(&json).unwrap();
+    assert_eq!(loaded, template);
+
+    let mut resolver = StatResolver::new();
+    resolver.register_source(atk_id.clone(), Box::new(ConstantSource::new(100.0)));
+    apply_compiled_bonuses(&mut resolver, &loaded.compile::<StatValue>());
+    let context = StatContext::new();
+    assert_eq!(resolver.resolve(&atk_id, &context).unwrap().value, 150.0);
+
+    // Data from another format version is rejected even if its shape differs
+    let future = r#"{"version": 99, "bonuses": {"something": "new"}}"#;
+    assert_eq!(
+        BonusTemplate::from_json(future),
+        Err(StatError::UnsupportedVersion {
+            found: 99,
+            supported: BONUS_FORMAT_VERSION
+        })
+    );
+
+    assert!(matches!(
+        BonusTemplate::from_json(r#"{"version": 1, "bonuses": [{"target": "ATK"}]}"#),
+        Err(StatError::Definition { .. })
+    ));
+}
+
+#[test]
+fn test_bonus_template_wire_format() {
+    use zzstat::bonus::BonusTemplate;
+
+    let atk_id = StatId::from_str("ATK");
+    let template = BonusTemplate::new(vec![
+        Bonus::add(atk_id.clone())
+            .flat(25.0)
+            .in_phase(TransformPhase::Additive),
+        Bonus::mul(atk_id.clone())
+            .percent(0.15)
+            .in_phase(TransformPhase::Multiplicative),
+    ]);
+
+    // The same bytes under every feature set
+    let json = concat!(
+        r#"{"version":2,"bonuses":["#,
+        r#"{"target":"ATK","operation":"Add","value":{"Flat":"25"},"phase":"Additive"},"#,
+        r#"{"target":"ATK","operation":"Multiply","value":{"Percent":"0.15"},"phase":"Multiplicative"}"#,
+        r#"]}"#
+    );
+    assert_eq!(template.to_json().unwrap(), json);
+    assert_eq!(BonusTemplate::from_json(json).unwrap(), template);
+
+    // Version 1 wrote values as numbers
+    let v1 = json
+        .replace(r#""version":2"#, r#""version":1"#)
+        .replace(r#""25""#, "25.0")
+        .replace(r#""0.15""#, "0.15");
+    assert_eq!(BonusTemplate::from_json(&v1).unwrap(), template);
+}
@@ -1,7 +1,9 @@
-use zzstat::source::ConstantSource;
+use zzstat::definition::StatDefinitions;
+use zzstat::expression::ExpressionTransform;
+use zzstat::source::{ConstantSource, LabeledSource};
 use zzstat::transform::{
-    AdditiveTransform, ClampTransform, MultiplicativeTransform, ScalingTransform, StackRule,
-    TransformPhase,
+    AdditiveTransform, ClampTransform, ConditionalTransform, MultiplicativeTransform,
+    OriginTransform, ScalingTransform, StackRule, TransformPhase,
 };
 use zzstat::*;
 
@@ -18,9 +20,9 @@ fn test_complete_pipeline() {
     let dps_id = StatId::from_str("DPS");
 
     // Base stats
-    resolver.register_source(str_id.clone(), Box::new(ConstantSource(10.0)));
-    resolver.register_source(dex_id.clone(), Box::new(ConstantSource(15.0)));
-    resolver.register_source(atk_id.clone(), Box::new(ConstantSource(50.0)));
+    resolver.register_source(str_id.clone(), Box::new(ConstantSource::new(10.0)));
+    resolver.register_source(dex_id.clone(), Box::new(ConstantSource::new(15.0)));
+    resolver.register_source(atk_id.clone(), Box::new(ConstantSource::new(50.0)));
 
     // ATK scales with STR
     resolver.register_transform(
@@ -29,14 +31,14 @@ fn test_complete_pipeline() {
     );
 
     // CRIT scales with DEX
-    resolver.register_source(crit_id.clone(), Box::new(ConstantSource(5.0)));
+    resolver.register_source(crit_id.clone(), Box::new(ConstantSource::new(5.0)));
     resolver.register_transform(
         crit_id.clone(),
         Box::new(ScalingTransform::new(dex_id.clone(), 1.5)),
     );
 
     // DPS depends on ATK and CRIT
-    resolver.register_source(dps_id.clone(), Box::new(ConstantSource(0.0)));
+    resolver.register_source(dps_id.clone(), Box::new(ConstantSource::new(0.0)));
     resolver.register_transform(
         dps_id.clone(),
         Box::new(ScalingTransform::new(atk_id.clone(), 1.0)),
@@ -79,9 +81,9 @@ fn test_additive_sources() {
     let hp_id = StatId::from_str("HP");
 
     // Multiple sources should be summed
-    resolver.register_source(hp_id.clone(), Box::new(ConstantSource(100.0)));
-    resolver.register_source(hp_id.clone(), Box::new(ConstantSource(50.0)));
-    resolver.register_source(hp_id.clone(), Box::new(ConstantSource(25.0)));
+    resolver.register_source(hp_id.clone(), Box::new(ConstantSource::new(100.0)));
+    resolver.register_source(hp_id.clone(), Box::new(ConstantSource::new(50.0)));
+    resolver.register_source(hp_id.clone(), Box::new(ConstantSource::new(25.0)));
 
     let context = StatContext::new();
     let resolved = resolver.resolve(&hp_id, &context).unwrap();
@@ -96,7 +98,7 @@ fn test_transform_chain() {
     let mut resolver = StatResolver::new();
     let atk_id = StatId::from_str("ATK");
 
-    resolver.register_source(atk_id.clone(), Box::new(ConstantSource(100.0)));
+    resolver.register_source(atk_id.clone(), Box::new(ConstantSource::new(100.0)));
 
     // Apply multiple transforms in sequence
     resolver.register_transform(atk_id.clone(), Box::new(MultiplicativeTransform::new(1.5)));
@@ -121,7 +123,7 @@ fn test_cache_behavior() {
     let mut resolver = StatResolver::new();
     let hp_id = StatId::from_str("HP");
 
-    resolver.register_source(hp_id.clone(), Box::new(ConstantSource(100.0)));
+    resolver.register_source(hp_id.clone(), Box::new(ConstantSource::new(100.0)));
 
     let context = StatContext::new();
 
@@ -134,7 +136,7 @@ fn test_cache_behavior() {
     assert_eq!(resolved2.value, 100.0);
 
     // Add new source and invalidate
-    resolver.register_source(hp_id.clone(), Box::new(ConstantSource(50.0)));
+    resolver.register_source(hp_id.clone(), Box::new(ConstantSource::new(50.0)));
 
     // Should recalculate
     let resolved3 = resolver.resolve(&hp_id, &context).unwrap();
@@ -150,9 +152,9 @@ fn test_complex_dependency_chain() {
     let mid_id = StatId::from_str("MID");
     let top_id = StatId::from_str("TOP");
 
-    resolver.register_source(base_id.clone(), Box::new(ConstantSource(10.0)));
-    resolver.register_source(mid_id.clone(), Box::new(ConstantSource(20.0)));
-    resolver.register_source(top_id.clone(), Box::new(ConstantSource(30.0)));
+    resolver.register_source(base_id.clone(), Box::new(ConstantSource::new(10.0)));
+    resolver.register_source(mid_id.clone(), Box::new(ConstantSource::new(20.0)));
+    resolver.register_source(top_id.clone(), Box::new(ConstantSource::new(30.0)));
 
     // MID depends on BASE
     resolver.register_transform(
@@ -185,8 +187,8 @@ fn test_breakdown_information() {
     let mut resolver = StatResolver::new();
     let atk_id = StatId::from_str("ATK");
 
-    resolver.register_source(atk_id.clone(), Box::new(ConstantSource(100.0)));
-    resolver.register_source(atk_id.clone(), Box::new(ConstantSource(50.0)));
+    resolver.register_source(atk_id.clone(), Box::new(ConstantSource::new(100.0)));
+    resolver.register_source(atk_id.clone(), Box::new(ConstantSource::new(50.0)));
     resolver.register_transform(atk_id.clone(), Box::new(MultiplicativeTransform::new(1.5)));
 
     let context = StatContext::new();
@@ -208,13 +210,13 @@ fn test_resolver_fork() {
     let hp_id = StatId::from_str("HP");
 
     // Register source in base
-    base.register_source(hp_id.clone(), Box::new(ConstantSource(100.0)));
+    base.register_source(hp_id.clone(), Box::new(ConstantSource::new(100.0)));
 
     // Fork the resolver
     let mut fork = base.fork();
 
     // Add source to fork (shouldn't affect base)
-    fork.register_source(hp_id.clone(), Box::new(ConstantSource(50.0)));
+    fork.register_source(hp_id.clone(), Box::new(ConstantSource::new(50.0)));
 
     let context = StatContext::new();
 
@@ -233,7 +235,7 @@ fn test_resolver_fork_with_transforms() {
     let mut base = StatResolver::new();
     let atk_id = StatId::from_str("ATK");
 
-    base.register_source(atk_id.clone(), Box::new(ConstantSource(100.0)));
+    base.register_source(atk_id.clone(), Box::new(ConstantSource::new(100.0)));
     base.register_transform(atk_id.clone(), Box::new(MultiplicativeTransform::new(1.5)));
 
     let mut fork = base.fork();
@@ -250,6 +252,208 @@ fn test_resolver_fork_with_transforms() {
     assert!((fork_resolved.value.to_f64() - 180.0).abs() < 0.01);
 }
 
+/// Test forking a fork: gear fork, then a buff fork that keeps the gear.
+#[test]
+fn test_nested_fork_keeps_gear() {
+    let mut base = StatResolver::new();
+    let atk_id = StatId::from_str("ATK");
+    base.register_source(atk_id.clone(), Box::new(ConstantSource::new(100.0)));
+
+    let mut gear = base.fork();
+    gear.register_source(atk_id.clone(), Box::new(ConstantSource::new(50.0)));
+
+    let mut combat = gear.fork();
+    combat.register_transform(atk_id.clone(), Box::new(MultiplicativeTransform::new(1.2)));
+
+    let context = StatContext::new();
+
+    // Combat: (100 + 50) * 1.2 = 180
+    let combat_resolved = combat.resolve(&atk_id, &context).unwrap();
+    assert!((combat_resolved.value.to_f64() - 180.0).abs() < 0.01);
+
+    // Gear and base are unaffected
+    assert_eq!(gear.resolve(&atk_id, &context).unwrap().value, 150.0);
+    assert_eq!(base.resolve(&atk_id, &context).unwrap().value, 100.0);
+}
+
+/// Test three levels of forks, each adding to everything above it.
+#[test]
+fn test_three_level_fork() {
+    let mut base = StatResolver::new();
+    let hp_id = StatId::from_str("HP");
+    base.register_source(hp_id.clone(), Box::new(ConstantSource::new(100.0)));
+
+    let mut level1 = base.fork();
+    level1.register_source(hp_id.clone(), Box::new(ConstantSource::new(20.0)));
+    let mut level2 = level1.fork();
+    level2.register_transform(hp_id.clone(), Box::new(MultiplicativeTransform::new(2.0)));
+    let mut level3 = level2.fork();
+    level3.register_transform(hp_id.clone(), Box::new(AdditiveTransform::new(10.0)));
+
+    let context = StatContext::new();
+
+    // (100 + 20 + 10) * 2 = 260, no matter how often it is re-forked
+    for _ in 0..3 {
+        let mut snapshot = level3.fork();
+        assert_eq!(snapshot.resolve(&hp_id, &context).unwrap().value, 260.0);
+    }
+    assert_eq!(level3.resolve(&hp_id, &context).unwrap().value, 260.0);
+    assert_eq!(level2.resolve(&hp_id, &context).unwrap().value, 240.0);
+    assert_eq!(level1.resolve(&hp_id, &context).unwrap().value, 120.0);
+    assert_eq!(base.resolve(&hp_id, &context).unwrap().value, 100.0);
+}
+
+/// Test confirming a gear preview by committing the fork into the base.
+#[test]
+fn test_commit_gear_preview() {
+    let mut base = StatResolver::new();
+    let atk_id = StatId::from_str("ATK");
+    let def_id = StatId::from_str("DEF");
+    base.register_source(atk_id.clone(), Box::new(ConstantSource::new(100.0)));
+    base.register_source(def_id.clone(), Box::new(ConstantSource::new(50.0)));
+
+    let context = StatContext::new();
+
+    let mut preview = base.fork();
+    let sword = preview.register_source(atk_id.clone(), Box::new(ConstantSource::new(25.0)));
+    assert_eq!(preview.resolve(&atk_id, &context).unwrap().value, 125.0);
+
+    // Player confirms the preview
+    let changed = preview.commit_into(&mut base);
+    assert!(changed.contains(&atk_id));
+    assert!(!changed.contains(&def_id));
+
+    assert_eq!(base.resolve(&atk_id, &context).unwrap().value, 125.0);
+    assert_eq!(base.resolve(&def_id, &context).unwrap().value, 50.0);
+
+    // Unequip later with the handle from the preview
+    assert!(base.remove_source(&sword));
+    assert_eq!(base.resolve(&atk_id, &context).unwrap().value, 100.0);
+}
+
+/// Test rejecting a cyclic transform at registration time.
+#[test]
+fn test_try_register_transform_cycle() {
+    let mut resolver = StatResolver::new();
+    let str_id = StatId::from_str("STR");
+    let atk_id = StatId::from_str("ATK");
+
+    resolver.register_source(str_id.clone(), Box::new(ConstantSource::new(10.0)));
+    resolver.register_source(atk_id.clone(), Box::new(ConstantSource::new(100.0)));
+    resolver
+        .try_register_transform(
+            atk_id.clone(),
+            Box::new(ScalingTransform::new(str_id.clone(), 2.0)),
+        )
+        .unwrap();
+
+    let result = resolver.try_register_transform(
+        str_id.clone(),
+        Box::new(ScalingTransform::new(atk_id.clone(), 0.1)),
+    );
+    match result {
+        Err(StatError::Cycle { path }) => {
+            assert_eq!(path, vec![str_id.clone(), atk_id.clone(), str_id.clone()]);
+        }
+        other => panic!("Expected Cycle error, got {:?}", other),
+    }
+
+    // The rejected transform was not registered
+    let context = StatContext::new();
+    assert_eq!(resolver.resolve(&atk_id, &context).unwrap().value, 120.0);
+}
+
+/// Test that source descriptions and tags flow into the breakdown.
+#[test]
+fn test_labeled_sources_in_breakdown() {
+    let mut resolver = StatResolver::new();
+    let atk_id = StatId::from_str("ATK");
+
+    resolver.register_source(
+        atk_id.clone(),
+        Box::new(LabeledSource::new(
+            "Base",
+            Box::new(ConstantSource::new(100.0)),
+        )),
+    );
+    resolver.register_source(
+        atk_id.clone(),
+        Box::new(
+            LabeledSource::new("Iron Sword", Box::new(ConstantSource::new(20.0)))
+                .with_tag("item_id", "iron_sword")
+                .with_tag("slot", "main_hand"),
+        ),
+    );
+    resolver.register_source(atk_id.clone(), Box::new(ConstantSource::new(5.0)));
+
+    let context = StatContext::new();
+    let resolved = resolver.resolve(&atk_id, &context).unwrap();
+
+    assert_eq!(resolved.value, 125.0);
+    assert_eq!(resolved.sources[0].0, "Base");
+    assert_eq!(resolved.sources[1].0, "Iron Sword");
+    assert_eq!(resolved.sources[2].0, "Source #3");
+    assert_eq!(resolved.source_tags[1]["item_id"], "iron_sword");
+    assert!(resolved.source_tags[2].is_empty());
+
+    let from_sword: Vec<_> = resolved.sources_with_tag("item_id", "iron_sword").collect();
+    assert_eq!(
+        from_sword,
+        vec![&("Iron Sword".to_string(), StatValue::from_f64(20.0))]
+    );
+}
+
+/// Test the typed breakdown tree, including origins and serialization.
+#[test]
+fn test_typed_breakdown() {
+    let mut resolver = StatResolver::new();
+    let atk_id = StatId::from_str("ATK");
+
+    resolver.register_source(atk_id.clone(), Box::new(ConstantSource::new(100.0)));
+    resolver.register_transform(
+        atk_id.clone(),
+        Box::new(OriginTransform::new(
+            "iron_sword",
+            Box::new(AdditiveTransform::new(20.0)),
+        )),
+    );
+    resolver.register_transform(
+        atk_id.clone(),
+        Box::new(OriginTransform::new(
+            "battle_cry",
+            Box::new(MultiplicativeTransform::new(1.5)),
+        )),
+    );
+
+    let context = StatContext::new();
+    let resolved = resolver.resolve(&atk_id, &context).unwrap();
+    assert_eq!(resolved.value, 180.0);
+
+    let phases: Vec<_> = resolved.breakdown.iter().map(|p| p.phase).collect();
+    assert_eq!(
+        phases,
+        vec![TransformPhase::Additive, TransformPhase::Multiplicative]
+    );
+
+    let sword = &resolved.breakdown[0].stacks[0].transforms[0];
+    assert_eq!(sword.origin.as_deref(), Some("iron_sword"));
+    assert_eq!(sword.delta, 20.0);
+    assert_eq!(
+        (sword.value_before.to_f64(), sword.value_after.to_f64()),
+        (100.0, 120.0)
+    );
+
+    let cry = &resolved.breakdown[1].stacks[0];
+    assert_eq!(cry.rule, StackRule::Multiplicative);
+    assert_eq!(cry.transforms[0].origin.as_deref(), Some("battle_cry"));
+    assert_eq!(cry.transforms[0].delta, 60.0);
+
+    // The breakdown survives a round trip to the client
+    let json = serde_json::to_string(&resolved).unwrap();
+    let decoded: ResolvedStat = serde_json::from_str(&json).unwrap();
+    assert_eq!(decoded, resolved);
+}
+
 /// Test resolve_batch functionality.
 #[test]
 fn test_resolve_batch() {
@@ -259,10 +463,10 @@ fn test_resolve_batch() {
     let hp_id = StatId::from_str("HP");
     let mp_id = StatId::from_str("MP");
 
-    resolver.register_source(str_id.clone(), Box::new(ConstantSource(10.0)));
-    resolver.register_source(atk_id.clone(), Box::new(ConstantSource(50.0)));
-    resolver.register_source(hp_id.clone(), Box::new(ConstantSource(100.0)));
-    resolver.register_source(mp_id.clone(), Box::new(ConstantSource(50.0)));
+    resolver.register_source(str_id.clone(), Box::new(ConstantSource::new(10.0)));
+    resolver.register_source(atk_id.clone(), Box::new(ConstantSource::new(50.0)));
+    resolver.register_source(hp_id.clone(), Box::new(ConstantSource::new(100.0)));
+    resolver.register_source(mp_id.clone(), Box::new(ConstantSource::new(50.0)));
 
     // ATK depends on STR
     resolver.register_transform(
@@ -309,7 +513,7 @@ fn test_cache_invalidation() {
     let mut resolver = StatResolver::new();
     let hp_id = StatId::from_str("HP");
 
-    resolver.register_source(hp_id.clone(), Box::new(ConstantSource(100.0)));
+    resolver.register_source(hp_id.clone(), Box::new(ConstantSource::new(100.0)));
 
     let context = StatContext::new();
 
@@ -325,7 +529,7 @@ fn test_cache_invalidation() {
     resolver.invalidate(&hp_id);
 
     // Add new source
-    resolver.register_source(hp_id.clone(), Box::new(ConstantSource(50.0)));
+    resolver.register_source(hp_id.clone(), Box::new(ConstantSource::new(50.0)));
 
     // Should recalculate
     let resolved3 = resolver.resolve(&hp_id, &context).unwrap();
@@ -339,8 +543,8 @@ fn test_invalidate_all() {
     let hp_id = StatId::from_str("HP");
     let mp_id = StatId::from_str("MP");
 
-    resolver.register_source(hp_id.clone(), Box::new(ConstantSource(100.0)));
-    resolver.register_source(mp_id.clone(), Box::new(ConstantSource(50.0)));
+    resolver.register_source(hp_id.clone(), Box::new(ConstantSource::new(100.0)));
+    resolver.register_source(mp_id.clone(), Box::new(ConstantSource::new(50.0)));
 
     let context = StatContext::new();
 
@@ -367,7 +571,7 @@ fn test_transform_phase_ordering() {
     let mut resolver = StatResolver::new();
     let atk_id = StatId::from_str("ATK");
 
-    resolver.register_source(atk_id.clone(), Box::new(ConstantSource(100.0)));
+    resolver.register_source(atk_id.clone(), Box::new(ConstantSource::new(100.0)));
 
     // Register transforms in different phases
     // Final phase (clamp) should be applied last
@@ -422,7 +626,7 @@ fn test_custom_transform_phase() {
     let mut resolver = StatResolver::new();
     let atk_id = StatId::from_str("ATK");
 
-    resolver.register_source(atk_id.clone(), Box::new(ConstantSource(100.0)));
+    resolver.register_source(atk_id.clone(), Box::new(ConstantSource::new(100.0)));
 
     // Add custom phase transform (phase 10)
     resolver.register_transform(
@@ -450,7 +654,7 @@ fn test_missing_dependency() {
     let atk_id = StatId::from_str("ATK");
     let missing_id = StatId::from_str("MISSING");
 
-    resolver.register_source(atk_id.clone(), Box::new(ConstantSource(100.0)));
+    resolver.register_source(atk_id.clone(), Box::new(ConstantSource::new(100.0)));
 
     // ATK depends on MISSING (which doesn't exist - no source registered)
     // The resolver will resolve MISSING to 0 (default) and use that
@@ -509,13 +713,13 @@ fn test_multiple_forks() {
     let mut base = StatResolver::new();
     let hp_id = StatId::from_str("HP");
 
-    base.register_source(hp_id.clone(), Box::new(ConstantSource(100.0)));
+    base.register_source(hp_id.clone(), Box::new(ConstantSource::new(100.0)));
 
     let mut fork1 = base.fork();
     let mut fork2 = base.fork();
 
-    fork1.register_source(hp_id.clone(), Box::new(ConstantSource(10.0)));
-    fork2.register_source(hp_id.clone(), Box::new(ConstantSource(20.0)));
+    fork1.register_source(hp_id.clone(), Box::new(ConstantSource::new(10.0)));
+    fork2.register_source(hp_id.clone(), Box::new(ConstantSource::new(20.0)));
 
     let context = StatContext::new();
 
@@ -534,7 +738,7 @@ fn test_additive_stacking() {
     let mut resolver = StatResolver::new();
     let atk_id = StatId::from_str("ATK");
 
-    resolver.register_source(atk_id.clone(), Box::new(ConstantSource(100.0)));
+    resolver.register_source(atk_id.clone(), Box::new(ConstantSource::new(100.0)));
 
     // Register multiple additive transforms with explicit stack rule
     resolver.register_transform_with_rule(
@@ -569,7 +773,7 @@ fn test_multiplicative_stacking() {
     let mut resolver = StatResolver::new();
     let atk_id = StatId::from_str("ATK");
 
-    resolver.register_source(atk_id.clone(), Box::new(ConstantSource(100.0)));
+    resolver.register_source(atk_id.clone(), Box::new(ConstantSource::new(100.0)));
 
     // Register multiple multiplicative transforms with explicit stack rule
     resolver.register_transform_with_rule(
@@ -604,7 +808,7 @@ fn test_additive_multiplicative_combination() {
     let mut resolver = StatResolver::new();
     let atk_id = StatId::from_str("ATK");
 
-    resolver.register_source(atk_id.clone(), Box::new(ConstantSource(100.0)));
+    resolver.register_source(atk_id.clone(), Box::new(ConstantSource::new(100.0)));
 
     // Additive phase: sum of deltas
     resolver.register_transform_with_rule(
@@ -650,7 +854,7 @@ fn test_override_precedence() {
     let mut resolver = StatResolver::new();
     let atk_id = StatId::from_str("ATK");
 
-    resolver.register_source(atk_id.clone(), Box::new(ConstantSource(100.0)));
+    resolver.register_source(atk_id.clone(), Box::new(ConstantSource::new(100.0)));
 
     // Register multiple override transforms
     resolver.register_transform_with_rule(
@@ -680,7 +884,7 @@ fn test_diminishing_returns() {
     let mut resolver = StatResolver::new();
     let atk_id = StatId::from_str("ATK");
 
-    resolver.register_source(atk_id.clone(), Box::new(ConstantSource(100.0)));
+    resolver.register_source(atk_id.clone(), Box::new(ConstantSource::new(100.0)));
 
     // Create a simple transform that works with diminishing returns
     // We'll use multiplicative transforms with diminishing stack rule
@@ -704,7 +908,7 @@ fn test_min_max_clamping() {
     let mut resolver = StatResolver::new();
     let atk_id = StatId::from_str("ATK");
 
-    resolver.register_source(atk_id.clone(), Box::new(ConstantSource(100.0)));
+    resolver.register_source(atk_id.clone(), Box::new(ConstantSource::new(100.0)));
 
     // Register multiple max clamps (most restrictive wins)
     resolver.register_transform_with_rule(
@@ -728,7 +932,7 @@ fn test_min_max_clamping() {
     assert_eq!(resolved.value.to_f64(), 100.0);
 
     // Test with value above the limit
-    resolver.register_source(atk_id.clone(), Box::new(ConstantSource(200.0)));
+    resolver.register_source(atk_id.clone(), Box::new(ConstantSource::new(200.0)));
     resolver.invalidate(&atk_id);
     let resolved2 = resolver.resolve(&atk_id, &context).unwrap();
     // Should be clamped to 120 (most restrictive max)
@@ -741,7 +945,7 @@ fn test_min_clamping() {
     let mut resolver = StatResolver::new();
     let atk_id = StatId::from_str("ATK");
 
-    resolver.register_source(atk_id.clone(), Box::new(ConstantSource(50.0)));
+    resolver.register_source(atk_id.clone(), Box::new(ConstantSource::new(50.0)));
 
     // Register multiple min clamps (most restrictive wins - highest min)
     resolver.register_transform_with_rule(
@@ -766,7 +970,7 @@ fn test_min_clamping() {
 
     // Test with value below the limit - create a new resolver to avoid source accumulation
     let mut resolver2 = StatResolver::new();
-    resolver2.register_source(atk_id.clone(), Box::new(ConstantSource(20.0)));
+    resolver2.register_source(atk_id.clone(), Box::new(ConstantSource::new(20.0)));
     resolver2.register_transform_with_rule(
         atk_id.clone(),
         TransformPhase::Final,
@@ -790,7 +994,7 @@ fn test_additive_stacking_zero_base() {
     let mut resolver = StatResolver::new();
     let atk_id = StatId::from_str("ATK");
 
-    resolver.register_source(atk_id.clone(), Box::new(ConstantSource(0.0)));
+    resolver.register_source(atk_id.clone(), Box::new(ConstantSource::new(0.0)));
 
     resolver.register_transform_with_rule(
         atk_id.clone(),
@@ -818,7 +1022,7 @@ fn test_additive_stacking_negative() {
     let mut resolver = StatResolver::new();
     let atk_id = StatId::from_str("ATK");
 
-    resolver.register_source(atk_id.clone(), Box::new(ConstantSource(100.0)));
+    resolver.register_source(atk_id.clone(), Box::new(ConstantSource::new(100.0)));
 
     resolver.register_transform_with_rule(
         atk_id.clone(),
@@ -849,7 +1053,7 @@ fn test_minmax_stack_rule() {
     let mut resolver = StatResolver::new();
     let crit_id = StatId::from_str("CRIT");
 
-    resolver.register_source(crit_id.clone(), Box::new(ConstantSource(0.9))); // 90% crit
+    resolver.register_source(crit_id.clone(), Box::new(ConstantSource::new(0.9))); // 90% crit
 
     // Register multiple clamp transforms with MinMax stack rule
     resolver.register_transform_with_rule(
@@ -888,7 +1092,7 @@ fn test_minmax_stack_rule_mixed_bounds() {
     let mut resolver = StatResolver::new();
     let move_speed_id = StatId::from_str("MOVE_SPEED");
 
-    resolver.register_source(move_speed_id.clone(), Box::new(ConstantSource(50.0)));
+    resolver.register_source(move_speed_id.clone(), Box::new(ConstantSource::new(50.0)));
 
     // Register min-only and max-only clamps with MinMax stack rule
     resolver.register_transform_with_rule(
@@ -911,7 +1115,7 @@ fn test_minmax_stack_rule_mixed_bounds() {
     assert_eq!(resolved.value.to_f64(), 100.0);
 
     // Test with value above max
-    resolver.register_source(move_speed_id.clone(), Box::new(ConstantSource(250.0)));
+    resolver.register_source(move_speed_id.clone(), Box::new(ConstantSource::new(250.0)));
     resolver.invalidate(&move_speed_id);
     let resolved2 = resolver.resolve(&move_speed_id, &context).unwrap();
     // 250 should be clamped to 200 (max cap)
@@ -927,7 +1131,7 @@ fn test_clamp_with_additive() {
     let mut resolver = StatResolver::new();
     let atk_id = StatId::from_str("ATK");
 
-    resolver.register_source(atk_id.clone(), Box::new(ConstantSource(100.0)));
+    resolver.register_source(atk_id.clone(), Box::new(ConstantSource::new(100.0)));
 
     // Additive transform first
     resolver.register_transform_with_rule(
@@ -964,7 +1168,7 @@ fn test_clamp_with_multiplicative() {
     let mut resolver = StatResolver::new();
     let atk_id = StatId::from_str("ATK");
 
-    resolver.register_source(atk_id.clone(), Box::new(ConstantSource(100.0)));
+    resolver.register_source(atk_id.clone(), Box::new(ConstantSource::new(100.0)));
 
     // Multiplicative transform
     resolver.register_transform_with_rule(
@@ -1001,7 +1205,7 @@ fn test_multiple_clamps_minmax_composition() {
     let mut resolver = StatResolver::new();
     let stat_id = StatId::from_str("STAT");
 
-    resolver.register_source(stat_id.clone(), Box::new(ConstantSource(150.0)));
+    resolver.register_source(stat_id.clone(), Box::new(ConstantSource::new(150.0)));
 
     // Multiple clamps with different bounds
     resolver.register_transform_with_rule(
@@ -1035,7 +1239,7 @@ fn test_multiple_clamps_minmax_composition() {
     // Create a new resolver with the same transforms but different source value
     // (since register_source adds sources additively, we need a fresh resolver)
     let mut resolver2 = StatResolver::new();
-    resolver2.register_source(stat_id.clone(), Box::new(ConstantSource(20.0)));
+    resolver2.register_source(stat_id.clone(), Box::new(ConstantSource::new(20.0)));
     resolver2.register_transform_with_rule(
         stat_id.clone(),
         TransformPhase::Final,
@@ -1069,8 +1273,8 @@ fn test_clamp_deterministic_ordering() {
     let mut resolver2 = StatResolver::new();
     let stat_id = StatId::from_str("STAT");
 
-    resolver1.register_source(stat_id.clone(), Box::new(ConstantSource(150.0)));
-    resolver2.register_source(stat_id.clone(), Box::new(ConstantSource(150.0)));
+    resolver1.register_source(stat_id.clone(), Box::new(ConstantSource::new(150.0)));
+    resolver2.register_source(stat_id.clone(), Box::new(ConstantSource::new(150.0)));
 
     // Register same clamps in different order
     resolver1.register_transform_with_rule(
@@ -1123,3 +1327,487 @@ fn test_clamp_deterministic_ordering() {
     assert_eq!(resolved2.value.to_f64(), 120.0);
     assert_eq!(resolved1.value, resolved2.value);
 }
+
+/// Test equipping and unequipping an item through registration handles.
+#[test]
+fn test_unequip_item_with_handles() {
+    let mut resolver = StatResolver::new();
+    let str_id = StatId::from_str("STR");
+    let atk_id = StatId::from_str("ATK");
+
+    resolver.register_source(str_id.clone(), Box::new(ConstantSource::new(10.0)));
+    resolver.register_source(atk_id.clone(), Box::new(ConstantSource::new(50.0)));
+    resolver.register_transform(
+        atk_id.clone(),
+        Box::new(ScalingTransform::new(str_id.clone(), 2.0)),
+    );
+
+    // Equip a sword: +20 ATK flat and +10% ATK
+    let sword_source =
+        resolver.register_source(atk_id.clone(), Box::new(ConstantSource::new(20.0)));
+    let sword_mult =
+        resolver.register_transform(atk_id.clone(), Box::new(MultiplicativeTransform::new(1.1)));
+
+    let context = StatContext::new();
+    let equipped = resolver.resolve(&atk_id, &context).unwrap();
+    assert!((equipped.value.to_f64() - 99.0).abs() < 1e-9); // (50 + 20 + 10 * 2) * 1.1
+
+    // Unequip
+    assert!(resolver.remove_source(&sword_source));
+    assert!(resolver.remove_transform(&sword_mult));
+
+    let unequipped = resolver.resolve(&atk_id, &context).unwrap();
+    assert_eq!(unequipped.value, 70.0); // 50 + 10 * 2
+}
+
+/// Test that removing an entry from one fork does not affect other forks.
+#[test]
+fn test_remove_in_fork_isolation() {
+    let mut base = StatResolver::new();
+    let hp_id = StatId::from_str("HP");
+
+    let base_source = base.register_source(hp_id.clone(), Box::new(ConstantSource::new(100.0)));
+
+    let mut fork1 = base.fork();
+    let mut fork2 = base.fork();
+
+    let fork1_source = fork1.register_source(hp_id.clone(), Box::new(ConstantSource::new(25.0)));
+    assert!(fork1.remove_source(&base_source));
+
+    // A handle from fork1 does not exist in fork2
+    assert!(!fork2.remove_source(&fork1_source));
+
+    let context = StatContext::new();
+    assert_eq!(fork1.resolve(&hp_id, &context).unwrap().value, 25.0);
+    assert_eq!(fork2.resolve(&hp_id, &context).unwrap().value, 100.0);
+    assert_eq!(base.resolve(&hp_id, &context).unwrap().value, 100.0);
+}
+
+/// Test that a conditional transform re-evaluates when the context changes.
+#[test]
+fn test_conditional_transform_follows_context() {
+    let mut resolver = StatResolver::new();
+    let hp_id = StatId::from_str("HP");
+
+    resolver.register_source(hp_id.clone(), Box::new(ConstantSource::new(100.0)));
+    resolver.register_transform(
+        hp_id.clone(),
+        Box::new(
+            ConditionalTransform::new(
+                |ctx| ctx.get::<bool>("in_combat").unwrap_or(false),
+                Box::new(MultiplicativeTransform::new(1.5)),
+                "combat bonus",
+            )
+            .with_context_keys(["in_combat"]),
+        ),
+    );
+
+    let mut out_of_combat = StatContext::new();
+    out_of_combat.set("in_combat", false);
+    let mut in_combat = StatContext::new();
+    in_combat.set("in_combat", true);
+
+    assert_eq!(
+        resolver.resolve(&hp_id, &out_of_combat).unwrap().value,
+        100.0
+    );
+    assert_eq!(resolver.resolve(&hp_id, &in_combat).unwrap().value, 150.0);
+    assert_eq!(
+        resolver.resolve(&hp_id, &out_of_combat).unwrap().value,
+        100.0
+    );
+
+    let all = resolver.resolve_all(&in_combat).unwrap();
+    assert_eq!(all[&hp_id].value, 150.0);
+}
+
+/// Test loading stat definitions from a JSON file and reporting errors by location.
+#[test]
+fn test_definitions_from_json_file() {
+    let dir = std::env::temp_dir().join(format!("zzstat-definitions-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let valid = dir.join("stats.json");
+    std::fs::write(
+        &valid,
+        r#"{
+            "stats": [
+                { "id": "STR", "sources": [{ "type": "constant", "value": 10 }] },
+                {
+                    "id": "ATK",
+                    "sources": [{ "type": "constant", "value": 5, "description": "Weapon" }],
+                    "transforms": [
+                        { "type": "scaling", "stat": "STR", "factor": 2 },
+                        { "type": "multiplicative", "value": 1.5 },
+                        { "type": "clamp", "max": 40, "phase": "Final" }
+                    ]
+                }
+            ]
+        }"#,
+    )
+    .unwrap();
+
+    let definitions = StatDefinitions::from_json_file(&valid).unwrap();
+    let mut resolver = definitions.build_resolver().unwrap();
+    let atk = resolver
+        .resolve(&StatId::from_str("ATK"), &StatContext::new())
+        .unwrap();
+    assert_eq!(atk.value, 37.5); // (5 + 10 * 2) * 1.5
+    assert_eq!(atk.sources[0].0, "Weapon");
+
+    let invalid = dir.join("broken.json");
+    std::fs::write(&invalid, "{\n  \"stats\": [\n    { \"id\": \"HP\" \n  ]\n}").unwrap();
+    let err = StatDefinitions::from_json_file(&invalid).unwrap_err();
+    match err {
+        StatError::Definition { location, .. } => {
+            assert!(location.starts_with(&format!("{}:4:", invalid.display())));
+        }
+        other => panic!("Expected Definition error, got {:?}", other),
+    }
+
+    let unknown = dir.join("unknown.json");
+    std::fs::write(
+        &unknown,
+        r#"{ "stats": [{ "id": "HP", "transforms": [{ "type": "clamp" }] }] }"#,
+    )
+    .unwrap();
+    let Err(err) = StatDefinitions::from_json_file(&unknown)
+        .unwrap()
+        .build_resolver()
+    else {
+        panic!("Expected clamp without bounds to be rejected");
+    };
+    assert!(err
+        .to_string()
+        .contains("unknown.json: stats[0] (HP).transforms[0]"));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+/// Test formula transforms resolving through the dependency graph.
+#[test]
+fn test_expression_transforms() {
+    let mut resolver = StatResolver::new();
+    let str_id = StatId::from_str("STR");
+    let dex_id = StatId::from_str("DEX");
+    let atk_id = StatId::from_str("ATK");
+    let crit_id = StatId::from_str("CRIT");
+
+    resolver.register_source(str_id.clone(), Box::new(ConstantSource::new(10.0)));
+    resolver.register_source(dex_id.clone(), Box::new(ConstantSource::new(20.0)));
+    resolver.register_source(atk_id.clone(), Box::new(ConstantSource::new(5.0)));
+    resolver.register_transform(
+        atk_id.clone(),
+        Box::new(ExpressionTransform::parse("STR * 2 + DEX").unwrap()),
+    );
+    resolver.register_transform(
+        crit_id.clone(),
+        Box::new(ExpressionTransform::parse("ATK / (ATK + 45)").unwrap()),
+    );
+
+    let context = StatContext::new();
+    let results = resolver.resolve_all(&context).unwrap();
+    assert_eq!(results[&atk_id].value, 45.0);
+    assert_eq!(results[&crit_id].value, 0.5);
+
+    // Dependencies come from the formula, so cycles are caught at registration
+    let err = resolver
+        .try_register_transform(
+            str_id.clone(),
+            Box::new(ExpressionTransform::parse("CRIT * 10").unwrap()),
+        )
+        .unwrap_err();
+    assert!(matches!(err, StatError::Cycle { .. }));
+}
+
+/// Test timed buffs expiring as the caller advances the clock.
+#[test]
+fn test_timed_buffs_expire_on_tick() {
+    let mut resolver = StatResolver::new();
+    let str_id = StatId::from_str("STR");
+    let atk_id = StatId::from_str("ATK");
+    let hp_id = StatId::from_str("HP");
+    resolver.register_source(str_id.clone(), Box::new(ConstantSource::new(10.0)));
+    resolver.register_source(hp_id.clone(), Box::new(ConstantSource::new(100.0)));
+    resolver.register_transform(
+        atk_id.clone(),
+        Box::new(ScalingTransform::new(str_id.clone(), 3.0)),
+    );
+
+    let mut buffs = BuffTracker::new();
+    let might = compile_bonus::<StatValue>(
+        &Bonus::add(str_id.clone())
+            .flat(10.0)
+            .in_phase(TransformPhase::Additive),
+    );
+    let fortitude = compile_bonus::<StatValue>(
+        &Bonus::mul(hp_id.clone())
+            .percent(0.5)
+            .in_phase(TransformPhase::Multiplicative),
+    );
+    buffs.apply_for(&mut resolver, &might, 1_000, 500);
+    buffs.apply_for(&mut resolver, &fortitude, 1_200, 300);
+
+    let context = StatContext::new();
+    let stats = resolver
+        .resolve_batch(&[atk_id.clone(), hp_id.clone()], &context)
+        .unwrap();
+    assert_eq!(stats[&atk_id].value, 60.0);
+    assert_eq!(stats[&hp_id].value, 150.0);
+
+    // Both expire on the same tick; a late tick still removes them
+    assert_eq!(buffs.next_expiry(), Some(1_500));
+    let changed = buffs.tick(&mut resolver, 2_000);
+    assert_eq!(
+        changed.into_iter().collect::<Vec<_>>(),
+        vec![atk_id.clone(), hp_id.clone(), str_id.clone()]
+    );
+    assert!(buffs.is_empty());
+
+    let stats = resolver
+        .resolve_batch(&[atk_id.clone(), hp_id.clone()], &context)
+        .unwrap();
+    assert_eq!(stats[&atk_id].value, 30.0);
+    assert_eq!(stats[&hp_id].value, 100.0);
+}
+
+/// Test stacking buffs loaded from data, with stacks from several casters.
+#[test]
+fn test_stacking_buffs_from_data() {
+    let buff: BuffDefinition = serde_json::from_str(
+        r#"{
+            "id": "poison",
+            "bonuses": [
+                {"target": "REGEN", "operation": "Add", "value": {"Flat": -2.0}, "phase": "Additive"}
+            ],
+            "duration": 30,
+            "max_stacks": 3,
+            "policy": "Refresh"
+        }"#,
+    )
+    .unwrap();
+
+    let mut resolver = StatResolver::new();
+    let regen_id = StatId::from_str("REGEN");
+    resolver.register_source(regen_id.clone(), Box::new(ConstantSource::new(5.0)));
+
+    let mut buffs = BuffTracker::new();
+    for now in 0..4 {
+        buffs.apply_buff(&mut resolver, &buff, 7, now);
+    }
+    buffs.apply_buff(&mut resolver, &buff, 8, 10);
+
+    let context = StatContext::new();
+    // Three stacks from caster 7, one from caster 8
+    assert_eq!(resolver.resolve(&regen_id, &context).unwrap().value, -3.0);
+
+    buffs.tick(&mut resolver, 33);
+    assert_eq!(buffs.stacks("poison", 7), 0);
+    assert_eq!(buffs.stacks("poison", 8), 1);
+    assert_eq!(resolver.resolve(&regen_id, &context).unwrap().value, 3.0);
+}
+
+/// Test a resource pool following its max stat through a timed buff.
+#[test]
+fn test_resource_pool_follows_max_stat() {
+    let mut resolver = StatResolver::new();
+    let vit_id = StatId::from_str("VIT");
+    let max_hp_id = StatId::from_str("MAX_HP");
+    resolver.register_source(vit_id.clone(), Box::new(ConstantSource::new(10.0)));
+    resolver.register_transform(
+        max_hp_id.clone(),
+        Box::new(ScalingTransform::new(vit_id.clone(), 10.0)),
+    );
+
+    let context = StatContext::new();
+    let mut hp = ResourcePool::from_resolver(
+        &mut resolver,
+        max_hp_id.clone(),
+        MaxChangePolicy::KeepMissing,
+        &context,
+    )
+    .unwrap();
+    hp.damage(30.0);
+
+    // +5 VIT for 10 ticks raises MAX_HP by 50, and current HP with it
+    let mut buffs = BuffTracker::new();
+    let fortitude = compile_bonus::<StatValue>(
+        &Bonus::add(vit_id.clone())
+            .flat(5.0)
+            .in_phase(TransformPhase::Additive),
+    );
+    buffs.apply_for(&mut resolver, &fortitude, 0, 10);
+    assert!(hp.sync(&mut resolver, &context).unwrap());
+    assert_eq!((hp.current().to_f64(), hp.max().to_f64()), (120.0, 150.0));
+    assert!(!hp.sync(&mut resolver, &context).unwrap());
+
+    // Only re-sync when the tick reports the pool's max stat changed
+    let changed = buffs.tick(&mut resolver, 10);
+    assert!(changed.contains(hp.max_stat()));
+    hp.sync(&mut resolver, &context).unwrap();
+    assert_eq!((hp.current().to_f64(), hp.max().to_f64()), (70.0, 100.0));
+}
+
+/// Test a rating curve diminishing the summed contributions of several sources.
+#[test]
+fn test_diminishing_returns_rating_curve() {
+    let mut resolver = StatResolver::new();
+    let rating_id = StatId::from_str("CRIT_RATING");
+    let crit_id = StatId::from_str("CRIT");
+    let curve = PiecewiseCurve::new(&[(30.0, 0.9), (39.0, 0.8), (47.0, 0.7)]).unwrap();
+
+    for rating in [20.0, 15.0, 15.0] {
+        resolver.register_source(rating_id.clone(), Box::new(ConstantSource::new(rating)));
+    }
+    resolver.register_transform_with_rule(
+        crit_id.clone(),
+        TransformPhase::Additive,
+        StackRule::DiminishingReturns(DiminishingMode::Piecewise(curve.clone())),
+        Box::new(ScalingTransform::new(rating_id.clone(), 1.0)),
+    );
+    resolver.register_transform_with_rule(
+        crit_id.clone(),
+        TransformPhase::Additive,
+        StackRule::DiminishingReturns(DiminishingMode::Piecewise(curve)),
+        Box::new(AdditiveTransform::new(10.0)),
+    );
+
+    let context = StatContext::new();
+    let crit = resolver.resolve(&crit_id, &context).unwrap();
+    // Raw 60: 30 + 9 * 0.9 + 8 * 0.8 + 13 * 0.7
+    assert!((crit.value.to_f64() - 53.6).abs() < 1e-9);
+
+    let stack = &crit.breakdown[0].stacks[0];
+    let diminishing = stack.diminishing.unwrap();
+    assert_eq!(diminishing.raw, 60.0);
+    assert!((diminishing.diminished.to_f64() - 53.6).abs() < 1e-9);
+    // Shares follow the raw contributions: 50 of 60 from rating
+    let tolerance = if cfg!(feature = "fixed-point") {
+        1e-4
+    } else {
+        1e-9
+    };
+    assert!((stack.transforms[0].delta.to_f64() - 53.6 * 50.0 / 60.0).abs() < tolerance);
+}
+
+/// Test named custom phases that run before the final caps.
+#[test]
+fn test_named_phases_before_caps() {
+    let phases = PhaseRegistry::from_phases([
+        ("base", TransformPhase::Additive),
+        ("gear", TransformPhase::Custom(3)),
+        ("buff", TransformPhase::Custom(4)),
+        ("aura", TransformPhase::Multiplicative),
+        ("cap", TransformPhase::Final),
+    ])
+    .unwrap();
+    let mut resolver = StatResolver::with_phases(phases);
+    let crit_id = StatId::from_str("CRIT_CHANCE");
+    resolver.register_source(crit_id.clone(), Box::new(ConstantSource::new(0.10)));
+
+    let phase = |name: &str| resolver.phases().phase(name).unwrap();
+    let (gear, buff) = (phase("gear"), phase("buff"));
+    let bonuses = [
+        Bonus::add(crit_id.clone()).flat(0.30).in_phase(gear),
+        Bonus::mul(crit_id.clone()).percent(0.50).in_phase(buff),
+        Bonus::clamp_max(crit_id.clone(), 0.75).in_phase(phase("cap")),
+    ];
+    for bonus in &bonuses {
+        apply_compiled_bonus(&mut resolver, &compile_bonus::<StatValue>(bonus));
+    }
+
+    let context = StatContext::new();
+    let crit = resolver.resolve(&crit_id, &context).unwrap();
+    // (0.10 + 0.30) * 1.5 = 0.60, under the cap
+    assert!((crit.value.to_f64() - 0.60).abs() < 1e-9);
+    let order: Vec<_> = crit.breakdown.iter().map(|p| p.phase).collect();
+    assert_eq!(order, [gear, buff, TransformPhase::Final]);
+
+    resolver.register_transform_in_phase(
+        crit_id.clone(),
+        buff,
+        Box::new(MultiplicativeTransform::new(2.0)),
+    );
+    let crit = resolver.resolve(&crit_id, &context).unwrap();
+    assert_eq!(crit.value, 0.75);
+}
+
+/// Test f64 and fixed-point resolvers side by side in one binary.
+#[test]
+fn test_mixed_numeric_backends() {
+    let str_id = StatId::from_str("STR");
+    let atk_id = StatId::from_str("ATK");
+    let context = StatContext::new();
+    let bonus = Bonus::mul(atk_id.clone())
+        .percent(0.25)
+        .in_phase(TransformPhase::Multiplicative);
+
+    let mut preview: StatResolver<f64> = StatResolver::default();
+    preview.register_source(str_id.clone(), Box::new(ConstantSource::new(10.0)));
+    preview.register_source(atk_id.clone(), Box::new(ConstantSource::new(50.0)));
+    preview.register_transform(
+        atk_id.clone(),
+        Box::new(ScalingTransform::from_value(str_id.clone(), 2.0)),
+    );
+    apply_compiled_bonus(&mut preview, &compile_bonus::<f64>(&bonus));
+
+    let mut authority: StatResolver<FixedPoint> = StatResolver::default();
+    authority.register_source(
+        str_id.clone(),
+        Box::new(ConstantSource::new(FixedPoint::from_int(10))),
+    );
+    authority.register_source(
+        atk_id.clone(),
+        Box::new(ConstantSource::new(FixedPoint::from_int(50))),
+    );
+    authority.register_transform(
+        atk_id.clone(),
+        Box::new(ScalingTransform::from_value(
+            str_id.clone(),
+            FixedPoint::from_int(2),
+        )),
+    );
+    apply_compiled_bonus(&mut authority, &compile_bonus::<FixedPoint>(&bonus));
+
+    // (50 + 10 * 2) * 1.25 = 87.5
+    let preview_atk = preview.resolve(&atk_id, &context).unwrap();
+    let authority_atk = authority.resolve(&atk_id, &context).unwrap();
+    assert_eq!(preview_atk.value, 87.5);
+    assert_eq!(authority_atk.value, FixedPoint::from_f64(87.5));
+    assert_eq!(preview_atk.value, authority_atk.value.to_f64());
+}
+
+#[test]
+fn test_content_is_exact_in_fixed_point_resolver() {
+    // 18 significant digits don't survive a round trip through f64
+    let hp_id = StatId::from_str("HP");
+    let context = StatContext::new();
+    let bonus = Bonus::add(hp_id.clone())
+        .flat("1234567890.12345678".parse::<Decimal>().unwrap())
+        .in_phase(TransformPhase::Additive);
+
+    let mut authority: StatResolver<FixedPoint> = StatResolver::default();
+    authority.register_source(
+        hp_id.clone(),
+        Box::new(ConstantSource::new(FixedPoint::zero())),
+    );
+    apply_compiled_bonus(&mut authority, &compile_bonus::<FixedPoint>(&bonus));
+    assert_eq!(
+        authority.resolve(&hp_id, &context).unwrap().value,
+        "1234567890.12345678".parse::<FixedPoint>().unwrap()
+    );
+
+    let definitions = StatDefinitions::from_json_str(
+        r#"{ "stats": [
+            { "id": "BASE", "sources": [{ "type": "constant", "value": "0.1" }] },
+            { "id": "HP", "transforms": [{ "type": "expression", "formula": "BASE * 3.3" }] }
+        ] }"#,
+    )
+    .unwrap();
+    let mut resolver: StatResolver<FixedPoint> = StatResolver::default();
+    definitions.register_into(&mut resolver).unwrap();
+    assert_eq!(
+        resolver.resolve(&hp_id, &context).unwrap().value,
+        "0.33".parse::<FixedPoint>().unwrap()
+    );
+}